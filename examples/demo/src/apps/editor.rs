@@ -196,14 +196,14 @@ impl Editor {
 
                     if ui.button("Delete").clicked() {
                         let event = match context.property {
-                            JsonPointerSegment::Key(key) => EditEvent::DeleteFromObject {
-                                object_pointer: parent.to_json_pointer_string(),
-                                key: key.to_string(),
-                            },
                             JsonPointerSegment::Index(idx) => EditEvent::DeleteFromArray {
                                 array_pointer: parent.to_json_pointer_string(),
                                 idx,
                             },
+                            key => EditEvent::DeleteFromObject {
+                                object_pointer: parent.to_json_pointer_string(),
+                                key: key.to_string(),
+                            },
                         };
                         self.edit_events.push(event);
                         ui.close_menu();
@@ -1,5 +1,5 @@
 use egui::Ui;
-use egui_json_tree::{DefaultExpand, JsonTree};
+use egui_json_tree::{DefaultExpand, JsonTree, SearchOptions, SearchScope, SearchWords};
 use serde_json::Value;
 
 use super::Show;
@@ -7,6 +7,13 @@ use super::Show;
 pub struct SearchExample {
     value: Value,
     search_input: String,
+    filter: bool,
+    case_sensitive: bool,
+    fuzzy: bool,
+    whole_word: bool,
+    query_syntax: bool,
+    scope: SearchScope,
+    words: SearchWords,
 }
 
 impl SearchExample {
@@ -14,6 +21,13 @@ impl SearchExample {
         Self {
             value,
             search_input: "".to_string(),
+            filter: false,
+            case_sensitive: false,
+            fuzzy: false,
+            whole_word: false,
+            query_syntax: false,
+            scope: SearchScope::KeysAndValues,
+            words: SearchWords::Phrase,
         }
     }
 }
@@ -36,12 +50,64 @@ impl Show for SearchExample {
                 (text_edit_response, clear_button_response)
             })
             .inner;
+        ui.checkbox(&mut self.filter, "Hide non-matching entries");
+        let case_sensitive_response = ui.checkbox(&mut self.case_sensitive, "Match case");
+        let fuzzy_response = ui.checkbox(&mut self.fuzzy, "Fuzzy matching");
+        let whole_word_response = ui.add_enabled(
+            !self.fuzzy,
+            egui::Checkbox::new(&mut self.whole_word, "Match whole words"),
+        );
+        let query_syntax_response = ui.checkbox(
+            &mut self.query_syntax,
+            "Query syntax, e.g. type:null or error -debug",
+        );
+        let mut scope_changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Match:");
+            for (scope, text) in [
+                (SearchScope::KeysAndValues, "Keys and values"),
+                (SearchScope::Keys, "Keys"),
+                (SearchScope::Values, "Values"),
+            ] {
+                scope_changed |= ui.selectable_value(&mut self.scope, scope, text).changed();
+            }
+        });
+        let mut words_changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Words:");
+            for (words, text) in [
+                (SearchWords::Phrase, "As a phrase"),
+                (SearchWords::AllInKeyOrValue, "All in the same key or value"),
+                (SearchWords::AllInSubtree, "All in the same subtree"),
+            ] {
+                words_changed |= ui.selectable_value(&mut self.words, words, text).changed();
+            }
+        });
 
         let response = JsonTree::new(self.title(), &self.value)
-            .default_expand(DefaultExpand::SearchResults(&self.search_input))
+            .default_expand(DefaultExpand::SearchResultsWith(
+                &self.search_input,
+                SearchOptions {
+                    case_sensitive: self.case_sensitive,
+                    fuzzy: self.fuzzy,
+                    whole_word: self.whole_word,
+                    query_syntax: self.query_syntax,
+                    scope: self.scope,
+                    words: self.words,
+                    ..Default::default()
+                },
+            ))
+            .filter_search_results(self.filter)
             .show(ui);
 
-        if text_edit_response.changed() {
+        if text_edit_response.changed()
+            || case_sensitive_response.changed()
+            || fuzzy_response.changed()
+            || whole_word_response.changed()
+            || query_syntax_response.changed()
+            || scope_changed
+            || words_changed
+        {
             response.reset_expanded(ui);
         }
 
@@ -50,6 +116,25 @@ impl Show for SearchExample {
             response.reset_expanded(ui);
         }
 
+        if !self.search_input.is_empty() {
+            ui.horizontal(|ui| {
+                match response.current_match() {
+                    Some(current) => ui.label(format!(
+                        "{} of {} matches",
+                        current + 1,
+                        response.search_match_count()
+                    )),
+                    None => ui.label(format!("{} matches", response.search_match_count())),
+                };
+                if ui.button("Previous").clicked() {
+                    response.scroll_to_previous_match(ui.ctx());
+                }
+                if ui.button("Next").clicked() {
+                    response.scroll_to_next_match(ui.ctx());
+                }
+            });
+        }
+
         if ui.button("Reset expanded").clicked() {
             response.reset_expanded(ui);
         }
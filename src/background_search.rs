@@ -0,0 +1,135 @@
+use std::{collections::HashSet, sync::Arc};
+
+use egui::{mutex::Mutex, Context, Id};
+
+use crate::{search::SearchTerm, value::ToJsonTreeValue, JsonTreeDocument, SearchOptions};
+
+/// Searches a [`JsonTreeDocument`] on a background thread, so that searching a very large document
+/// does not block the UI while the user types.
+///
+/// This is a cheaply cloneable handle. Request a search whenever the search term or the document may have changed,
+/// and subscribe a tree with [`JsonTree::background_search`](crate::JsonTree::background_search):
+/// ```rust
+/// # use egui_json_tree::{DefaultExpand, JsonTree, JsonTreeBackgroundSearch, JsonTreeDocument, SearchOptions};
+/// # egui::__run_test_ui(|ui| {
+/// # let mut search_input = String::new();
+/// let document = JsonTreeDocument::new(serde_json::json!({ "id": 1 }));
+/// let search = JsonTreeBackgroundSearch::new();
+///
+/// ui.text_edit_singleline(&mut search_input);
+/// // Does nothing unless the search term or the document changed since the last request.
+/// search.request(ui.ctx(), &document, search_input.as_str(), SearchOptions::default());
+///
+/// let snapshot = document.load();
+/// JsonTree::new("large-tree", &*snapshot)
+///     .default_expand(DefaultExpand::SearchResults(""))
+///     .background_search(&search)
+///     .show(ui);
+/// # });
+/// ```
+///
+/// While a search is running, the tree shows the results of the previous search, preceded by a "Searching..." indicator.
+/// When it completes, the arrays/objects containing its matches are expanded.
+///
+/// Each request spawns a thread with [`std::thread::spawn`], so this is not supported on the web.
+#[derive(Clone, Default)]
+pub struct JsonTreeBackgroundSearch {
+    shared: Arc<Mutex<BackgroundSearchState>>,
+}
+
+#[derive(Default)]
+struct BackgroundSearchState {
+    /// The search term, options and document version of the latest request.
+    requested: Option<(String, SearchOptions, u64)>,
+    /// Incremented whenever a search is requested.
+    generation: u64,
+    completed: Option<Arc<CompletedSearch>>,
+}
+
+/// The results of a search that completed on a background thread.
+pub(crate) struct CompletedSearch {
+    /// The generation of the request that this search completed.
+    pub(crate) generation: u64,
+    pub(crate) term: String,
+    pub(crate) options: SearchOptions,
+    /// The JSON Pointer strings of the matched object entries and array elements, in document order.
+    pub(crate) matched_pointers: Vec<String>,
+}
+
+impl JsonTreeBackgroundSearch {
+    /// Creates a new [`JsonTreeBackgroundSearch`] that has not searched anything yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Searches the current version of the document for the search term on a background thread,
+    /// unless the same search of the same version was already requested.
+    /// The results of any search still running are discarded.
+    ///
+    /// A repaint of the [`Context`] is requested when the search completes.
+    pub fn request<T: ToJsonTreeValue + Send + Sync + 'static>(
+        &self,
+        ctx: &Context,
+        document: &JsonTreeDocument<T>,
+        term: impl Into<String>,
+        options: SearchOptions,
+    ) {
+        let term = term.into();
+        let version = document.version();
+        let generation = {
+            let mut state = self.shared.lock();
+            let request = (term.clone(), options, version);
+            if state.requested.as_ref() == Some(&request) {
+                return;
+            }
+            state.requested = Some(request);
+            state.generation += 1;
+            state.generation
+        };
+
+        let shared = Arc::clone(&self.shared);
+        let value = document.load();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let matched_pointers = SearchTerm::parse(&term, options)
+                .map(|search_term| {
+                    search_term
+                        .find_matching_paths_in(
+                            &*value,
+                            &[],
+                            true,
+                            &|path_segments| Id::new(path_segments),
+                            &mut HashSet::new(),
+                        )
+                        .matched_pointers
+                })
+                .unwrap_or_default();
+
+            let mut state = shared.lock();
+            if state.generation == generation {
+                state.completed = Some(Arc::new(CompletedSearch {
+                    generation,
+                    term,
+                    options,
+                    matched_pointers,
+                }));
+                ctx.request_repaint();
+            }
+        });
+    }
+
+    /// Returns whether a requested search has not completed yet.
+    pub fn is_searching(&self) -> bool {
+        let state = self.shared.lock();
+        state.requested.is_some()
+            && state
+                .completed
+                .as_ref()
+                .map_or(true, |completed| completed.generation != state.generation)
+    }
+
+    /// Returns the results of the latest search that completed, if any.
+    pub(crate) fn completed(&self) -> Option<Arc<CompletedSearch>> {
+        self.shared.lock().completed.clone()
+    }
+}
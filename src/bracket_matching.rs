@@ -0,0 +1,82 @@
+use std::cell::Cell;
+
+use egui::{layers::ShapeIdx, Context, Id, Response, Shape, Ui};
+
+use crate::{delimiters::ExpandableDelimiter, identity::PathIds, pointer::JsonPointer};
+
+/// Highlights the opening and closing delimiters of the array/object whose delimiter is hovered.
+/// See [`JsonTreeStyle::bracket_matching`](crate::JsonTreeStyle::bracket_matching).
+pub(crate) struct BracketMatching {
+    path_ids: PathIds,
+    /// The id of the path of the array/object whose delimiter was hovered last frame.
+    hovered: Option<Id>,
+    /// The id of the path of the array/object whose delimiter is hovered this frame.
+    hovered_now: Cell<Option<Id>>,
+}
+
+impl BracketMatching {
+    pub(crate) fn load(ctx: &Context, path_ids: PathIds) -> Self {
+        let hovered = ctx.data(|d| d.get_temp::<Id>(Self::state_id(&path_ids)));
+        Self {
+            path_ids,
+            hovered,
+            hovered_now: Cell::new(None),
+        }
+    }
+
+    fn state_id(path_ids: &PathIds) -> Id {
+        path_ids.tree_state_id().with("hovered_delimiter")
+    }
+
+    /// Returns whether a delimiter of the array/object with this path id was hovered last frame.
+    pub(crate) fn is_hovered(&self, path_id: Id) -> bool {
+        self.hovered == Some(path_id)
+    }
+
+    /// Records whether the delimiter of the response is hovered,
+    /// and highlights it in the background shape reserved before it was rendered if its array/object is hovered.
+    pub(crate) fn attach(
+        &self,
+        ui: &Ui,
+        response: &Response,
+        pointer: JsonPointer,
+        delimiter: ExpandableDelimiter,
+        background: ShapeIdx,
+    ) {
+        if !matches!(
+            delimiter,
+            ExpandableDelimiter::OpeningArray
+                | ExpandableDelimiter::ClosingArray
+                | ExpandableDelimiter::OpeningObject
+                | ExpandableDelimiter::ClosingObject
+        ) {
+            return;
+        }
+
+        let path_id = self.path_ids.id(pointer.0);
+        let hovered = response.hovered();
+        if hovered {
+            self.hovered_now.set(Some(path_id));
+        }
+        if hovered || self.is_hovered(path_id) {
+            ui.painter().set(
+                background,
+                Shape::rect_filled(response.rect, 2.0, ui.visuals().selection.bg_fill),
+            );
+        }
+    }
+
+    /// Stores the delimiter hovered this frame, so that both of its array's/object's delimiters are highlighted next frame.
+    pub(crate) fn store(&self, ctx: &Context) {
+        let hovered_now = self.hovered_now.get();
+        if hovered_now == self.hovered {
+            return;
+        }
+        let id = Self::state_id(&self.path_ids);
+        ctx.data_mut(|d| match hovered_now {
+            Some(path_id) => d.insert_temp(id, path_id),
+            None => d.remove::<Id>(id),
+        });
+        ctx.request_repaint();
+    }
+}
@@ -0,0 +1,71 @@
+use egui::{CursorIcon, Id, Rangef, Rect, Sense, Ui};
+
+/// The minimum width of the key column, so that the splitter cannot be dragged out of reach.
+const MIN_KEY_COLUMN_WIDTH: f32 = 16.0;
+
+/// The width of the area around the splitter that can be dragged.
+const SPLITTER_GRAB_WIDTH: f32 = 6.0;
+
+/// The key column of a tree shown with [`JsonTreeLayout::Columns`](crate::JsonTreeLayout::Columns).
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct KeyColumn {
+    id: Id,
+    /// The left edge of the tree.
+    left: f32,
+    width: f32,
+    /// The space between the splitter and the values.
+    padding: f32,
+}
+
+impl KeyColumn {
+    /// Loads the width of the key column, as last resized via the splitter, for a tree whose left edge is at `left`.
+    pub(crate) fn load(ui: &Ui, tree_state_id: Id, left: f32, initial_width: f32) -> Self {
+        let id = tree_state_id.with("key_column_width");
+        let width = ui.data(|d| d.get_temp::<f32>(id)).unwrap_or(initial_width);
+        Self {
+            id,
+            left,
+            width,
+            padding: ui.spacing().item_spacing.x,
+        }
+    }
+
+    /// The horizontal position of the splitter between the columns.
+    fn splitter_x(&self) -> f32 {
+        self.left + self.width
+    }
+
+    /// Adds space to a row so that the value that follows starts in the value column.
+    /// Keys that are too wide for the key column are followed directly by their value.
+    pub(crate) fn align_to_value_column(&self, ui: &mut Ui) {
+        let value_x = self.splitter_x() + self.padding;
+        let gap = value_x - ui.cursor().left();
+        if gap > 0.0 {
+            ui.add_space(gap);
+        }
+    }
+
+    /// Shows the draggable splitter between the columns, spanning the given vertical extent of the tree.
+    pub(crate) fn show_splitter(&self, ui: &Ui, y_range: Rangef) {
+        let x = self.splitter_x();
+        let rect = Rect::from_x_y_ranges(
+            Rangef::new(x - SPLITTER_GRAB_WIDTH / 2.0, x + SPLITTER_GRAB_WIDTH / 2.0),
+            y_range,
+        );
+        let response = ui
+            .interact(rect, self.id, Sense::drag())
+            .on_hover_cursor(CursorIcon::ResizeHorizontal);
+
+        let stroke = if response.hovered() || response.dragged() {
+            ui.visuals().widgets.hovered.fg_stroke
+        } else {
+            ui.visuals().widgets.noninteractive.bg_stroke
+        };
+        ui.painter().vline(x, y_range, stroke);
+
+        if response.dragged() {
+            let width = (self.width + response.drag_delta().x).max(MIN_KEY_COLUMN_WIDTH);
+            ui.data_mut(|d| d.insert_temp(self.id, width));
+        }
+    }
+}
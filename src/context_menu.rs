@@ -0,0 +1,245 @@
+//! Built-in context menus for a [`JsonTree`](crate::JsonTree), and an API for extending them.
+//!
+//! Enable the built-in entries with [`JsonTree::context_menu`](crate::JsonTree::context_menu),
+//! suppress specific entries per value with [`JsonTree::context_menu_entries`](crate::JsonTree::context_menu_entries),
+//! and append your own items with [`JsonTree::on_context_menu`](crate::JsonTree::on_context_menu):
+//! ```rust
+//! # use egui_json_tree::{context_menu::ContextMenuEntry, JsonTree};
+//! # egui::__run_test_ui(|ui| {
+//! let value = serde_json::json!({ "secret": "hunter2", "items": [1, 2] });
+//!
+//! JsonTree::new("context-menu-tree", &value)
+//!     .context_menu(true)
+//!     // Don't allow copying the secret.
+//!     .context_menu_entries(|entry, pointer, _| {
+//!         entry != ContextMenuEntry::CopyValue || pointer.to_json_pointer_string() != "/secret"
+//!     })
+//!     .on_context_menu(|ui, pointer, value| {
+//!         if ui.button("Log value").clicked() {
+//!             println!("{}: {value}", pointer.to_json_pointer_string());
+//!             ui.close_menu();
+//!         }
+//!     })
+//!     .show(ui);
+//! # });
+//! ```
+//!
+//! Context menus are attached to the keys, values and brackets rendered by the default render implementation,
+//! so they are also shown within a custom render hook that calls `render_default`.
+//!
+//! The context menu of the focused key, value or bracket can also be opened from the keyboard with [`OPEN_SHORTCUT`],
+//! so that every entry is reachable without a mouse.
+
+use std::{cell::RefCell, collections::HashSet};
+
+use egui::{
+    collapsing_header::CollapsingState,
+    menu::{BarState, MenuRoot},
+    Context, Id, Key, KeyboardShortcut, Modifiers, Response, Ui,
+};
+
+use crate::{
+    identity::PathIds,
+    pointer::{JsonPointer, JsonPointerSegment},
+    search::find_expandable_path_ids_in,
+    tabular::{to_delimited_string, Delimited},
+    uuid::is_uuid,
+    value::{
+        to_json_string, to_python_literal, to_rust_literal, BaseValueType, JsonTreeValue,
+        ToJsonTreeValue,
+    },
+};
+
+/// The built-in entries of the context menu.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContextMenuEntry {
+    /// Copies the JSON Pointer string of the value to the clipboard. Not shown for the root value.
+    CopyPointer,
+    /// Copies the value to the clipboard as pretty-printed JSON.
+    CopyValue,
+    /// Copies a UUID to the clipboard without quotes. Only shown for string values formatted as UUIDs.
+    CopyUuid,
+    /// Expands the array/object and all arrays/objects nested within it. Only shown for arrays/objects.
+    ExpandAll,
+    /// Collapses the array/object and all arrays/objects nested within it. Only shown for arrays/objects.
+    CollapseAll,
+    /// Copies an array of objects to the clipboard as comma-separated values, with a header row of the keys of the objects.
+    /// Only shown for non-empty arrays whose elements are all objects.
+    CopyAsCsv,
+    /// Copies an array of objects to the clipboard as tab-separated values, with a header row of the keys of the objects.
+    /// Only shown for non-empty arrays whose elements are all objects.
+    CopyAsTsv,
+    /// Copies the value to the clipboard as a `serde_json::json!` macro invocation, e.g. for use as a test fixture.
+    CopyAsRust,
+    /// Copies the value to the clipboard as a Python literal, with `True`/`False`/`None` in place of `true`/`false`/`null`.
+    CopyAsPython,
+}
+
+/// The keyboard shortcut that opens the context menu of the focused key, value or bracket, i.e. Shift+F10.
+pub const OPEN_SHORTCUT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::SHIFT, Key::F10);
+
+/// The id under which `egui` stores the state of the open context menu.
+const EGUI_CONTEXT_MENU_ID: &str = "__egui::context_menu";
+
+/// A closure that decides whether a built-in [`ContextMenuEntry`] is shown for the value at the JSON pointer.
+pub type ContextMenuEntryFilter<'a, T> = dyn Fn(ContextMenuEntry, JsonPointer, &T) -> bool + 'a;
+
+/// A closure that appends items to the context menu of the value at the JSON pointer.
+pub type ContextMenuHook<'a, T> = dyn FnMut(&mut Ui, JsonPointer<'a, '_>, &'a T) + 'a;
+
+pub(crate) struct ContextMenu<'a, T: ToJsonTreeValue> {
+    pub(crate) builtin_entries: bool,
+    pub(crate) entry_filter: Option<Box<ContextMenuEntryFilter<'a, T>>>,
+    pub(crate) hook: Option<RefCell<Box<ContextMenuHook<'a, T>>>>,
+    /// The ids of the paths of the tree being shown, for expanding/collapsing nested arrays/objects.
+    pub(crate) path_ids: Option<PathIds>,
+    /// Collapsing state ids to open or close once the tree has been shown,
+    /// so that they are not overwritten by the nodes currently being shown.
+    pending_set_expanded: RefCell<Option<(HashSet<Id>, bool)>>,
+}
+
+impl<'a, T: ToJsonTreeValue> Default for ContextMenu<'a, T> {
+    fn default() -> Self {
+        Self {
+            builtin_entries: false,
+            entry_filter: None,
+            hook: None,
+            path_ids: None,
+            pending_set_expanded: RefCell::new(None),
+        }
+    }
+}
+
+impl<'a, T: ToJsonTreeValue> ContextMenu<'a, T> {
+    /// Shows the context menu when the response is right clicked, or when it has focus and [`OPEN_SHORTCUT`] is pressed,
+    /// if there is anything to show.
+    pub(crate) fn attach(&self, response: &Response, pointer: JsonPointer<'a, '_>, value: &'a T) {
+        if self.builtin_entries || self.hook.is_some() {
+            if response.has_focus()
+                && response
+                    .ctx
+                    .input_mut(|i| i.consume_shortcut(&OPEN_SHORTCUT))
+            {
+                open_context_menu(response);
+            }
+            response.context_menu(|ui| self.show(ui, pointer, value));
+        }
+    }
+
+    fn show(&self, ui: &mut Ui, pointer: JsonPointer<'a, '_>, value: &'a T) {
+        let is_shown = |entry| {
+            self.builtin_entries
+                && self
+                    .entry_filter
+                    .as_ref()
+                    .map_or(true, |entry_filter| entry_filter(entry, pointer, value))
+        };
+
+        if !pointer.0.is_empty()
+            && is_shown(ContextMenuEntry::CopyPointer)
+            && ui.button("Copy JSON pointer").clicked()
+        {
+            ui.ctx().copy_text(pointer.to_json_pointer_string());
+            ui.close_menu();
+        }
+
+        if is_shown(ContextMenuEntry::CopyValue) && ui.button("Copy value").clicked() {
+            ui.ctx().copy_text(to_json_string(value));
+            ui.close_menu();
+        }
+
+        if is_shown(ContextMenuEntry::CopyUuid) {
+            if let Some(uuid) = to_uuid_string(value) {
+                if ui.button("Copy UUID").clicked() {
+                    ui.ctx().copy_text(uuid);
+                    ui.close_menu();
+                }
+            }
+        }
+
+        if is_shown(ContextMenuEntry::CopyAsRust) && ui.button("Copy as Rust literal").clicked() {
+            ui.ctx().copy_text(to_rust_literal(value));
+            ui.close_menu();
+        }
+
+        if is_shown(ContextMenuEntry::CopyAsPython) && ui.button("Copy as Python literal").clicked()
+        {
+            ui.ctx().copy_text(to_python_literal(value));
+            ui.close_menu();
+        }
+
+        for (entry, format, text) in [
+            (ContextMenuEntry::CopyAsCsv, Delimited::Csv, "Copy as CSV"),
+            (ContextMenuEntry::CopyAsTsv, Delimited::Tsv, "Copy as TSV"),
+        ] {
+            if !is_shown(entry) {
+                continue;
+            }
+            if let Some(table) = to_delimited_string(value, format) {
+                if ui.button(text).clicked() {
+                    ui.ctx().copy_text(table);
+                    ui.close_menu();
+                }
+            }
+        }
+
+        if value.is_expandable() {
+            if is_shown(ContextMenuEntry::ExpandAll) && ui.button("Expand all").clicked() {
+                self.set_all_expanded(ui, pointer, value, true);
+                ui.close_menu();
+            }
+            if is_shown(ContextMenuEntry::CollapseAll) && ui.button("Collapse all").clicked() {
+                self.set_all_expanded(ui, pointer, value, false);
+                ui.close_menu();
+            }
+        }
+
+        if let Some(hook) = &self.hook {
+            (hook.borrow_mut())(ui, pointer, value);
+        }
+    }
+
+    /// Expands/collapses the array/object at the pointer and every array/object nested within it, once the tree has been shown.
+    pub(crate) fn set_all_expanded(&self, ui: &Ui, pointer: JsonPointer, value: &T, open: bool) {
+        let Some(path_ids) = &self.path_ids else {
+            return;
+        };
+        let make_persistent_id = |path_segments: &[JsonPointerSegment]| {
+            let full_path_segments = [pointer.0, path_segments].concat();
+            path_ids.id(&full_path_segments)
+        };
+        let ids = find_expandable_path_ids_in(value, &make_persistent_id);
+        *self.pending_set_expanded.borrow_mut() = Some((ids, open));
+        ui.ctx().request_repaint();
+    }
+
+    /// Applies any "Expand all"/"Collapse all" entry clicked while the tree was shown.
+    pub(crate) fn apply_pending_set_expanded(&self, ctx: &Context) {
+        let Some((ids, open)) = self.pending_set_expanded.take() else {
+            return;
+        };
+        for id in ids {
+            let mut state = CollapsingState::load_with_default_open(ctx, id, open);
+            state.set_open(open);
+            state.store(ctx);
+        }
+    }
+}
+
+/// Opens the context menu of the response below it, as if it had been right clicked.
+fn open_context_menu(response: &Response) {
+    let menu_id = Id::new(EGUI_CONTEXT_MENU_ID);
+    let mut bar_state = BarState::load(&response.ctx, menu_id);
+    **bar_state = Some(MenuRoot::new(response.rect.left_bottom(), response.id));
+    bar_state.store(&response.ctx, menu_id);
+}
+
+/// Returns the text of a string value formatted as a UUID, without quotes, if it is one.
+fn to_uuid_string<T: ToJsonTreeValue>(value: &T) -> Option<String> {
+    match value.to_json_tree_value() {
+        JsonTreeValue::Base(_, display_value, BaseValueType::String) => {
+            Some(display_value.to_string()).filter(|text| is_uuid(text))
+        }
+        _ => None,
+    }
+}
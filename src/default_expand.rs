@@ -1,5 +1,10 @@
-#[derive(Debug, Clone, Copy, Default)]
+use std::fmt;
+
+use crate::{pointer::JsonPointer, SearchCandidate, SearchOptions};
+
+#[derive(Clone, Copy, Default)]
 /// Configuration for how a [`JsonTree`](crate::JsonTree) should expand arrays and objects by default.
+#[non_exhaustive]
 pub enum DefaultExpand<'a> {
     /// Expand all arrays and objects.
     All,
@@ -13,8 +18,112 @@ pub enum DefaultExpand<'a> {
     ///
     /// And so on.
     ToLevel(u8),
+    /// Like [`DefaultExpand::ToLevel`], but with how arrays and objects are expanded overridden beneath specific JSON Pointer strings,
+    /// e.g. `ToLevelWith(2, &[("/raw_data", ExpandOverride::ToLevel(0))])` expands two levels deep, except that `/raw_data` stays collapsed,
+    /// so that one giant subtree does not force a conservative default for the whole tree.
+    /// An override can also expand everything or nothing beneath its pointer,
+    /// e.g. `ToLevelWith(1, &[("/errors", ExpandOverride::All), ("/raw", ExpandOverride::None)])`
+    /// for documents with a predictable shape.
+    ///
+    /// An override only takes effect once the arrays/objects containing its pointer are expanded.
+    /// If several overrides contain an array/object, the one with the longest pointer applies.
+    /// Overrides whose pointer string is not a valid JSON Pointer are ignored.
+    ToLevelWith(u8, &'a [(&'a str, ExpandOverride)]),
     /// Expand arrays and objects to display object keys and values,
     /// and array elements, that match the search term. Letter case is ignored. The matches are highlighted.
     /// If the search term is empty, nothing will be expanded by default.
+    /// The search term is matched as text. To filter values by type, e.g. `type:null`, or exclude words, e.g. `error -debug`,
+    /// see [`SearchOptions::query_syntax`].
     SearchResults(&'a str),
+    /// Like [`DefaultExpand::SearchResults`], but matching the search term according to the given options,
+    /// e.g. to match letter case.
+    ///
+    /// The options also apply to a search term given via [`JsonTree::search_term`](crate::JsonTree::search_term)
+    /// or [`JsonTree::shared_search`](crate::JsonTree::shared_search).
+    SearchResultsWith(&'a str, SearchOptions),
+    /// Like [`DefaultExpand::SearchResultsWith`], but with several search terms,
+    /// e.g. `SearchResultsAll(&["error", "timeout"], SearchOptions::default())`.
+    /// An object key or non-recursive value matches only if it matches every search term, and the matches of all of them are highlighted.
+    /// Each search term is matched according to the options. Empty search terms are ignored.
+    SearchResultsAll(&'a [&'a str], SearchOptions),
+    /// Like [`DefaultExpand::SearchResultsAll`], but an object key or non-recursive value matches if it matches any of the search terms.
+    SearchResultsAny(&'a [&'a str], SearchOptions),
+    /// Like [`DefaultExpand::SearchResults`], but with custom matching logic: an object key or non-recursive value matches
+    /// if the predicate returns `true` for it, given its JSON Pointer, e.g. to match strings longer than some length.
+    /// The array indices and object keys of the matched values are highlighted.
+    ///
+    /// A search term given via [`JsonTree::search_term`](crate::JsonTree::search_term)
+    /// or [`JsonTree::shared_search`](crate::JsonTree::shared_search) takes precedence over the predicate.
+    SearchWith(&'a dyn Fn(JsonPointer, SearchCandidate) -> bool),
+    /// Expand exactly the arrays and objects containing the value at the given JSON Pointer string,
+    /// e.g. `"/bar/thud/a~1b/2"` from a validation error, so that the value is visible.
+    /// The value itself is not expanded if it is an array or object.
+    ///
+    /// If the string is not a valid JSON Pointer, nothing will be expanded by default.
+    /// Parts of the pointer that do not resolve to an array or object in the value are ignored.
+    ToPointer(&'a str),
+    /// Expand only the arrays and objects at the given JSON Pointer strings, and the arrays and objects containing them,
+    /// e.g. `&["/errors", "/summary"]` to always reveal errors and a summary while leaving bulky data collapsed.
+    ///
+    /// To expand the regions described by a JSON Schema, collect the pointers of the relevant properties of the schema.
+    /// Pointers that do not resolve to an array or object in the value are ignored.
+    Important(&'a [&'a str]),
+    /// Expand arrays and objects to display the values selected by a JSONPath expression,
+    /// e.g. `"$.store.book[*].author"`. The array indices and object keys of the selected values are highlighted.
+    ///
+    /// Member names, wildcards, array indices and slices, unions and recursive descent are supported,
+    /// but filter expressions are not. If the expression cannot be parsed, nothing will be expanded by default,
+    /// and the error is available via [`JsonTreeResponse::json_path_error`](crate::JsonTreeResponse::json_path_error).
+    JsonPath(&'a str),
+}
+
+/// How the arrays and objects at and beneath a JSON Pointer string are expanded by default.
+/// See [`DefaultExpand::ToLevelWith`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExpandOverride {
+    /// Expand the array/object at the pointer, and all arrays and objects beneath it.
+    All,
+    /// Collapse the array/object at the pointer, and all arrays and objects beneath it.
+    None,
+    /// Expand the given number of levels at and beneath the pointer:
+    /// `0` keeps the array/object at the pointer collapsed, `1` expands it only,
+    /// `2` expands it and any array/object that is a direct child, and so on.
+    ToLevel(u8),
+}
+
+impl fmt::Debug for DefaultExpand<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::All => write!(f, "All"),
+            Self::None => write!(f, "None"),
+            Self::ToLevel(level) => f.debug_tuple("ToLevel").field(level).finish(),
+            Self::ToLevelWith(level, overrides) => f
+                .debug_tuple("ToLevelWith")
+                .field(level)
+                .field(overrides)
+                .finish(),
+            Self::SearchResults(search_str) => {
+                f.debug_tuple("SearchResults").field(search_str).finish()
+            }
+            Self::SearchResultsWith(search_str, search_options) => f
+                .debug_tuple("SearchResultsWith")
+                .field(search_str)
+                .field(search_options)
+                .finish(),
+            Self::SearchResultsAll(search_strs, search_options) => f
+                .debug_tuple("SearchResultsAll")
+                .field(search_strs)
+                .field(search_options)
+                .finish(),
+            Self::SearchResultsAny(search_strs, search_options) => f
+                .debug_tuple("SearchResultsAny")
+                .field(search_strs)
+                .field(search_options)
+                .finish(),
+            Self::SearchWith(_) => write!(f, "SearchWith(..)"),
+            Self::ToPointer(pointer) => f.debug_tuple("ToPointer").field(pointer).finish(),
+            Self::Important(pointers) => f.debug_tuple("Important").field(pointers).finish(),
+            Self::JsonPath(expression) => f.debug_tuple("JsonPath").field(expression).finish(),
+        }
+    }
 }
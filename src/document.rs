@@ -0,0 +1,309 @@
+//! A thread-safe handle to a JSON document, for updating the value shown by a [`JsonTree`](crate::JsonTree) from a background thread,
+//! or from a push-based [`JsonTreeSource`] of live data.
+
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc::Receiver,
+        Arc,
+    },
+};
+
+use egui::{
+    mutex::{Mutex, RwLock},
+    Context,
+};
+
+/// The number of recent changes to a document that are remembered for updating search matches incrementally.
+const MAX_RECORDED_CHANGES: usize = 256;
+
+/// A thread-safe, cheaply cloneable handle to a JSON document.
+///
+/// A background thread can replace or modify the document, while the UI thread
+/// renders the latest snapshot each frame without blocking on the writer:
+/// ```rust
+/// # use egui_json_tree::{JsonTree, JsonTreeDocument};
+/// # egui::__run_test_ui(|ui| {
+/// let document = JsonTreeDocument::new(serde_json::json!({ "count": 0 }));
+///
+/// let background_document = document.clone();
+/// std::thread::spawn(move || {
+///     background_document.store(serde_json::json!({ "count": 1 }));
+/// });
+///
+/// let snapshot = document.load();
+/// JsonTree::new("live-tree", &*snapshot).show(ui);
+/// # });
+/// ```
+pub struct JsonTreeDocument<T> {
+    shared: Arc<Shared<T>>,
+}
+
+struct Shared<T> {
+    value: RwLock<Arc<T>>,
+    version: AtomicU64,
+    repaint_ctx: RwLock<Option<Context>>,
+    changes: ChangeLog,
+}
+
+impl<T> Clone for JsonTreeDocument<T> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> JsonTreeDocument<T> {
+    /// Creates a new [`JsonTreeDocument`] holding the given value, with a version of `0`.
+    pub fn new(value: T) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                value: RwLock::new(Arc::new(value)),
+                version: AtomicU64::new(0),
+                repaint_ctx: RwLock::new(None),
+                changes: ChangeLog::default(),
+            }),
+        }
+    }
+
+    /// Returns a snapshot of the current value.
+    /// The snapshot is unaffected by any subsequent changes to the document.
+    pub fn load(&self) -> Arc<T> {
+        Arc::clone(&self.shared.value.read())
+    }
+
+    /// Returns the current version of the document, which is incremented whenever it changes.
+    pub fn version(&self) -> u64 {
+        self.shared.version.load(Ordering::Acquire)
+    }
+
+    /// Returns a snapshot of the current value together with its version,
+    /// which unlike [`JsonTreeDocument::load`] followed by [`JsonTreeDocument::version`] cannot be torn by a concurrent change.
+    pub fn load_with_version(&self) -> (Arc<T>, u64) {
+        let value = self.shared.value.read();
+        (Arc::clone(&value), self.version())
+    }
+
+    /// Replaces the value of the document.
+    pub fn store(&self, value: T) {
+        self.modify(None, |current| *current = Arc::new(value));
+    }
+
+    /// Modifies the value of the document in place.
+    /// If any snapshots of the current value are still held, the value is cloned before being modified.
+    pub fn update(&self, f: impl FnOnce(&mut T))
+    where
+        T: Clone,
+    {
+        self.modify(None, |current| f(Arc::make_mut(current)));
+    }
+
+    /// Like [`JsonTreeDocument::update`], but declaring that only the value at the JSON Pointer string and its descendants are modified,
+    /// e.g. `/logs/1024` for a log line appended to the array at `/logs`. The value there may be added or removed,
+    /// but removing an element other than the last of an array changes the indices of the elements after it, i.e. changes the array.
+    ///
+    /// A [`JsonTree`](crate::JsonTree) shown via [`JsonTree::document_changes`](crate::JsonTree::document_changes) then updates its search matches
+    /// by searching only the modified subtree, rather than the whole document.
+    pub fn update_at(&self, pointer: impl Into<String>, f: impl FnOnce(&mut T))
+    where
+        T: Clone,
+    {
+        self.modify(Some(pointer.into()), |current| f(Arc::make_mut(current)));
+    }
+
+    /// Requests a repaint of the given [`Context`] whenever the document changes,
+    /// so that changes made from a background thread are shown immediately.
+    pub fn set_repaint_context(&self, ctx: Context) {
+        *self.shared.repaint_ctx.write() = Some(ctx);
+    }
+
+    /// Applies a single update to the document.
+    pub fn apply(&self, update: JsonTreeUpdate<T>)
+    where
+        T: Clone,
+    {
+        match update {
+            JsonTreeUpdate::Replace(value) => self.store(value),
+            JsonTreeUpdate::Patch(patch) => self.update(patch),
+            JsonTreeUpdate::PatchAt(pointer, patch) => self.update_at(pointer, patch),
+        }
+    }
+
+    /// Applies all pending updates from the source to the document, without blocking.
+    /// Call this once per frame, e.g. before loading a snapshot of the document to show.
+    ///
+    /// Returns whether the document changed.
+    pub fn poll_source(&self, source: &mut impl JsonTreeSource<T>) -> bool
+    where
+        T: Clone,
+    {
+        let mut changed = false;
+        while let Some(update) = source.poll() {
+            self.apply(update);
+            changed = true;
+        }
+        changed
+    }
+
+    /// Returns the record of recent changes to the document.
+    pub(crate) fn changes(&self) -> ChangeLog {
+        self.shared.changes.clone()
+    }
+
+    /// Modifies the value, recording a change to the value at the JSON Pointer string, or to the whole value if `None`.
+    fn modify(&self, pointer: Option<String>, f: impl FnOnce(&mut Arc<T>)) {
+        {
+            // The version is incremented while the value is locked, so that it is always loaded with the matching value.
+            let mut value = self.shared.value.write();
+            f(&mut value);
+            let version = self.shared.version.fetch_add(1, Ordering::AcqRel) + 1;
+            let mut changes = self.shared.changes.0.lock();
+            if changes.len() == MAX_RECORDED_CHANGES {
+                changes.pop_front();
+            }
+            changes.push_back((version, pointer));
+        }
+        if let Some(ctx) = self.shared.repaint_ctx.read().as_ref() {
+            ctx.request_repaint();
+        }
+    }
+}
+
+/// An update to a [`JsonTreeDocument`].
+pub enum JsonTreeUpdate<T> {
+    /// Replaces the value of the document.
+    Replace(T),
+    /// Modifies the value of the document in place.
+    Patch(Box<dyn FnOnce(&mut T) + Send>),
+    /// Modifies only the value at the JSON Pointer string and its descendants. See [`JsonTreeDocument::update_at`].
+    PatchAt(String, Box<dyn FnOnce(&mut T) + Send>),
+}
+
+/// The versions of a [`JsonTreeDocument`] produced by its recent changes,
+/// each with the JSON Pointer string of the only subtree that changed, if known.
+#[derive(Clone, Default)]
+pub(crate) struct ChangeLog(Arc<Mutex<VecDeque<Change>>>);
+
+/// A version of a document and the JSON Pointer string of the only subtree changed to produce it, if known.
+type Change = (u64, Option<String>);
+
+impl ChangeLog {
+    /// Returns the JSON Pointer strings of the subtrees that changed after version `from` up to and including version `to`,
+    /// or `None` if the whole value may have changed, or the changes are no longer recorded.
+    pub(crate) fn changed_pointers(&self, from: u64, to: u64) -> Option<Vec<String>> {
+        let changes = self.0.lock();
+        let pointers = changes
+            .iter()
+            .filter(|(version, _)| (from + 1..=to).contains(version))
+            .map(|(_, pointer)| pointer.clone())
+            .collect::<Option<Vec<_>>>()?;
+        (pointers.len() as u64 == to.checked_sub(from)?).then_some(pointers)
+    }
+}
+
+/// A push-based source of updates to a [`JsonTreeDocument`], such as live telemetry or a log stream.
+///
+/// This is implemented for [`Receiver`], so a producer can send [`JsonTreeUpdate`]s through a channel:
+/// ```rust
+/// # use egui_json_tree::document::{JsonTreeDocument, JsonTreeUpdate};
+/// let document = JsonTreeDocument::new(serde_json::json!([]));
+/// let (sender, mut receiver) = std::sync::mpsc::channel();
+///
+/// sender
+///     .send(JsonTreeUpdate::Patch(Box::new(|value: &mut serde_json::Value| {
+///         value.as_array_mut().unwrap().push(serde_json::json!("log line"));
+///     })))
+///     .unwrap();
+///
+/// assert!(document.poll_source(&mut receiver));
+/// assert_eq!(*document.load(), serde_json::json!(["log line"]));
+/// ```
+pub trait JsonTreeSource<T> {
+    /// Returns the next pending update, if there is one, without blocking.
+    fn poll(&mut self) -> Option<JsonTreeUpdate<T>>;
+}
+
+impl<T> JsonTreeSource<T> for Receiver<JsonTreeUpdate<T>> {
+    fn poll(&mut self) -> Option<JsonTreeUpdate<T>> {
+        self.try_recv().ok()
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn store_replaces_value_and_bumps_version() {
+        let document = JsonTreeDocument::new(serde_json::json!(1));
+        let snapshot = document.load();
+
+        let background_document = document.clone();
+        std::thread::spawn(move || background_document.store(serde_json::json!(2)))
+            .join()
+            .unwrap();
+
+        assert_eq!(*snapshot, serde_json::json!(1));
+        assert_eq!(*document.load(), serde_json::json!(2));
+        assert_eq!(document.version(), 1);
+    }
+
+    #[test]
+    fn update_does_not_affect_existing_snapshots() {
+        let document = JsonTreeDocument::new(serde_json::json!({"foo": 1}));
+        let snapshot = document.load();
+
+        document.update(|value| value["foo"] = serde_json::json!(2));
+
+        assert_eq!(*snapshot, serde_json::json!({"foo": 1}));
+        assert_eq!(*document.load(), serde_json::json!({"foo": 2}));
+        assert_eq!(document.version(), 1);
+    }
+
+    #[test]
+    fn poll_source_applies_all_pending_updates() {
+        let document = JsonTreeDocument::new(serde_json::json!(0));
+        let (sender, mut receiver) = std::sync::mpsc::channel();
+
+        assert!(!document.poll_source(&mut receiver));
+
+        sender
+            .send(JsonTreeUpdate::Replace(serde_json::json!(1)))
+            .unwrap();
+        sender
+            .send(JsonTreeUpdate::Patch(Box::new(|value| {
+                *value = serde_json::json!(value.as_i64().unwrap() + 1)
+            })))
+            .unwrap();
+
+        assert!(document.poll_source(&mut receiver));
+        assert_eq!(*document.load(), serde_json::json!(2));
+        assert_eq!(document.version(), 2);
+    }
+
+    #[test]
+    fn records_the_pointers_of_changed_subtrees() {
+        let document = JsonTreeDocument::new(serde_json::json!({"logs": []}));
+        let push = |line| {
+            move |value: &mut serde_json::Value| value["logs"].as_array_mut().unwrap().push(line)
+        };
+        document.update_at("/logs/0", push(serde_json::json!("a")));
+        document.update_at("/logs/1", push(serde_json::json!("b")));
+
+        let changes = document.changes();
+        assert_eq!(
+            changes.changed_pointers(0, 2),
+            Some(vec!["/logs/0".to_owned(), "/logs/1".to_owned()])
+        );
+        assert_eq!(
+            changes.changed_pointers(1, 2),
+            Some(vec!["/logs/1".to_owned()])
+        );
+
+        document.update(push(serde_json::json!("c")));
+        assert_eq!(changes.changed_pointers(1, 3), None);
+        assert_eq!(changes.changed_pointers(3, 3), Some(vec![]));
+    }
+}
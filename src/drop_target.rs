@@ -0,0 +1,132 @@
+use std::cell::RefCell;
+
+use egui::{Context, Pos2, Rect, Stroke, Ui};
+
+use crate::{
+    identity::segment_matches,
+    pointer::{parse_json_pointer_string, JsonPointer, JsonPointerSegment},
+    search::find_path_in,
+    value::{disambiguate_duplicate_keys, JsonTreeValue, ToJsonTreeValue},
+};
+
+/// Returns whether a drag payload accepted by the tree is being dragged. See [`JsonTree::drop_targets`](crate::JsonTree::drop_targets).
+pub(crate) type AcceptsPayload = fn(&Context) -> bool;
+
+/// The gap between the entries of an array/object that is targeted by a drag hovering a [`JsonTree`](crate::JsonTree),
+/// i.e. where the dragged item would be inserted if dropped. See [`JsonTree::drop_targets`](crate::JsonTree::drop_targets).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonTreeDropTarget {
+    /// The JSON Pointer string of the row hovered by the drag.
+    pub hovered_pointer: String,
+    /// The JSON Pointer string of the array/object that the dragged item would be inserted into.
+    pub parent_pointer: String,
+    /// The position among the entries of that array/object, in the order of the value, at which the dragged item would be inserted.
+    /// This is the number of entries if it would be inserted after the last entry.
+    pub index: usize,
+}
+
+/// The row hovered by a drag, as recorded while the tree is shown.
+struct HoveredRow {
+    pointer: String,
+    rect: Rect,
+    is_expanded: bool,
+}
+
+/// Finds the row hovered by a drag while the tree is shown.
+pub(crate) struct DropTargetRecorder {
+    hover_pos: Pos2,
+    hovered: RefCell<Option<HoveredRow>>,
+}
+
+impl DropTargetRecorder {
+    /// Starts recording if a drag payload accepted by the tree is hovering the `Ui`.
+    pub(crate) fn start(ui: &Ui, accepts_payload: AcceptsPayload) -> Option<Self> {
+        if !accepts_payload(ui.ctx()) {
+            return None;
+        }
+        let hover_pos = ui
+            .ctx()
+            .pointer_hover_pos()
+            .filter(|pos| ui.clip_rect().contains(*pos))?;
+        Some(Self {
+            hover_pos,
+            hovered: RefCell::new(None),
+        })
+    }
+
+    /// Records the row if the drag is hovering it.
+    pub(crate) fn record_row(
+        &self,
+        path_segments: &[JsonPointerSegment],
+        rect: Rect,
+        is_expanded: bool,
+    ) {
+        if rect.y_range().contains(self.hover_pos.y) {
+            *self.hovered.borrow_mut() = Some(HoveredRow {
+                pointer: JsonPointer(path_segments).to_json_pointer_string(),
+                rect,
+                is_expanded,
+            });
+        }
+    }
+
+    /// Returns the targeted gap, and paints an insertion indicator at it with the stroke.
+    pub(crate) fn finish<T: ToJsonTreeValue>(
+        self,
+        ui: &Ui,
+        value: &T,
+        stroke: Stroke,
+    ) -> Option<JsonTreeDropTarget> {
+        let hovered = self.hovered.into_inner()?;
+        let segments = parse_json_pointer_string(&hovered.pointer)?;
+        let in_lower_half = self.hover_pos.y > hovered.rect.center().y;
+
+        let (parent_pointer, index, y, left) = match segments.split_last() {
+            // Insert as the first entry of an expanded array/object, or of the root.
+            _ if hovered.is_expanded && (in_lower_half || segments.is_empty()) => (
+                hovered.pointer.clone(),
+                0,
+                hovered.rect.bottom(),
+                hovered.rect.left() + ui.spacing().indent,
+            ),
+            None => return None,
+            Some((last, parent_segments)) => {
+                let (_, parent) = find_path_in(value, parent_segments)?;
+                let JsonTreeValue::Expandable(mut entries, _) = parent.to_json_tree_value() else {
+                    return None;
+                };
+                disambiguate_duplicate_keys(parent, &mut entries);
+                let position = entries
+                    .iter()
+                    .position(|(property, _)| segment_matches(property, last))?;
+                let parent_pointer = hovered
+                    .pointer
+                    .rfind('/')
+                    .map_or("", |idx| &hovered.pointer[..idx])
+                    .to_owned();
+                if in_lower_half {
+                    (
+                        parent_pointer,
+                        position + 1,
+                        hovered.rect.bottom(),
+                        hovered.rect.left(),
+                    )
+                } else {
+                    (
+                        parent_pointer,
+                        position,
+                        hovered.rect.top(),
+                        hovered.rect.left(),
+                    )
+                }
+            }
+        };
+
+        ui.painter().hline(left..=ui.max_rect().right(), y, stroke);
+        Some(JsonTreeDropTarget {
+            hovered_pointer: hovered.pointer,
+            parent_pointer,
+            index,
+        })
+    }
+}
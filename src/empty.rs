@@ -0,0 +1,36 @@
+use egui::Ui;
+
+use crate::value::{BaseValueType, JsonTreeValue, ToJsonTreeValue};
+
+/// A hook that renders a placeholder instead of a [`JsonTree`](crate::JsonTree) with nothing to show.
+/// See [`JsonTree::on_empty`](crate::JsonTree::on_empty).
+pub(crate) type EmptyPlaceholder<'a> = dyn FnMut(&mut Ui, JsonTreeEmptyReason) + 'a;
+
+/// Why a [`JsonTree`](crate::JsonTree) has nothing to show. See [`JsonTree::on_empty`](crate::JsonTree::on_empty).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JsonTreeEmptyReason {
+    /// The value is `null`.
+    Null,
+    /// The value is an empty array or object.
+    NoEntries,
+    /// Every entry is hidden by [`JsonTree::filter_search_results`](crate::JsonTree::filter_search_results), because nothing matches the search.
+    NoSearchResults,
+}
+
+impl JsonTreeEmptyReason {
+    /// Returns why the value has nothing to show, if so.
+    /// `has_search_results` is `None` if search results are not being filtered.
+    pub(crate) fn of<T: ToJsonTreeValue>(
+        value: &T,
+        has_search_results: Option<bool>,
+    ) -> Option<Self> {
+        match value.to_json_tree_value() {
+            JsonTreeValue::Base(_, _, BaseValueType::Null) => Some(Self::Null),
+            JsonTreeValue::Base(..) => None,
+            JsonTreeValue::Expandable(entries, _) if entries.is_empty() => Some(Self::NoEntries),
+            JsonTreeValue::Expandable(..) => {
+                (has_search_results == Some(false)).then_some(Self::NoSearchResults)
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::EpochUnit;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Integers in this range are guessed to be timestamps in seconds, i.e. from the year 2001 to 5138.
+const GUESSED_SECONDS_RANGE: std::ops::Range<i64> = 1_000_000_000..100_000_000_000;
+
+/// Integers in this range are guessed to be timestamps in milliseconds, i.e. from the year 1973 to 5138.
+const GUESSED_MILLIS_RANGE: std::ops::Range<i64> = 100_000_000_000..100_000_000_000_000;
+
+/// Formats an integer as an ISO 8601 UTC date-time string, interpreting it as a Unix epoch timestamp.
+///
+/// Returns `None` if the value is not an integer, or if [`EpochUnit::Auto`] cannot guess the unit from its magnitude.
+pub(crate) fn format_epoch_timestamp(value_str: &str, unit: EpochUnit) -> Option<String> {
+    let n = value_str.parse::<i64>().ok()?;
+
+    let unit = match unit {
+        EpochUnit::Auto if GUESSED_SECONDS_RANGE.contains(&n) => EpochUnit::Seconds,
+        EpochUnit::Auto if GUESSED_MILLIS_RANGE.contains(&n) => EpochUnit::Milliseconds,
+        EpochUnit::Auto => return None,
+        unit => unit,
+    };
+
+    let (secs, millis) = match unit {
+        EpochUnit::Milliseconds => (n.div_euclid(1000), Some(n.rem_euclid(1000))),
+        _ => (n, None),
+    };
+
+    let days = secs.div_euclid(SECONDS_PER_DAY);
+    let secs_of_day = secs.rem_euclid(SECONDS_PER_DAY);
+    let (year, month, day) = civil_from_days(days);
+    let (hour, minute, second) = (
+        secs_of_day / 3600,
+        (secs_of_day % 3600) / 60,
+        secs_of_day % 60,
+    );
+
+    let date_time = format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}");
+
+    Some(match millis {
+        Some(millis) => format!("{date_time}.{millis:03}Z"),
+        None => format!("{date_time}Z"),
+    })
+}
+
+/// Converts a number of days since 1970-01-01 to a (year, month, day) date in the proleptic Gregorian calendar.
+///
+/// See <https://howardhinnant.github.io/date_algorithms.html#civil_from_days>.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_seconds() {
+        assert_eq!(
+            format_epoch_timestamp("0", EpochUnit::Seconds).as_deref(),
+            Some("1970-01-01T00:00:00Z")
+        );
+        assert_eq!(
+            format_epoch_timestamp("1700000000", EpochUnit::Seconds).as_deref(),
+            Some("2023-11-14T22:13:20Z")
+        );
+        assert_eq!(
+            format_epoch_timestamp("-1", EpochUnit::Seconds).as_deref(),
+            Some("1969-12-31T23:59:59Z")
+        );
+    }
+
+    #[test]
+    fn formats_milliseconds() {
+        assert_eq!(
+            format_epoch_timestamp("1700000000123", EpochUnit::Milliseconds).as_deref(),
+            Some("2023-11-14T22:13:20.123Z")
+        );
+    }
+
+    #[test]
+    fn guesses_unit_from_magnitude() {
+        assert_eq!(
+            format_epoch_timestamp("1700000000", EpochUnit::Auto).as_deref(),
+            Some("2023-11-14T22:13:20Z")
+        );
+        assert_eq!(
+            format_epoch_timestamp("1700000000123", EpochUnit::Auto).as_deref(),
+            Some("2023-11-14T22:13:20.123Z")
+        );
+        assert_eq!(format_epoch_timestamp("42", EpochUnit::Auto), None);
+    }
+
+    #[test]
+    fn ignores_non_integers() {
+        assert_eq!(
+            format_epoch_timestamp("1700000000.5", EpochUnit::Auto),
+            None
+        );
+        assert_eq!(format_epoch_timestamp("foo", EpochUnit::Seconds), None);
+    }
+}
@@ -0,0 +1,86 @@
+use std::collections::BTreeSet;
+
+use egui::{collapsing_header::CollapsingState, Context};
+
+use crate::{
+    identity::PathIds,
+    pointer::{parse_json_pointer_string, JsonPointer},
+    search::for_each_expandable_path_in,
+    value::ToJsonTreeValue,
+};
+
+/// The expanded state of the arrays/objects of a [`JsonTree`](crate::JsonTree), by JSON Pointer string,
+/// e.g. to save it with your app state and restore it on startup. Requires the `persistence` feature.
+///
+/// Capture it via [`JsonTreeResponse::expansion_state`](crate::JsonTreeResponse::expansion_state),
+/// and restore it via [`JsonTree::restore_expansion_state`](crate::JsonTree::restore_expansion_state):
+/// ```rust
+/// # use egui_json_tree::{JsonTree, JsonTreeExpansionState};
+/// # egui::__run_test_ui(|ui| {
+/// # let value = serde_json::json!({ "users": [{ "id": 1 }] });
+/// # let saved = String::from(r#"{"expanded":["","/users"]}"#);
+/// let restored: JsonTreeExpansionState = serde_json::from_str(&saved).unwrap_or_default();
+///
+/// let response = JsonTree::new("persisted-tree", &value)
+///     .restore_expansion_state(&restored)
+///     .show(ui);
+///
+/// // When saving your app state:
+/// let saved = serde_json::to_string(&response.expansion_state(ui.ctx(), &value)).unwrap();
+/// # });
+/// ```
+///
+/// Arrays/objects in neither set respect the `default_expand` setting.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct JsonTreeExpansionState {
+    /// The JSON Pointer strings of the expanded arrays/objects.
+    pub expanded: BTreeSet<String>,
+    /// The JSON Pointer strings of the collapsed arrays/objects.
+    pub collapsed: BTreeSet<String>,
+}
+
+impl JsonTreeExpansionState {
+    /// Returns the stored expanded state of the arrays/objects within the value shown by a tree.
+    pub(crate) fn capture<T: ToJsonTreeValue>(
+        ctx: &Context,
+        path_ids: &PathIds,
+        value: &T,
+    ) -> Self {
+        let mut expansion_state = Self::default();
+        for_each_expandable_path_in(value, &mut |path_segments| {
+            if let Some(state) = CollapsingState::load(ctx, path_ids.id(path_segments)) {
+                let pointer = JsonPointer(path_segments).to_json_pointer_string();
+                if state.is_open() {
+                    expansion_state.expanded.insert(pointer);
+                } else {
+                    expansion_state.collapsed.insert(pointer);
+                }
+            }
+        });
+        expansion_state
+    }
+
+    /// Stores the expanded state in the tree, unless it has already been restored since the app started.
+    pub(crate) fn restore_once(&self, ctx: &Context, path_ids: &PathIds) {
+        let restored_id = path_ids.tree_state_id().with("expansion_state_restored");
+        if ctx
+            .data(|d| d.get_temp::<bool>(restored_id))
+            .unwrap_or_default()
+        {
+            return;
+        }
+        for (pointers, open) in [(&self.expanded, true), (&self.collapsed, false)] {
+            for segments in pointers
+                .iter()
+                .filter_map(|pointer| parse_json_pointer_string(pointer))
+            {
+                let id = path_ids.id_of_segments(&segments);
+                let mut state = CollapsingState::load_with_default_open(ctx, id, open);
+                state.set_open(open);
+                state.store(ctx);
+            }
+        }
+        ctx.data_mut(|d| d.insert_temp(restored_id, true));
+    }
+}
@@ -0,0 +1,123 @@
+use std::hash::Hash;
+
+use egui::{Id, Ui};
+
+use crate::{value::ToJsonTreeValue, DefaultExpand, JsonTree, JsonTreeResponse, JsonTreeStyle};
+
+/// Visualises multiple JSON documents as labeled sibling trees, e.g. to compare several payloads
+/// or to display a set of config files at once.
+///
+/// Each document is shown as a [`JsonTree`] whose root is labeled via [`JsonTreeStyle::root_label`],
+/// with its own expanded state, but the style, expansion and search settings are shared by all documents.
+/// ```rust
+/// # use egui_json_tree::{DefaultExpand, JsonForest};
+/// # egui::__run_test_ui(|ui| {
+/// let request = serde_json::json!({ "id": 1 });
+/// let response = serde_json::json!({ "id": 1, "ok": true });
+///
+/// JsonForest::new("forest", [("request", &request), ("response", &response)])
+///     .default_expand(DefaultExpand::SearchResults("id"))
+///     .show(ui);
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct JsonForest<'a, T: ToJsonTreeValue> {
+    id: Id,
+    documents: Vec<(String, &'a T)>,
+    style: Option<JsonTreeStyle>,
+    default_expand: Option<DefaultExpand<'a>>,
+    filter_search_results: bool,
+    record_snapshot: bool,
+}
+
+impl<'a, T: ToJsonTreeValue> JsonForest<'a, T> {
+    /// Creates a new [`JsonForest`] of the given labeled documents, shown in order.
+    /// `id` must be a globally unique identifier.
+    pub fn new(
+        id: impl Hash,
+        documents: impl IntoIterator<Item = (impl Into<String>, &'a T)>,
+    ) -> Self {
+        Self {
+            id: Id::new(id),
+            documents: documents
+                .into_iter()
+                .map(|(label, value)| (label.into(), value))
+                .collect(),
+            style: None,
+            default_expand: None,
+            filter_search_results: false,
+            record_snapshot: false,
+        }
+    }
+
+    /// Override the style of all documents. See [`JsonTree::style`].
+    /// Any [`JsonTreeStyle::root_label`] is replaced by the label of each document.
+    pub fn style(mut self, style: JsonTreeStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Override how all documents expand arrays/objects by default, e.g. to search all of them at once.
+    /// See [`JsonTree::default_expand`].
+    pub fn default_expand(mut self, default_expand: DefaultExpand<'a>) -> Self {
+        self.default_expand = Some(default_expand);
+        self
+    }
+
+    /// See [`JsonTree::filter_search_results`].
+    pub fn filter_search_results(mut self, filter_search_results: bool) -> Self {
+        self.filter_search_results = filter_search_results;
+        self
+    }
+
+    /// See [`JsonTree::record_snapshot`].
+    pub fn record_snapshot(mut self, record_snapshot: bool) -> Self {
+        self.record_snapshot = record_snapshot;
+        self
+    }
+
+    /// Show the documents within the `Ui`, one below the other.
+    pub fn show(self, ui: &mut Ui) -> JsonForestResponse {
+        let style = self.style.unwrap_or_default();
+        let default_expand = self.default_expand.unwrap_or_default();
+
+        let responses = ui
+            .vertical(|ui| {
+                self.documents
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, (label, value))| {
+                        JsonTree::new(self.id.with(idx), *value)
+                            .style(style.clone().root_label(label.as_str()))
+                            .default_expand(default_expand)
+                            .filter_search_results(self.filter_search_results)
+                            .record_snapshot(self.record_snapshot)
+                            .show(ui)
+                    })
+                    .collect()
+            })
+            .inner;
+
+        JsonForestResponse { responses }
+    }
+}
+
+/// The response from showing a [`JsonForest`].
+pub struct JsonForestResponse {
+    responses: Vec<JsonTreeResponse>,
+}
+
+impl JsonForestResponse {
+    /// Returns the responses of the trees of each document, in order.
+    pub fn responses(&self) -> &[JsonTreeResponse] {
+        &self.responses
+    }
+
+    /// Resets the expanded state of all documents to respect the `default_expand` setting.
+    /// See [`JsonTreeResponse::reset_expanded`].
+    pub fn reset_expanded(&self, ui: &mut Ui) {
+        for response in &self.responses {
+            response.reset_expanded(ui);
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! Fuzzy matching of search terms, scored in the style of Sublime Text's "Goto Anything".
+
+use std::ops::Range;
+
+/// The score of each matched character.
+const MATCH_SCORE: i32 = 1;
+/// The bonus for a matched character that directly follows the previously matched character.
+const CONSECUTIVE_BONUS: i32 = 5;
+/// The bonus for a matched character at the start of a word, e.g. after `_` or at a lowercase to uppercase transition.
+const WORD_START_BONUS: i32 = 8;
+/// The penalty for each unmatched character between two matched characters.
+const GAP_PENALTY: i32 = 1;
+/// The maximum penalty for unmatched characters before the first matched character.
+const MAX_LEADING_PENALTY: i32 = 3;
+
+/// Matches the characters of the term in order within the text, allowing gaps between them,
+/// e.g. `usrnm` matches `username`.
+///
+/// `term` and `text` are compared as given, while `original_text` is the text before any case normalization,
+/// with a character for each character of `text`, for detecting the start of words.
+///
+/// Returns the byte ranges of the matched characters for the best scoring alignment, merging adjacent characters,
+/// or `None` if the term is not a subsequence of the text, or its matched characters are too scattered to score positively.
+pub(crate) fn fuzzy_match(
+    term: &str,
+    text: &str,
+    original_text: &str,
+) -> Option<Vec<Range<usize>>> {
+    let term = term.chars().collect::<Vec<_>>();
+    let text_chars = text.char_indices().collect::<Vec<_>>();
+    let original_chars = original_text.chars().collect::<Vec<_>>();
+    if term.is_empty() || term.len() > text_chars.len() {
+        return None;
+    }
+
+    let is_word_start = |i: usize| {
+        let Some(prev) = i.checked_sub(1).and_then(|prev| original_chars.get(prev)) else {
+            return true;
+        };
+        let current = original_chars.get(i).copied().unwrap_or_default();
+        !prev.is_alphanumeric() || (prev.is_lowercase() && current.is_uppercase())
+    };
+
+    // `scores[j][i]` is the best score for matching the first `j + 1` characters of the term,
+    // with the last of them matched at the `i`th character of the text.
+    let n = text_chars.len();
+    let mut scores = vec![vec![None::<i32>; n]; term.len()];
+    let mut previous = vec![vec![0; n]; term.len()];
+
+    for (j, term_char) in term.iter().enumerate() {
+        // The best score of the previous term character matched at least two characters before `i`,
+        // less the penalty for the gap up to `i`, and where it was matched.
+        let mut best_gapped: Option<(i32, usize)> = None;
+        for (i, (_, text_char)) in text_chars.iter().enumerate() {
+            if j > 0 {
+                if let Some((score, _)) = &mut best_gapped {
+                    *score -= GAP_PENALTY;
+                }
+                if let Some(k) = i.checked_sub(2) {
+                    if let Some(score) = scores[j - 1][k] {
+                        best_gapped = best_gapped.max(Some((score - GAP_PENALTY, k)));
+                    }
+                }
+            }
+
+            if text_char != term_char {
+                continue;
+            }
+            let char_score = MATCH_SCORE
+                + if is_word_start(i) {
+                    WORD_START_BONUS
+                } else {
+                    0
+                };
+
+            if j == 0 {
+                scores[j][i] = Some(char_score - (i as i32).min(MAX_LEADING_PENALTY));
+                continue;
+            }
+
+            let consecutive = i
+                .checked_sub(1)
+                .and_then(|k| scores[j - 1][k].map(|score| (score + CONSECUTIVE_BONUS, k)));
+            if let Some((score, k)) = consecutive.max(best_gapped) {
+                scores[j][i] = Some(score + char_score);
+                previous[j][i] = k;
+            }
+        }
+    }
+
+    let last = term.len() - 1;
+    let (score, mut i) = (0..n)
+        .filter_map(|i| scores[last][i].map(|score| (score, i)))
+        .max_by_key(|(score, i)| (*score, std::cmp::Reverse(*i)))?;
+    if score <= 0 {
+        return None;
+    }
+
+    let mut matched = vec![i];
+    for j in (1..term.len()).rev() {
+        i = previous[j][i];
+        matched.push(i);
+    }
+    matched.reverse();
+
+    let mut ranges: Vec<Range<usize>> = vec![];
+    for i in matched {
+        let (start, c) = text_chars[i];
+        let end = start + c.len_utf8();
+        match ranges.last_mut() {
+            Some(range) if range.end == start => range.end = end,
+            _ => ranges.push(start..end),
+        }
+    }
+    Some(ranges)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Range;
+
+    use super::fuzzy_match;
+
+    fn fuzzy_match_lowercase(term: &str, text: &str) -> Option<Vec<Range<usize>>> {
+        fuzzy_match(term, &text.to_ascii_lowercase(), text)
+    }
+
+    #[test]
+    fn matches_subsequences() {
+        assert_eq!(
+            fuzzy_match_lowercase("usrnm", "username"),
+            Some(vec![0..2, 3..5, 6..7])
+        );
+        assert_eq!(fuzzy_match_lowercase("abc", "acb"), None);
+        assert_eq!(
+            fuzzy_match_lowercase("ab", &format!("a{}b", " ".repeat(20))),
+            None
+        );
+    }
+
+    #[test]
+    fn prefers_word_starts_and_consecutive_characters() {
+        // "ui" matches the start of "user" and "Id", rather than the first "u" and "i" of "build".
+        assert_eq!(
+            fuzzy_match_lowercase("ui", "buildUserId"),
+            Some(vec![5..6, 9..10])
+        );
+        assert_eq!(
+            fuzzy_match_lowercase("name", "first_name"),
+            Some(vec![Range { start: 6, end: 10 }])
+        );
+    }
+}
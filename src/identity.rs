@@ -0,0 +1,207 @@
+use std::{collections::HashSet, rc::Rc};
+
+use egui::Id;
+
+use crate::{
+    pointer::{parse_json_pointer_string, JsonPointerSegment},
+    value::{to_compact_json_string, JsonTreeValue, ToJsonTreeValue},
+};
+
+/// Derives the [`Id`]s of the paths in a tree, e.g. for storing the expanded state of arrays/objects.
+///
+/// Elements of arrays configured via [`JsonTree::element_identity`](crate::JsonTree::element_identity)
+/// are identified by the value of their identity field rather than their index,
+/// so that their state follows them when elements are inserted or removed.
+#[derive(Clone)]
+pub(crate) struct PathIds {
+    persistent_id: Id,
+    tree_id: Id,
+    arrays: Rc<Vec<ArrayIdentities>>,
+}
+
+struct ArrayIdentities {
+    /// The unescaped segments of the JSON pointer to the array.
+    array_segments: Vec<String>,
+    /// The identity of each element by index, prefixed by `#`,
+    /// or `None` if its identity field is missing or not unique within the array.
+    identities: Vec<Option<String>>,
+}
+
+impl PathIds {
+    pub(crate) fn new(persistent_id: Id, tree_id: Id) -> Self {
+        Self {
+            persistent_id,
+            tree_id,
+            arrays: Rc::new(vec![]),
+        }
+    }
+
+    /// Resolves the identity of each element of the configured arrays within the value,
+    /// given as pairs of JSON Pointer strings to the array and to the identity field relative to each element.
+    pub(crate) fn with_element_identities<T: ToJsonTreeValue>(
+        mut self,
+        value: &T,
+        element_identities: &[(String, String)],
+    ) -> Self {
+        let arrays = element_identities
+            .iter()
+            .filter_map(|(array_pointer, identity_pointer)| {
+                let array_segments = parse_json_pointer_string(array_pointer)?;
+                let identity_segments = parse_json_pointer_string(identity_pointer)?;
+                let JsonTreeValue::Expandable(elements, _) =
+                    find_value(value, &array_segments)?.to_json_tree_value()
+                else {
+                    return None;
+                };
+
+                let mut seen = HashSet::new();
+                let identities = elements
+                    .iter()
+                    .map(|(_, elem)| {
+                        let identity =
+                            find_value(*elem, &identity_segments).map(|identity| match identity
+                                .to_json_tree_value()
+                            {
+                                JsonTreeValue::Base(_, display_value, _) => {
+                                    display_value.to_string()
+                                }
+                                JsonTreeValue::Expandable(..) => to_compact_json_string(identity),
+                            })?;
+                        // Elements of an array otherwise only have index segments, so a non-numeric segment cannot collide with them.
+                        let identity = format!("#{identity}");
+                        seen.insert(identity.clone()).then_some(identity)
+                    })
+                    .collect();
+
+                Some(ArrayIdentities {
+                    array_segments,
+                    identities,
+                })
+            })
+            .collect();
+        self.arrays = Rc::new(arrays);
+        self
+    }
+
+    /// Returns the id under which state of the whole tree is stored.
+    pub(crate) fn tree_state_id(&self) -> Id {
+        self.persistent_id.with(self.tree_id)
+    }
+
+    /// Returns the id of the path to a value in the tree.
+    pub(crate) fn id(&self, path_segments: &[JsonPointerSegment]) -> Id {
+        let mut identity_segments: Option<Vec<JsonPointerSegment>> = None;
+        for array in self.arrays.iter() {
+            let len = array.array_segments.len();
+            let Some(JsonPointerSegment::Index(idx)) = path_segments.get(len) else {
+                continue;
+            };
+            let Some(Some(identity)) = array.identities.get(*idx) else {
+                continue;
+            };
+            if path_segments[..len]
+                .iter()
+                .zip(&array.array_segments)
+                .all(|(segment, array_segment)| segment_matches(segment, array_segment))
+            {
+                identity_segments.get_or_insert_with(|| path_segments.to_vec())[len] =
+                    JsonPointerSegment::Key(identity);
+            }
+        }
+
+        let path_segments = identity_segments.as_deref().unwrap_or(path_segments);
+        self.persistent_id.with(self.tree_id.with(path_segments))
+    }
+
+    /// Returns the id of the path given by the unescaped segments of a JSON Pointer string.
+    pub(crate) fn id_of_segments(&self, segments: &[String]) -> Id {
+        let path_segments = segments
+            .iter()
+            .map(|segment| match parse_index(segment) {
+                Some(idx) => JsonPointerSegment::Index(idx),
+                None => JsonPointerSegment::Key(segment),
+            })
+            .collect::<Vec<_>>();
+        self.id(&path_segments)
+    }
+}
+
+/// Parses an unescaped JSON Pointer segment as an array index, if it is one.
+pub(crate) fn parse_index(segment: &str) -> Option<usize> {
+    let is_index = segment == "0"
+        || (!segment.starts_with('0') && segment.bytes().all(|b| b.is_ascii_digit()));
+    is_index.then(|| segment.parse().ok()).flatten()
+}
+
+/// Returns whether a segment of a path equals an unescaped segment of a JSON Pointer string.
+pub(crate) fn segment_matches(segment: &JsonPointerSegment, unescaped: &str) -> bool {
+    match segment {
+        JsonPointerSegment::Key(key) => *key == unescaped,
+        JsonPointerSegment::Index(idx) => parse_index(unescaped) == Some(*idx),
+        JsonPointerSegment::DuplicateKey(..) => {
+            segment.to_json_pointer_segment_string()[1..]
+                .replace("~1", "/")
+                .replace("~0", "~")
+                == unescaped
+        }
+    }
+}
+
+/// Returns the value at the path given by the unescaped segments of a JSON Pointer string, if it exists.
+fn find_value<'a, T: ToJsonTreeValue>(value: &'a T, segments: &[String]) -> Option<&'a T> {
+    let mut current = value;
+    for segment in segments {
+        let JsonTreeValue::Expandable(entries, _) = current.to_json_tree_value() else {
+            return None;
+        };
+        current = entries
+            .into_iter()
+            .find(|(property, _)| segment_matches(property, segment))?
+            .1;
+    }
+    Some(current)
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use egui::Id;
+    use serde_json::json;
+
+    use crate::pointer::JsonPointerSegment;
+
+    use super::PathIds;
+
+    #[test]
+    fn identifies_elements_by_unique_identity() {
+        let value = json!({"items": [{"id": 1}, {"id": 2}, {"id": 1}, {}]});
+        let path_ids = PathIds::new(Id::new("ui"), Id::new("tree"))
+            .with_element_identities(&value, &[("/items".to_string(), "/id".to_string())]);
+        let plain_ids = PathIds::new(Id::new("ui"), Id::new("tree"));
+
+        let id = |path_ids: &PathIds, segment| {
+            path_ids.id(&[
+                JsonPointerSegment::Key("items"),
+                segment,
+                JsonPointerSegment::Key("id"),
+            ])
+        };
+        assert_eq!(
+            id(&path_ids, JsonPointerSegment::Index(1)),
+            id(&plain_ids, JsonPointerSegment::Key("#2"))
+        );
+        // Duplicate and missing identities fall back to the index.
+        for idx in [2, 3] {
+            assert_eq!(
+                id(&path_ids, JsonPointerSegment::Index(idx)),
+                id(&plain_ids, JsonPointerSegment::Index(idx))
+            );
+        }
+        assert_eq!(
+            path_ids.id_of_segments(&["items".to_string(), "0".to_string()]),
+            path_ids.id(&[
+                JsonPointerSegment::Key("items"),
+                JsonPointerSegment::Key("#1")
+            ])
+        );
+    }
+}
@@ -0,0 +1,442 @@
+//! Selection of values by JSONPath expressions. See [`DefaultExpand::JsonPath`](crate::DefaultExpand::JsonPath).
+
+use std::{collections::HashSet, fmt, iter::Peekable, str::CharIndices};
+
+use egui::Id;
+
+use crate::{
+    pointer::JsonPointerSegment,
+    search::{for_each_expandable_path_in, update_matches, SearchMatches},
+    value::{disambiguate_duplicate_keys, JsonTreeValue, ToJsonTreeValue},
+};
+
+/// An error from parsing a JSONPath expression given via [`DefaultExpand::JsonPath`](crate::DefaultExpand::JsonPath).
+/// See [`JsonTreeResponse::json_path_error`](crate::JsonTreeResponse::json_path_error).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPathError {
+    position: usize,
+    message: &'static str,
+}
+
+impl JsonPathError {
+    /// The byte offset within the expression at which parsing failed.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl fmt::Display for JsonPathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for JsonPathError {}
+
+/// A parsed JSONPath expression, supporting:
+/// - the root `$`,
+/// - child members `.name`, `['name']` and `["name"]`,
+/// - wildcards `.*` and `[*]`,
+/// - array indices `[0]`, counting from the end if negative, e.g. `[-1]`,
+/// - array slices `[start:end]`, where either bound may be omitted or negative,
+/// - unions of the above within brackets, e.g. `[0,2]` or `['a','b']`,
+/// - recursive descent `..name`, `..*` and `..[...]`.
+///
+/// Filter and script expressions are not supported.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct JsonPath {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    /// Selects from the children of each selected value.
+    Child(Vec<Selector>),
+    /// Selects from the children of each selected value and all of its descendants.
+    Descendant(Vec<Selector>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Selector {
+    Name(String),
+    Wildcard,
+    Index(i64),
+    Slice(Option<i64>, Option<i64>),
+}
+
+impl JsonPath {
+    pub(crate) fn parse(expression: &str) -> Result<Self, JsonPathError> {
+        Parser {
+            chars: expression.char_indices().peekable(),
+            len: expression.len(),
+        }
+        .parse()
+    }
+
+    /// Returns the values selected by this expression as search matches,
+    /// so that they are expanded and counted in the same way as the matches of a search term.
+    pub(crate) fn find_selected_paths_in<T: ToJsonTreeValue>(
+        &self,
+        value: &T,
+        make_persistent_id: &dyn Fn(&[JsonPointerSegment]) -> Id,
+        reset_path_ids: &mut HashSet<Id>,
+    ) -> SearchMatches {
+        for_each_expandable_path_in(value, &mut |path_segments| {
+            reset_path_ids.insert(make_persistent_id(path_segments));
+        });
+
+        let mut search_matches = SearchMatches::default();
+        self.select(value, &mut |path_segments| {
+            update_matches(path_segments, &mut search_matches, make_persistent_id);
+        });
+        search_matches
+    }
+
+    /// Calls `f` with the path to each value selected by this expression.
+    /// A value may be selected more than once, e.g. via overlapping recursive descents.
+    pub(crate) fn select<'a, T: ToJsonTreeValue>(
+        &self,
+        value: &'a T,
+        f: &mut dyn FnMut(&[JsonPointerSegment<'a>]),
+    ) {
+        let mut selected = vec![(vec![], value)];
+        for segment in &self.segments {
+            let mut next = vec![];
+            for (path_segments, value) in selected {
+                match segment {
+                    Segment::Child(selectors) => {
+                        select_children(value, &path_segments, selectors, &mut next);
+                    }
+                    Segment::Descendant(selectors) => {
+                        // Visit the descendants depth-first in document order.
+                        let mut descendants = vec![(path_segments, value)];
+                        while let Some((path_segments, value)) = descendants.pop() {
+                            select_children(value, &path_segments, selectors, &mut next);
+                            let mut children = vec![];
+                            select_children(
+                                value,
+                                &path_segments,
+                                &[Selector::Wildcard],
+                                &mut children,
+                            );
+                            descendants.extend(children.into_iter().rev());
+                        }
+                    }
+                }
+            }
+            selected = next;
+        }
+
+        for (path_segments, _) in selected {
+            f(&path_segments);
+        }
+    }
+}
+
+fn select_children<'a, T: ToJsonTreeValue>(
+    value: &'a T,
+    path_segments: &[JsonPointerSegment<'a>],
+    selectors: &[Selector],
+    selected: &mut Vec<(Vec<JsonPointerSegment<'a>>, &'a T)>,
+) {
+    let JsonTreeValue::Expandable(mut entries, _) = value.to_json_tree_value() else {
+        return;
+    };
+    disambiguate_duplicate_keys(value, &mut entries);
+
+    let mut push = |property: JsonPointerSegment<'a>, elem: &'a T| {
+        let mut child_path_segments = path_segments.to_vec();
+        child_path_segments.push(property);
+        selected.push((child_path_segments, elem));
+    };
+
+    for selector in selectors {
+        match selector {
+            Selector::Wildcard => {
+                for (property, elem) in &entries {
+                    push(*property, elem);
+                }
+            }
+            Selector::Name(name) => {
+                for (property, elem) in &entries {
+                    if let JsonPointerSegment::Key(key) | JsonPointerSegment::DuplicateKey(key, _) =
+                        property
+                    {
+                        if key == name {
+                            push(*property, elem);
+                        }
+                    }
+                }
+            }
+            Selector::Index(index) => {
+                if let Some((property, elem)) = resolve_index(*index, &entries)
+                    .and_then(|index| entries.get(index))
+                    .filter(|(property, _)| matches!(property, JsonPointerSegment::Index(_)))
+                {
+                    push(*property, elem);
+                }
+            }
+            Selector::Slice(start, end) => {
+                if !matches!(entries.first(), Some((JsonPointerSegment::Index(_), _))) {
+                    continue;
+                }
+                let len = entries.len() as i64;
+                let clamp =
+                    |bound: i64| (if bound < 0 { len + bound } else { bound }).clamp(0, len);
+                let start = start.map_or(0, clamp);
+                let end = end.map_or(len, clamp);
+                for (property, elem) in entries.iter().take(end as usize).skip(start as usize) {
+                    push(*property, elem);
+                }
+            }
+        }
+    }
+}
+
+fn resolve_index<T>(index: i64, entries: &[T]) -> Option<usize> {
+    let index = if index < 0 {
+        entries.len() as i64 + index
+    } else {
+        index
+    };
+    usize::try_from(index).ok()
+}
+
+struct Parser<'e> {
+    chars: Peekable<CharIndices<'e>>,
+    len: usize,
+}
+
+impl<'e> Parser<'e> {
+    fn parse(mut self) -> Result<JsonPath, JsonPathError> {
+        if !self.eat('$') {
+            return Err(self.error("expected `$`"));
+        }
+
+        let mut segments = vec![];
+        while let Some(c) = self.peek() {
+            let segment = match c {
+                '.' => {
+                    self.next();
+                    if self.eat('.') {
+                        let selectors = if self.peek() == Some('[') {
+                            self.next();
+                            self.parse_bracketed_selectors()?
+                        } else {
+                            vec![self.parse_dot_selector()?]
+                        };
+                        Segment::Descendant(selectors)
+                    } else {
+                        Segment::Child(vec![self.parse_dot_selector()?])
+                    }
+                }
+                '[' => {
+                    self.next();
+                    Segment::Child(self.parse_bracketed_selectors()?)
+                }
+                _ => return Err(self.error("expected `.` or `[`")),
+            };
+            segments.push(segment);
+        }
+
+        Ok(JsonPath { segments })
+    }
+
+    fn parse_dot_selector(&mut self) -> Result<Selector, JsonPathError> {
+        if self.eat('*') {
+            return Ok(Selector::Wildcard);
+        }
+        let mut name = String::new();
+        while let Some(c) = self.peek().filter(|c| !matches!(c, '.' | '[')) {
+            if c.is_whitespace() {
+                return Err(self.error("unexpected whitespace"));
+            }
+            name.push(c);
+            self.next();
+        }
+        if name.is_empty() {
+            return Err(self.error("expected a member name or `*`"));
+        }
+        Ok(Selector::Name(name))
+    }
+
+    /// Parses the comma-separated selectors following a `[`, up to and including the closing `]`.
+    fn parse_bracketed_selectors(&mut self) -> Result<Vec<Selector>, JsonPathError> {
+        let mut selectors = vec![];
+        loop {
+            self.skip_whitespace();
+            selectors.push(self.parse_bracketed_selector()?);
+            self.skip_whitespace();
+            if self.eat(']') {
+                return Ok(selectors);
+            }
+            if !self.eat(',') {
+                return Err(self.error("expected `,` or `]`"));
+            }
+        }
+    }
+
+    fn parse_bracketed_selector(&mut self) -> Result<Selector, JsonPathError> {
+        match self.peek() {
+            Some('*') => {
+                self.next();
+                Ok(Selector::Wildcard)
+            }
+            Some(quote @ ('\'' | '"')) => {
+                self.next();
+                self.parse_quoted_name(quote).map(Selector::Name)
+            }
+            Some(c) if c == '-' || c == ':' || c.is_ascii_digit() => {
+                let start = self.parse_optional_integer()?;
+                self.skip_whitespace();
+                if !self.eat(':') {
+                    return start
+                        .map(Selector::Index)
+                        .ok_or_else(|| self.error("expected an integer"));
+                }
+                self.skip_whitespace();
+                let end = self.parse_optional_integer()?;
+                Ok(Selector::Slice(start, end))
+            }
+            _ => Err(self.error("expected a quoted name, an integer, a slice or `*`")),
+        }
+    }
+
+    fn parse_quoted_name(&mut self, quote: char) -> Result<String, JsonPathError> {
+        let mut name = String::new();
+        loop {
+            match self.next() {
+                Some(c) if c == quote => return Ok(name),
+                Some('\\') => match self.next() {
+                    Some(c) => name.push(c),
+                    None => return Err(self.error("unterminated escape sequence")),
+                },
+                Some(c) => name.push(c),
+                None => return Err(self.error("unterminated quoted name")),
+            }
+        }
+    }
+
+    fn parse_optional_integer(&mut self) -> Result<Option<i64>, JsonPathError> {
+        let position = self.position();
+        let mut digits = String::new();
+        if self.eat('-') {
+            digits.push('-');
+        }
+        while let Some(c) = self.peek().filter(char::is_ascii_digit) {
+            digits.push(c);
+            self.next();
+        }
+        if digits.is_empty() {
+            return Ok(None);
+        }
+        digits.parse().map(Some).map_err(|_| JsonPathError {
+            position,
+            message: "invalid integer",
+        })
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next();
+        }
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|(_, c)| *c)
+    }
+
+    fn next(&mut self) -> Option<char> {
+        self.chars.next().map(|(_, c)| c)
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        let is_expected = self.peek() == Some(expected);
+        if is_expected {
+            self.next();
+        }
+        is_expected
+    }
+
+    fn position(&mut self) -> usize {
+        self.chars.peek().map_or(self.len, |(i, _)| *i)
+    }
+
+    fn error(&mut self, message: &'static str) -> JsonPathError {
+        JsonPathError {
+            position: self.position(),
+            message,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::json;
+
+    use crate::pointer::JsonPointer;
+
+    use super::JsonPath;
+
+    fn select(expression: &str, value: &serde_json::Value) -> Vec<String> {
+        let mut pointers = vec![];
+        JsonPath::parse(expression)
+            .unwrap()
+            .select(value, &mut |path_segments| {
+                pointers.push(JsonPointer(path_segments).to_json_pointer_string());
+            });
+        pointers.sort();
+        pointers
+    }
+
+    #[test]
+    fn selects_values() {
+        let value = json!({
+            "store": {
+                "book": [
+                    {"author": "Rees", "title": "Sayings"},
+                    {"author": "Waugh", "title": "Sword"},
+                    {"title": "Anonymous"}
+                ],
+                "bicycle": {"color": "red"}
+            }
+        });
+
+        assert_eq!(select("$", &value), vec![""]);
+        assert_eq!(
+            select("$.store.book[*].author", &value),
+            vec!["/store/book/0/author", "/store/book/1/author"]
+        );
+        assert_eq!(select("$['store'].bicycle", &value), vec!["/store/bicycle"]);
+        assert_eq!(select("$..color", &value), vec!["/store/bicycle/color"]);
+        assert_eq!(
+            select("$..book[-1, 0]", &value),
+            vec!["/store/book/0", "/store/book/2"]
+        );
+        assert_eq!(
+            select("$.store.book[1:]", &value),
+            vec!["/store/book/1", "/store/book/2"]
+        );
+        assert_eq!(select("$.store.book[5]", &value), Vec::<String>::new());
+        assert_eq!(select("$.store[0]", &value), Vec::<String>::new());
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        for (expression, expected) in [
+            ("store", "expected `$` at position 0"),
+            ("$.", "expected a member name or `*` at position 2"),
+            ("$[0", "expected `,` or `]` at position 3"),
+            ("$['a]", "unterminated quoted name at position 5"),
+            (
+                "$[?(@.a)]",
+                "expected a quoted name, an integer, a slice or `*` at position 2",
+            ),
+        ] {
+            assert_eq!(
+                JsonPath::parse(expression).unwrap_err().to_string(),
+                expected
+            );
+        }
+    }
+}
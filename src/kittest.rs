@@ -0,0 +1,122 @@
+//! Helpers for driving a [`JsonTree`](crate::JsonTree) in [`egui_kittest`] tests, without pixel assertions.
+//!
+//! Each row of the tree is exposed to AccessKit as a tree item identified by the JSON Pointer string of its value,
+//! so rows can be queried, expanded and asserted on by pointer:
+//! ```rust
+//! # use egui_json_tree::{kittest::JsonTreeHarnessExt, JsonTree};
+//! # use egui_kittest::Harness;
+//! let value = serde_json::json!({ "foo": { "bar": 1 } });
+//! let mut harness = Harness::new_ui(|ui| {
+//!     JsonTree::new("tree", &value).show(ui);
+//! });
+//!
+//! assert_eq!(harness.json_tree_rows(), vec![""]);
+//!
+//! harness.click_json_tree_expander("");
+//! harness.click_json_tree_expander("/foo");
+//!
+//! assert_eq!(harness.json_tree_rows(), vec!["", "/foo", "/foo/bar"]);
+//! assert_eq!(harness.json_tree_row_text("/foo/bar"), "\"bar\": 1");
+//! ```
+//!
+//! If a [`Harness`] shows multiple trees, rows with the same pointer in different trees cannot be told apart.
+
+use egui::accesskit::Role;
+use egui_kittest::{
+    kittest::{Node, Queryable},
+    Harness,
+};
+
+/// Extension methods for querying and interacting with a [`JsonTree`](crate::JsonTree) shown in a [`Harness`].
+pub trait JsonTreeHarnessExt {
+    /// Returns the row for the value at the given JSON Pointer string, if it was shown.
+    fn query_json_tree_row<'a>(&'a self, pointer: &'a str) -> Option<Node<'a>>;
+
+    /// Returns the row for the value at the given JSON Pointer string.
+    ///
+    /// # Panics
+    /// If no row was shown for the pointer.
+    fn json_tree_row<'a>(&'a self, pointer: &'a str) -> Node<'a>;
+
+    /// Returns the JSON Pointer strings of all rows shown in the last frame, in order.
+    ///
+    /// The closing delimiters of expanded arrays/objects are not counted as rows.
+    fn json_tree_rows(&self) -> Vec<String>;
+
+    /// Returns the text displayed in the row for the value at the given JSON Pointer string.
+    ///
+    /// # Panics
+    /// If no row was shown for the pointer.
+    fn json_tree_row_text(&self, pointer: &str) -> String;
+
+    /// Clicks the expander of the array/object at the given JSON Pointer string,
+    /// then runs the harness until any expand/collapse animation has finished.
+    ///
+    /// # Panics
+    /// If no row was shown for the pointer, or the row has no expander, e.g. because toggle buttons are hidden.
+    fn click_json_tree_expander(&mut self, pointer: &str);
+
+    /// Types into the search bar, i.e. the only text input shown,
+    /// then runs the harness until any expand/collapse animation has finished.
+    /// The text is inserted at the cursor, which is at the end of any existing text in a newly focused text input.
+    ///
+    /// # Panics
+    /// If there is not exactly one text input shown.
+    fn type_json_tree_search(&mut self, text: &str);
+}
+
+impl<State> JsonTreeHarnessExt for Harness<'_, State> {
+    fn query_json_tree_row<'a>(&'a self, pointer: &'a str) -> Option<Node<'a>> {
+        self.query_by(move |node| is_row(node) && node.author_id() == Some(pointer))
+    }
+
+    fn json_tree_row<'a>(&'a self, pointer: &'a str) -> Node<'a> {
+        self.query_json_tree_row(pointer)
+            .unwrap_or_else(|| panic!("No JsonTree row was shown for pointer {pointer:?}"))
+    }
+
+    fn json_tree_rows(&self) -> Vec<String> {
+        self.query_all_by(is_row)
+            .filter_map(|node| node.author_id().map(str::to_string))
+            .collect()
+    }
+
+    fn json_tree_row_text(&self, pointer: &str) -> String {
+        self.json_tree_row(pointer)
+            .query_all_by_role(Role::Label)
+            .filter_map(|node| node.value())
+            .collect()
+    }
+
+    fn click_json_tree_expander(&mut self, pointer: &str) {
+        self.json_tree_row(pointer)
+            .query_by_role(Role::Button)
+            .unwrap_or_else(|| panic!("The JsonTree row for pointer {pointer:?} has no expander"))
+            .click();
+        run_until_settled(self);
+    }
+
+    fn type_json_tree_search(&mut self, text: &str) {
+        let search_bar = self.get_by_role(Role::TextInput);
+        search_bar.focus();
+        search_bar.type_text(text);
+        run_until_settled(self);
+    }
+}
+
+/// The maximum number of frames to run while waiting for animations to finish.
+const MAX_SETTLE_STEPS: usize = 100;
+
+fn run_until_settled<State>(harness: &mut Harness<'_, State>) {
+    harness.run();
+    for _ in 0..MAX_SETTLE_STEPS {
+        if !harness.ctx.has_requested_repaint() {
+            break;
+        }
+        harness.step();
+    }
+}
+
+fn is_row(node: &Node<'_>) -> bool {
+    node.role() == Role::TreeItem && node.author_id().is_some()
+}
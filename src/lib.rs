@@ -72,25 +72,85 @@
 //! | `serde_json`       | `serde_json::Value`       | Yes     |
 //! | `simd_json`        | `simd_json::owned::Value` | No      |
 //!
+//! The optional `serde` feature enables (de)serialization of [`JsonTreeSettings`], [`JsonTreeProfile`]s and [`recording::JsonTreeInteraction`]s.
+//! The `persistence` feature additionally enables egui's `persistence` feature, and provides `JsonTreeExpansionState`
+//! for saving and restoring which arrays/objects of a tree are expanded.
+//!
+//! The optional `accesskit` feature exposes each row of the tree to AccessKit,
+//! and the `kittest` feature additionally provides helpers for testing with `egui_kittest` in the `kittest` module.
+//!
 //! If you wish to use a different JSON type, see the [`value`](mod@value) module,
 //! and disable default features in your `Cargo.toml` if you do not need the `serde_json` dependency.
+mod background_search;
+mod bracket_matching;
+mod columns;
 mod default_expand;
+mod drop_target;
+mod empty;
+mod epoch;
+#[cfg(feature = "persistence")]
+mod expansion_state;
+mod forest;
+mod fuzzy;
+mod identity;
+mod json_path;
 mod node;
+mod profile;
 mod response;
+mod schema_fields;
+mod scroll;
+mod scroll_markers;
 mod search;
+mod search_history;
+mod settings;
+mod shared_search;
+mod snapshot;
+mod split_view;
 mod style;
+mod subtree;
+mod subtree_hash;
+mod summary;
+mod tabular;
 mod toggle_buttons_state;
 mod tree;
+mod uuid;
+mod value_editor;
 
+pub mod context_menu;
 pub mod delimiters;
+pub mod document;
+#[cfg(feature = "kittest")]
+pub mod kittest;
 pub mod pointer;
+pub mod recording;
 pub mod render;
 pub mod value;
 
-pub use default_expand::DefaultExpand;
-pub use response::JsonTreeResponse;
+pub use background_search::JsonTreeBackgroundSearch;
+pub use default_expand::{DefaultExpand, ExpandOverride};
+pub use document::JsonTreeDocument;
+pub use drop_target::JsonTreeDropTarget;
+pub use empty::JsonTreeEmptyReason;
+#[cfg(feature = "persistence")]
+pub use expansion_state::JsonTreeExpansionState;
+pub use forest::{JsonForest, JsonForestResponse};
+pub use json_path::JsonPathError;
+pub use profile::JsonTreeProfile;
+pub use response::{JsonTreeMemoryUsage, JsonTreeResponse};
+pub use schema_fields::JsonTreeSchemaField;
+pub use scroll_markers::JsonTreeScrollMarker;
+pub use search::{SearchCandidate, SearchOptions, SearchScope, SearchWords};
+pub use settings::JsonTreeSettings;
+pub use shared_search::JsonTreeSharedSearch;
+pub use snapshot::{JsonTreeSnapshot, JsonTreeSnapshotRow};
+pub use split_view::{JsonTreeSplitView, JsonTreeSplitViewResponse};
 pub use style::{
-    JsonTreeMaxWidth, JsonTreeStyle, JsonTreeVisuals, JsonTreeWrapping, JsonTreeWrappingConfig,
+    EpochTimestampConfig, EpochUnit, JsonTreeBracketMatching, JsonTreeClosingDelimiters,
+    JsonTreeDensity, JsonTreeFontFamilies, JsonTreeLayout, JsonTreeLongStringViewer,
+    JsonTreeMaxWidth, JsonTreeProvenanceDisplay, JsonTreeScrollAnimation, JsonTreeSeverityStyling,
+    JsonTreeStyle, JsonTreeVisuals, JsonTreeWrapping, JsonTreeWrappingConfig, UuidDisplay,
 };
+pub use subtree::JsonTreeSubtreeConfig;
 pub use toggle_buttons_state::ToggleButtonsState;
 pub use tree::JsonTree;
+pub use value_editor::JsonTreeNumericDrag;
@@ -1,87 +1,515 @@
-use std::collections::HashSet;
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
 
 use egui::{
     collapsing_header::{paint_default_icon, CollapsingState},
-    Id, Ui,
+    vec2, Frame, Id, InnerResponse, Modifiers, Rect, Response, ScrollArea, Shape, Ui, WidgetInfo,
+    WidgetType,
 };
+use web_time::Instant;
 
 use crate::{
+    bracket_matching::BracketMatching,
+    columns::KeyColumn,
     delimiters::{SpacingDelimiter, ARRAY_DELIMITERS, OBJECT_DELIMITERS},
-    pointer::{JsonPointer, JsonPointerSegment},
+    drop_target::DropTargetRecorder,
+    empty::JsonTreeEmptyReason,
+    identity::{segment_matches, PathIds},
+    json_path::JsonPath,
+    pointer::{parse_json_pointer_string, JsonPointer, JsonPointerSegment},
     render::{
+        render_condensed_path, render_expansion_progress, render_group_heading,
+        render_hidden_entries_count, render_long_string, render_match_count_badge,
+        render_missing_field, render_root_label, render_search_progress, render_trailing_comment,
         JsonTreeRenderer, ParentStatus, RenderBaseValueContext, RenderExpandableDelimiterContext,
         RenderPropertyContext, RenderSpacingDelimiterContext,
     },
     response::JsonTreeResponse,
-    search::SearchTerm,
-    value::{ExpandableType, JsonTreeValue, ToJsonTreeValue},
-    DefaultExpand, JsonTree, JsonTreeStyle, ToggleButtonsState,
+    schema_fields::{JsonTreeSchemaField, SchemaFields},
+    scroll::ScrollToPointer,
+    scroll_markers::ScrollMarkerRecorder,
+    search::{
+        current_match_id, find_expandable_path_ids_in, find_paths_matching_in,
+        focus_current_match_id, SearchMatches, SearchOptions, SearchTarget, SearchTerm,
+    },
+    search_history::{load_search_history, record_search},
+    snapshot::{record_row, SnapshotRecorder},
+    subtree::{find_subtree, split_subtrees, SubtreeOverride},
+    subtree_hash::SubtreeHash,
+    summary::NumericSummary,
+    value::{
+        disambiguate_duplicate_keys, BaseValueType, ExpandableType, JsonTreeValue, ToJsonTreeValue,
+    },
+    DefaultExpand, ExpandOverride, JsonTree, JsonTreeBackgroundSearch, JsonTreeBracketMatching,
+    JsonTreeClosingDelimiters, JsonTreeLayout, JsonTreeLongStringViewer, JsonTreeProvenanceDisplay,
+    JsonTreeStyle, ToggleButtonsState,
 };
 
+/// A closure that returns the heading of the group that the entry with the given key belongs to, within the object at the JSON pointer.
+pub(crate) type EntryGroups<'a> = dyn Fn(JsonPointer, &str) -> Option<String> + 'a;
+
+/// A closure that returns the provenance of the value at the JSON pointer, e.g. the config file and line it came from.
+pub(crate) type Provenance<'a> = dyn Fn(JsonPointer) -> Option<String> + 'a;
+
+/// A hook that is called with the JSON pointer to an array/object and whether it is now expanded, whenever it is expanded or collapsed.
+pub(crate) type ToggleHook<'a> = dyn FnMut(JsonPointer, bool) + 'a;
+
+type Entries<'a, T> = Vec<(JsonPointerSegment<'a>, &'a T)>;
+
 pub(crate) struct JsonTreeNode<'a, 'b, T: ToJsonTreeValue> {
     value: &'a T,
     parent: Option<JsonPointerSegment<'a>>,
     make_persistent_id: &'b dyn Fn(&[JsonPointerSegment]) -> Id,
-    config: &'b JsonTreeNodeConfig,
+    config: &'b JsonTreeNodeConfig<'a>,
+    /// The style of the subtree containing this node, if configured via [`JsonTree::subtree`], otherwise that of the tree.
+    style: &'b JsonTreeStyle,
+    /// The default expansion of the innermost subtree containing this node that configures it,
+    /// along with the number of segments in the JSON pointer of that subtree.
+    subtree_expand: Option<(usize, ExpandOverride)>,
+    /// If present, only entries that are or contain search matches are shown.
+    search_filter: Option<&'b SearchMatches>,
 }
 
 impl<'a, 'b, T: ToJsonTreeValue> JsonTreeNode<'a, 'b, T> {
     pub(crate) fn show(tree: JsonTree<'a, T>, ui: &mut Ui) -> JsonTreeResponse {
+        let frame_start = Instant::now();
         let persistent_id = ui.id();
         let tree_id = tree.id;
-        let make_persistent_id =
-            |path_segments: &[JsonPointerSegment]| persistent_id.with(tree_id.with(path_segments));
+        let tree_state_id = persistent_id.with(tree_id);
+        let frame_budget_exceeded_id = tree_state_id.with("frame_budget_exceeded");
+        let path_ids = PathIds::new(persistent_id, tree_id)
+            .with_element_identities(tree.value, &tree.config.element_identities);
+        let make_persistent_id = |path_segments: &[JsonPointerSegment]| path_ids.id(path_segments);
+        #[cfg(feature = "persistence")]
+        if let Some(expansion_state) = tree.config.expansion_state {
+            expansion_state.restore_once(ui.ctx(), &path_ids);
+        }
 
         let style = tree.config.style.unwrap_or_default();
         let default_expand = tree.config.default_expand.unwrap_or_default();
 
         let mut reset_path_ids = HashSet::new();
 
-        let (default_expand, search_term) = match default_expand {
-            DefaultExpand::All => (InnerExpand::All, None),
-            DefaultExpand::None => (InnerExpand::None, None),
-            DefaultExpand::ToLevel(l) => (InnerExpand::ToLevel(l), None),
-            DefaultExpand::SearchResults(search_str) => {
-                let search_term = SearchTerm::parse(search_str);
-                let search_match_path_ids = search_term
-                    .as_ref()
-                    .map(|search_term| {
-                        search_term.find_matching_paths_in(
-                            tree.value,
-                            style.abbreviate_root,
-                            &make_persistent_id,
-                            &mut reset_path_ids,
-                        )
-                    })
-                    .unwrap_or_default();
-                (InnerExpand::Paths(search_match_path_ids), search_term)
+        let shared_search = tree.config.shared_search.as_ref().map(|s| s.load());
+        if let Some((_, generation)) = &shared_search {
+            reset_expanded_if_shared_search_changed(
+                ui,
+                tree.value,
+                tree_state_id,
+                *generation,
+                &make_persistent_id,
+            );
+        }
+
+        let background_search = tree.config.background_search.as_ref();
+        let is_background_searching =
+            background_search.is_some_and(JsonTreeBackgroundSearch::is_searching);
+        let completed_search = background_search.and_then(JsonTreeBackgroundSearch::completed);
+        let background_search_completed = completed_search.as_ref().is_some_and(|completed| {
+            background_search_generation_changed(ui, tree_state_id, completed.generation)
+        });
+
+        let search_str = completed_search
+            .as_ref()
+            .map(|completed| completed.term.as_str())
+            .or(shared_search.as_ref().map(|(term, _)| term.as_str()))
+            .or(tree.config.search_term);
+        let search_options = match (&completed_search, default_expand) {
+            (Some(completed), _) => completed.options,
+            (None, DefaultExpand::SearchResultsWith(_, search_options))
+            | (None, DefaultExpand::SearchResultsAll(_, search_options))
+            | (None, DefaultExpand::SearchResultsAny(_, search_options)) => search_options,
+            _ => SearchOptions::default(),
+        };
+        let search_term = match (search_str, default_expand) {
+            (Some(search_str), _)
+            | (None, DefaultExpand::SearchResults(search_str))
+            | (None, DefaultExpand::SearchResultsWith(search_str, _)) => {
+                SearchTerm::parse(search_str, search_options)
+            }
+            (None, DefaultExpand::SearchResultsAll(search_strs, _)) => {
+                SearchTerm::parse_all(search_strs, search_options, true)
             }
+            (None, DefaultExpand::SearchResultsAny(search_strs, _)) => {
+                SearchTerm::parse_all(search_strs, search_options, false)
+            }
+            _ => None,
+        };
+        let search_history = match (search_str, default_expand) {
+            (Some(search_str), _)
+            | (None, DefaultExpand::SearchResults(search_str))
+            | (None, DefaultExpand::SearchResultsWith(search_str, _)) => {
+                record_search(ui.ctx(), tree_state_id, search_str)
+            }
+            _ => load_search_history(ui.ctx(), tree_state_id),
+        };
+        let search_term = match style.search_debounce {
+            Some(debounce) => {
+                let (search_term, applied) =
+                    debounce_search_term(ui, tree_state_id, search_term, debounce);
+                if applied {
+                    reset_expanded_in(ui, tree.value, &make_persistent_id);
+                }
+                search_term
+            }
+            None => search_term,
+        };
+        // A background search always searches the whole value.
+        let search_root = tree
+            .config
+            .search_root
+            .filter(|_| background_search.is_none())
+            .map(parse_json_pointer_string);
+        let search_predicate = match (search_str, default_expand) {
+            (None, DefaultExpand::SearchWith(predicate)) => Some(predicate),
+            _ => None,
+        };
+        // Nothing is searched beneath an invalid JSON Pointer string.
+        let search_term = search_term.filter(|_| !matches!(search_root, Some(None)));
+        let search_predicate = search_predicate.filter(|_| !matches!(search_root, Some(None)));
+        let search_root = search_root.flatten().unwrap_or_default();
+        let search_term_applied = tree.config.scroll_to_first_match
+            && search_term.is_some()
+            && search_term_changed(ui, tree_state_id, Id::new((&search_term, &search_root)));
+        let json_path = match default_expand {
+            DefaultExpand::JsonPath(expression) => Some(JsonPath::parse(expression)),
+            _ => None,
+        };
+        let search_matches = match &json_path {
+            Some(json_path) => json_path.as_ref().ok().map(|json_path| {
+                Arc::new(json_path.find_selected_paths_in(
+                    tree.value,
+                    &make_persistent_id,
+                    &mut reset_path_ids,
+                ))
+            }),
+            None if search_predicate.is_some() => search_predicate
+                .filter(|_| !tree.config.highlight_only)
+                .map(|predicate| {
+                    Arc::new(find_paths_matching_in(
+                        tree.value,
+                        predicate,
+                        &search_root,
+                        style.abbreviate_root,
+                        &make_persistent_id,
+                        &mut reset_path_ids,
+                    ))
+                }),
+            None if completed_search.is_some() => completed_search
+                .as_ref()
+                .filter(|_| search_term.is_some() && !tree.config.highlight_only)
+                .map(|completed| {
+                    SearchMatches::load_or_find(
+                        ui.ctx(),
+                        tree_state_id,
+                        Some(completed.generation),
+                        Id::new(("background_search", style.abbreviate_root)),
+                        &mut reset_path_ids,
+                        |_, reset_path_ids| {
+                            SearchMatches::from_pointers(
+                                &completed.matched_pointers,
+                                &path_ids,
+                                style.abbreviate_root,
+                                reset_path_ids,
+                            )
+                        },
+                    )
+                }),
+            None => search_term
+                .as_ref()
+                .filter(|_| !tree.config.highlight_only)
+                .map(|search_term| {
+                    SearchMatches::load_or_find(
+                        ui.ctx(),
+                        tree_state_id,
+                        tree.config.value_version,
+                        Id::new((search_term, &search_root, style.abbreviate_root)),
+                        &mut reset_path_ids,
+                        |previous, reset_path_ids| {
+                            previous
+                                .and_then(|(previous_version, previous)| {
+                                    let changed_pointers =
+                                        tree.config.document_changes.as_ref()?.changed_pointers(
+                                            previous_version,
+                                            tree.config.value_version?,
+                                        )?;
+                                    search_term.update_matching_paths_in(
+                                        tree.value,
+                                        previous,
+                                        &changed_pointers,
+                                        &search_root,
+                                        style.abbreviate_root,
+                                        &path_ids,
+                                        reset_path_ids,
+                                    )
+                                })
+                                .unwrap_or_else(|| {
+                                    search_term.find_matching_paths_in(
+                                        tree.value,
+                                        &search_root,
+                                        style.abbreviate_root,
+                                        &make_persistent_id,
+                                        reset_path_ids,
+                                    )
+                                })
+                        },
+                    )
+                }),
+        };
+
+        let default_expand = match default_expand {
+            DefaultExpand::All => InnerExpand::All,
+            DefaultExpand::None => InnerExpand::None,
+            DefaultExpand::ToLevel(l) => InnerExpand::ToLevel(l, vec![]),
+            DefaultExpand::ToLevelWith(l, overrides) => InnerExpand::ToLevel(
+                l,
+                overrides
+                    .iter()
+                    .filter_map(|(pointer, expand)| {
+                        Some((parse_json_pointer_string(pointer)?, *expand))
+                    })
+                    .collect(),
+            ),
+            DefaultExpand::SearchResults(_)
+            | DefaultExpand::SearchResultsWith(..)
+            | DefaultExpand::SearchResultsAll(..)
+            | DefaultExpand::SearchResultsAny(..)
+            | DefaultExpand::SearchWith(_)
+            | DefaultExpand::JsonPath(_) => InnerExpand::SearchResults {
+                expand_matches: search_options.expand_match_children,
+            },
+            DefaultExpand::ToPointer(pointer) => InnerExpand::Ids(
+                parse_json_pointer_string(pointer)
+                    .map(|segments| {
+                        (0..segments.len())
+                            .map(|len| path_ids.id_of_segments(&segments[..len]))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            ),
+            DefaultExpand::Important(pointers) => InnerExpand::Ids(
+                pointers
+                    .iter()
+                    .filter_map(|pointer| parse_json_pointer_string(pointer))
+                    .flat_map(|segments| {
+                        (0..=segments.len())
+                            .map(|len| path_ids.id_of_segments(&segments[..len]))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect(),
+            ),
+        };
+
+        let scroll_markers = (!tree.config.scroll_markers.is_empty()
+            || tree.config.search_match_scroll_marker_color.is_some())
+        .then(|| {
+            ScrollMarkerRecorder::new(
+                &tree.config.scroll_markers,
+                tree.config.search_match_scroll_marker_color,
+                &make_persistent_id,
+            )
+        });
+
+        let drop_target = tree
+            .config
+            .accepts_drop
+            .and_then(|accepts_payload| DropTargetRecorder::start(ui, accepts_payload));
+
+        let key_column = match style.layout {
+            JsonTreeLayout::Inline => None,
+            JsonTreeLayout::Columns { key_column_width } => Some(KeyColumn::load(
+                ui,
+                tree_state_id,
+                ui.cursor().left(),
+                key_column_width,
+            )),
         };
 
         let mut renderer = tree.config.renderer;
+        let (subtrees, subtree_render_hooks) = split_subtrees(tree.config.subtrees);
+        renderer.subtree_render_hooks = subtree_render_hooks;
+        renderer.context_menu.path_ids = Some(path_ids.clone());
+        renderer.value_editor.load(ui.ctx(), path_ids.clone());
+        if style.bracket_matching != JsonTreeBracketMatching::Off && !style.low_cost {
+            renderer.bracket_matching = Some(BracketMatching::load(ui.ctx(), path_ids.clone()));
+        }
+
+        let frame_budget = style.frame_budget.map(|budget| FrameBudget {
+            deadline: frame_start + budget,
+            exceeded_last_frame: ui
+                .data(|d| d.get_temp::<bool>(frame_budget_exceeded_id))
+                .unwrap_or_default(),
+            degraded: Cell::new(false),
+        });
+
+        let current_match = search_matches
+            .as_ref()
+            .and_then(|_| ui.data(|d| d.get_temp::<String>(current_match_id(tree_state_id))))
+            .and_then(|pointer| parse_json_pointer_string(&pointer))
+            .map(|segments| path_ids.id_of_segments(&segments));
+
+        let focus_current_match_id = focus_current_match_id(tree_state_id);
+        let focus_current_match_pending = ui
+            .data(|d| d.get_temp::<bool>(focus_current_match_id))
+            .unwrap_or_default();
+
+        let low_cost = style.low_cost;
+        let config = JsonTreeNodeConfig {
+            default_expand,
+            remaining_expansion_budget: style.expansion_budget.map(Cell::new),
+            frame_budget,
+            style,
+            search_term,
+            search_matches,
+            highlight_matched_properties: matches!(json_path, Some(Ok(_)))
+                || search_predicate.is_some(),
+            current_match,
+            focus_current_match: Cell::new(
+                focus_current_match_pending && tree.config.focus_follows_search,
+            ),
+            toggled: RefCell::new(vec![]),
+            tree_state_id,
+            scroll_to_pointer: ScrollToPointer::load(ui, tree_state_id),
+            scroll_markers,
+            drop_target,
+            key_column,
+            row_rects: RefCell::new(HashMap::new()),
+            entry_groups: tree.config.entry_groups,
+            numeric_summaries: tree.config.numeric_summaries && !low_cost,
+            subtree_hashes: tree.config.subtree_hashes && !low_cost,
+            condense_search_paths: tree.config.condense_search_paths,
+            value_version: tree.config.value_version,
+            schema_fields: tree.config.schema_fields,
+            search_root,
+            provenance: tree.config.provenance,
+            count_hidden_entries: tree.config.count_hidden_entries,
+            on_toggle: tree.config.on_toggle.map(RefCell::new),
+            recursive_toggle_modifiers: tree.config.recursive_toggle_modifiers,
+            subtrees,
+        };
 
         let node = JsonTreeNode {
             value: tree.value,
             parent: None,
             make_persistent_id: &make_persistent_id,
-            config: &JsonTreeNodeConfig {
-                default_expand,
-                style,
-                search_term,
-            },
+            config: &config,
+            style: &config.style,
+            subtree_expand: None,
+            search_filter: config
+                .search_matches
+                .as_deref()
+                .filter(|_| tree.config.filter_search_results),
         };
 
+        let snapshot_recorder = tree
+            .config
+            .record_snapshot
+            .then(|| SnapshotRecorder::start(ui));
+
+        let mut empty_placeholder = tree.config.empty_placeholder.and_then(|placeholder| {
+            let has_search_results = node
+                .search_filter
+                .map(|search_filter| !search_filter.matched_ids.is_empty());
+            JsonTreeEmptyReason::of(tree.value, has_search_results)
+                .map(|reason| (placeholder, reason))
+        });
+
         // Wrap in a vertical layout in case this tree is placed directly in a horizontal layout,
         // which does not allow indent layouts as direct children.
-        ui.vertical(|ui| {
-            // Centres the collapsing header icon.
-            ui.spacing_mut().interact_size.y = node.config.style.resolve_font_id(ui).size;
+        let tree_rect = ui
+            .vertical(|ui| {
+                if is_background_searching {
+                    render_search_progress(ui, node.style);
+                }
+
+                if let Some((placeholder, reason)) = &mut empty_placeholder {
+                    placeholder(ui, *reason);
+                    return;
+                }
 
-            node.show_impl(ui, &mut vec![], &mut reset_path_ids, &mut renderer);
+                node.style.apply_density(ui);
+                // Centres the collapsing header icon.
+                ui.spacing_mut().interact_size.y = node.style.resolve_font_id(ui).size;
+
+                node.show_impl(ui, &mut vec![], &mut reset_path_ids, &mut renderer);
+            })
+            .response
+            .rect;
+        if let Some(key_column) = &config.key_column {
+            key_column.show_splitter(ui, tree_rect.y_range());
+        }
+        let drop_target = config.drop_target.and_then(|recorder| {
+            recorder.finish(
+                ui,
+                tree.value,
+                config.style.resolve_drop_indicator_stroke(ui),
+            )
         });
+        renderer.context_menu.apply_pending_set_expanded(ui.ctx());
+        if let Some(bracket_matching) = &renderer.bracket_matching {
+            bracket_matching.store(ui.ctx());
+        }
+
+        if background_search_completed {
+            // Reveal the matches of the search that just completed, as for a new search term.
+            for id in &reset_path_ids {
+                if let Some(state) = CollapsingState::load(ui.ctx(), *id) {
+                    state.remove(ui.ctx());
+                }
+            }
+            ui.ctx().request_repaint();
+        }
+
+        if focus_current_match_pending && !config.focus_current_match.get() {
+            ui.data_mut(|d| d.remove::<bool>(focus_current_match_id));
+        }
+
+        let degraded = match &config.frame_budget {
+            Some(frame_budget) => {
+                let exceeded = frame_budget.is_exceeded();
+                ui.data_mut(|d| d.insert_temp(frame_budget_exceeded_id, exceeded));
+                frame_budget.degraded.get()
+            }
+            None => false,
+        };
+
+        let search_match_pointers = config
+            .search_matches
+            .map(|search_matches| search_matches.matched_pointers.clone())
+            .unwrap_or_default();
+        let current_match = ui
+            .data(|d| d.get_temp::<String>(current_match_id(tree_state_id)))
+            .and_then(|pointer| search_match_pointers.iter().position(|p| *p == pointer));
+
+        if let Some(segments) = search_match_pointers
+            .first()
+            .filter(|_| search_term_applied)
+            .and_then(|pointer| parse_json_pointer_string(pointer))
+        {
+            ScrollToPointer::request(ui.ctx(), tree_state_id, path_ids.id_of_segments(&segments));
+            ui.ctx().request_repaint();
+        }
 
         JsonTreeResponse {
             collapsing_state_ids: reset_path_ids,
+            degraded,
+            path_ids,
+            toggled: config.toggled.into_inner(),
+            snapshot: snapshot_recorder.map(|recorder| recorder.finish(ui)),
+            scroll_markers: config
+                .scroll_markers
+                .map(ScrollMarkerRecorder::finish)
+                .unwrap_or_default(),
+            row_rects: config.row_rects.into_inner(),
+            json_path_error: json_path.and_then(Result::err),
+            current_match,
+            search_match_pointers,
+            search_history,
+            drop_target,
         }
     }
 
@@ -91,54 +519,174 @@ impl<'a, 'b, T: ToJsonTreeValue> JsonTreeNode<'a, 'b, T> {
         path_segments: &'b mut Vec<JsonPointerSegment<'a>>,
         reset_path_ids: &'b mut HashSet<Id>,
         renderer: &'b mut JsonTreeRenderer<'a, T>,
+    ) {
+        let Some(index) = find_subtree(&self.config.subtrees, path_segments) else {
+            self.show_node(ui, path_segments, reset_path_ids, renderer);
+            return;
+        };
+        let subtree = &self.config.subtrees[index];
+        let node = JsonTreeNode {
+            style: subtree.style.as_ref().unwrap_or(self.style),
+            subtree_expand: subtree
+                .default_expand
+                .map(|expand| (path_segments.len(), expand))
+                .or(self.subtree_expand),
+            ..self
+        };
+
+        let swap_render_hook = renderer.subtree_render_hooks[index].is_some();
+        if swap_render_hook {
+            std::mem::swap(
+                &mut renderer.render_hook,
+                &mut renderer.subtree_render_hooks[index],
+            );
+        }
+        match &subtree.style {
+            Some(style) => {
+                ui.scope(|ui| {
+                    style.reapply_density(ui, self.style);
+                    ui.spacing_mut().interact_size.y = style.resolve_font_id(ui).size;
+                    node.show_node(ui, path_segments, reset_path_ids, renderer);
+                });
+            }
+            None => node.show_node(ui, path_segments, reset_path_ids, renderer),
+        }
+        if swap_render_hook {
+            std::mem::swap(
+                &mut renderer.render_hook,
+                &mut renderer.subtree_render_hooks[index],
+            );
+        }
+    }
+
+    fn show_node(
+        self,
+        ui: &mut Ui,
+        path_segments: &'b mut Vec<JsonPointerSegment<'a>>,
+        reset_path_ids: &'b mut HashSet<Id>,
+        renderer: &'b mut JsonTreeRenderer<'a, T>,
     ) {
         match self.value.to_json_tree_value() {
+            JsonTreeValue::Base(..)
+                if self.parent.is_some()
+                    && !self.is_scroll_target(path_segments)
+                    && self.should_show_placeholder(ui) =>
+            {
+                ui.allocate_space(vec2(0.0, ui.spacing().interact_size.y));
+            }
             JsonTreeValue::Base(value, display_value, value_type) => {
+                let long_string = self.style.long_string_viewer.and_then(|viewer| {
+                    let text = display_value.to_string();
+                    (value_type == BaseValueType::String && text.chars().count() > viewer.min_chars)
+                        .then_some((text, viewer))
+                });
+                let single_row_style;
+                let value_style = if long_string.is_some() {
+                    single_row_style = self.style.with_single_row_values();
+                    &single_row_style
+                } else {
+                    self.style
+                };
+                let viewer_id = long_string
+                    .as_ref()
+                    .map(|_| (self.make_persistent_id)(path_segments).with("long_string_viewer"));
+                // The text to find within the value while the viewer is open.
+                let mut find = viewer_id.and_then(|id| ui.data(|d| d.get_temp::<String>(id)));
+
+                let provenance = self.provenance(path_segments);
+
                 // Use horizontal instead of horizontal_wrapped so that the
                 // base value always starts inline with the property and not below it.
-                ui.horizontal(|ui| {
-                    ui.spacing_mut().item_spacing.x = 0.0;
+                let row_res = self.show_row(ui, path_segments, false, |ui, path_segments| {
+                    ui.horizontal(|ui| {
+                        ui.spacing_mut().item_spacing.x = 0.0;
 
-                    if let Some(property) = self.parent {
-                        renderer.render_property(
+                        if let Some(property) = self.parent {
+                            renderer.render_property(
+                                ui,
+                                RenderPropertyContext {
+                                    property,
+                                    value: self.value,
+                                    pointer: JsonPointer(path_segments),
+                                    style: self.style,
+                                    search_term: self.search_term_at(path_segments),
+                                    highlighted: self.is_property_highlighted(path_segments),
+                                    is_current_match: self.is_current_match(path_segments),
+                                    request_focus: self.should_request_focus(path_segments),
+                                    collapsing_state: None,
+                                    context_menu: None,
+                                    selection: None,
+                                },
+                            );
+                            renderer.render_spacing_delimiter(
+                                ui,
+                                RenderSpacingDelimiterContext {
+                                    delimiter: SpacingDelimiter::Colon,
+                                    style: self.style,
+                                },
+                            );
+                            self.align_to_value_column(ui);
+                        } else {
+                            self.show_root_label(ui, renderer);
+                        }
+
+                        if viewer_id.is_some() {
+                            let text = if find.is_some() { "hide" } else { "view" };
+                            if ui.small_button(text).clicked() {
+                                find = match find {
+                                    Some(_) => None,
+                                    None => Some(String::new()),
+                                };
+                            }
+                            ui.add_space(ui.spacing().icon_spacing);
+                        }
+
+                        renderer.render_value(
                             ui,
-                            RenderPropertyContext {
-                                property,
-                                value: self.value,
+                            RenderBaseValueContext {
+                                value,
+                                display_value,
+                                value_type,
                                 pointer: JsonPointer(path_segments),
-                                style: &self.config.style,
-                                search_term: self.config.search_term.as_ref(),
-                                collapsing_state: None,
-                            },
-                        );
-                        renderer.render_spacing_delimiter(
-                            ui,
-                            RenderSpacingDelimiterContext {
-                                delimiter: SpacingDelimiter::Colon,
-                                style: &self.config.style,
+                                style: value_style,
+                                search_term: self.search_term_at(path_segments),
+                                is_current_match: self.is_current_match(path_segments),
+                                parent_status: if self.parent.is_some() {
+                                    ParentStatus::ExpandedParent
+                                } else {
+                                    ParentStatus::NoParent
+                                },
+                                formatted_number: None,
+                                context_menu: None,
+                                selection: None,
+                                value_editor: None,
+                                copy_on_double_click: false,
                             },
                         );
-                    }
-
-                    renderer.render_value(
-                        ui,
-                        RenderBaseValueContext {
-                            value,
-                            display_value,
-                            value_type,
-                            pointer: JsonPointer(path_segments),
-                            style: &self.config.style,
-                            search_term: self.config.search_term.as_ref(),
-                            parent_status: if self.parent.is_some() {
-                                ParentStatus::ExpandedParent
-                            } else {
-                                ParentStatus::NoParent
-                            },
-                        },
-                    );
+                        self.show_provenance_suffix(ui, &provenance);
+                    })
                 });
+                self.show_provenance_tooltip(&row_res.response, provenance);
+
+                if let (Some(viewer_id), Some((text, viewer))) = (viewer_id, &long_string) {
+                    match &mut find {
+                        Some(find) => {
+                            self.show_long_string_viewer(
+                                ui,
+                                viewer_id,
+                                text,
+                                viewer,
+                                find,
+                                self.search_term_at(path_segments),
+                            );
+                            ui.data_mut(|d| d.insert_temp(viewer_id, find.clone()));
+                        }
+                        None => ui.data_mut(|d| d.remove::<String>(viewer_id)),
+                    }
+                }
             }
-            JsonTreeValue::Expandable(entries, expandable_type) => {
+            JsonTreeValue::Expandable(mut entries, expandable_type) => {
+                disambiguate_duplicate_keys(self.value, &mut entries);
                 self.show_expandable(
                     ui,
                     path_segments,
@@ -160,11 +708,8 @@ impl<'a, 'b, T: ToJsonTreeValue> JsonTreeNode<'a, 'b, T> {
         entries: Vec<(JsonPointerSegment<'a>, &'a T)>,
         expandable_type: ExpandableType,
     ) {
-        let JsonTreeNodeConfig {
-            default_expand,
-            style,
-            search_term,
-        } = self.config;
+        let style = self.style;
+        let search_matches = &self.config.search_matches;
 
         let delimiters = match expandable_type {
             ExpandableType::Array => &ARRAY_DELIMITERS,
@@ -174,206 +719,340 @@ impl<'a, 'b, T: ToJsonTreeValue> JsonTreeNode<'a, 'b, T> {
         let path_id = (self.make_persistent_id)(path_segments);
         reset_path_ids.insert(path_id);
 
-        let default_open = match &default_expand {
-            InnerExpand::All => true,
-            InnerExpand::None => false,
-            InnerExpand::ToLevel(num_levels_open) => {
-                (path_segments.len() as u8) <= *num_levels_open
-            }
-            InnerExpand::Paths(search_match_path_ids) => search_match_path_ids.contains(&path_id),
-        };
+        let numeric_summary = (self.config.numeric_summaries
+            && expandable_type == ExpandableType::Array)
+            .then(|| {
+                NumericSummary::load_or_compute(ui, path_id, self.config.value_version, &entries)
+            })
+            .flatten();
+        let subtree_hash = self.config.subtree_hashes.then(|| {
+            SubtreeHash::load_or_compute(ui, path_id, self.config.value_version, self.value)
+        });
+
+        // Log entries are objects within arrays.
+        let severity_tint = style
+            .severity_styling
+            .as_ref()
+            .filter(|_| {
+                expandable_type == ExpandableType::Object
+                    && matches!(path_segments.last(), Some(JsonPointerSegment::Index(_)))
+            })
+            .and_then(|severity_styling| severity_styling.resolve_tint(&entries));
+
+        let num_unfiltered_entries = entries.len();
+        let entries = self.filter_entries(path_segments, entries);
+        let num_hidden_entries = num_unfiltered_entries - entries.len();
+        let default_open = self.default_open(path_segments, path_id);
+        let provenance = self.provenance(path_segments);
 
         let mut state = CollapsingState::load_with_default_open(ui.ctx(), path_id, default_open);
         let is_expanded = state.is_open();
+        let missing_fields = if is_expanded {
+            self.missing_fields(path_segments, expandable_type, &entries)
+        } else {
+            vec![]
+        };
 
-        let header_res = ui.horizontal_wrapped(|ui| {
-            ui.spacing_mut().item_spacing.x = 0.0;
-
-            if let Some(enabled) = style.toggle_buttons_state.enabled() {
-                ui.add_enabled_ui(enabled, |ui| {
-                    state.show_toggle_button(ui, paint_default_icon)
-                });
+        if is_expanded {
+            if let Some((chain, (property, elem))) =
+                self.find_condensed_path(ui, path_segments, path_id, &entries)
+            {
+                self.show_condensed_path(
+                    ui,
+                    path_segments,
+                    reset_path_ids,
+                    renderer,
+                    path_id,
+                    &chain,
+                    property,
+                    elem,
+                );
+                return;
             }
+        }
+
+        let hidden_match_count = search_matches
+            .as_ref()
+            .filter(|_| style.show_hidden_match_counts && !style.low_cost && !is_expanded)
+            .and_then(|search_matches| search_matches.match_counts.get(&path_id))
+            .copied();
+
+        // Reserve shapes behind the array/object, to tint and/or faintly highlight it once its extent is known.
+        let severity_background = severity_tint.map(|tint| (ui.painter().add(Shape::Noop), tint));
+        let subtree_background = (is_expanded
+            && style.bracket_matching == JsonTreeBracketMatching::BracketsAndSubtree
+            && renderer
+                .bracket_matching
+                .as_ref()
+                .is_some_and(|bracket_matching| bracket_matching.is_hovered(path_id)))
+        .then(|| ui.painter().add(Shape::Noop));
+
+        let header_res = self.show_row(ui, path_segments, is_expanded, |ui, path_segments| {
+            ui.horizontal_wrapped(|ui| {
+                ui.spacing_mut().item_spacing.x = 0.0;
+
+                if let Some(enabled) = style.toggle_buttons_state.enabled() {
+                    let toggle_button = ui.add_enabled_ui(enabled, |ui| {
+                        let toggle_button = state.show_toggle_button(ui, paint_default_icon);
+                        toggle_button.widget_info(|| {
+                            WidgetInfo::selected(
+                                WidgetType::CollapsingHeader,
+                                enabled,
+                                is_expanded,
+                                JsonPointer(path_segments).to_json_pointer_string(),
+                            )
+                        });
+                        toggle_button
+                    });
+                    if toggle_button.inner.clicked() && self.is_recursive_toggle(ui) {
+                        renderer.context_menu.set_all_expanded(
+                            ui,
+                            JsonPointer(path_segments),
+                            self.value,
+                            !is_expanded,
+                        );
+                    }
+                }
+
+                if path_segments.is_empty() && !is_expanded {
+                    self.show_root_label(ui, renderer);
+
+                    if style.abbreviate_root || self.should_skip_preview() {
+                        renderer.render_expandable_delimiter(
+                            ui,
+                            RenderExpandableDelimiterContext {
+                                delimiter: delimiters.collapsed,
+                                value: self.value,
+                                pointer: JsonPointer(path_segments),
+                                style,
+                                collapsing_state: &mut state,
+                                context_menu: None,
+                                selection: None,
+                                bracket_matching: None,
+                            },
+                        );
+                        if let Some(match_count) = hidden_match_count {
+                            render_match_count_badge(ui, style, match_count);
+                        }
+                        if let Some(summary) = &numeric_summary {
+                            render_trailing_comment(ui, style, summary);
+                        }
+                        if let Some(hash) = &subtree_hash {
+                            render_trailing_comment(ui, style, hash);
+                        }
+                        return;
+                    }
 
-            if path_segments.is_empty() && !is_expanded {
-                if style.abbreviate_root {
                     renderer.render_expandable_delimiter(
                         ui,
                         RenderExpandableDelimiterContext {
-                            delimiter: delimiters.collapsed,
+                            delimiter: delimiters.opening,
                             value: self.value,
                             pointer: JsonPointer(path_segments),
                             style,
                             collapsing_state: &mut state,
+                            context_menu: None,
+                            selection: None,
+                            bracket_matching: None,
+                        },
+                    );
+                    renderer.render_spacing_delimiter(
+                        ui,
+                        RenderSpacingDelimiterContext {
+                            delimiter: SpacingDelimiter::Empty,
+                            style,
                         },
                     );
-                    return;
-                }
-
-                renderer.render_expandable_delimiter(
-                    ui,
-                    RenderExpandableDelimiterContext {
-                        delimiter: delimiters.opening,
-                        value: self.value,
-                        pointer: JsonPointer(path_segments),
-                        style,
-                        collapsing_state: &mut state,
-                    },
-                );
-                renderer.render_spacing_delimiter(
-                    ui,
-                    RenderSpacingDelimiterContext {
-                        delimiter: SpacingDelimiter::Empty,
-                        style,
-                    },
-                );
-
-                let entries_len = entries.len();
 
-                for (idx, (property, elem)) in entries.iter().enumerate() {
-                    path_segments.push(*property);
+                    let entries_len = entries.len();
 
-                    // Don't show array indices when the array is collapsed.
-                    if matches!(expandable_type, ExpandableType::Object) {
-                        renderer.render_property(
-                            ui,
-                            RenderPropertyContext {
-                                property: *property,
-                                value: elem,
-                                pointer: JsonPointer(path_segments),
-                                style,
-                                search_term: search_term.as_ref(),
-                                collapsing_state: Some(&mut state),
-                            },
-                        );
-                        renderer.render_spacing_delimiter(
-                            ui,
-                            RenderSpacingDelimiterContext {
-                                delimiter: SpacingDelimiter::Colon,
-                                style,
-                            },
-                        );
-                    }
+                    for (idx, (property, elem)) in entries.iter().enumerate() {
+                        path_segments.push(*property);
 
-                    match elem.to_json_tree_value() {
-                        JsonTreeValue::Base(value, display_value, value_type) => {
-                            renderer.render_value(
+                        // Don't show array indices when the array is collapsed.
+                        if matches!(expandable_type, ExpandableType::Object) {
+                            renderer.render_property(
                                 ui,
-                                RenderBaseValueContext {
-                                    value,
-                                    display_value,
-                                    value_type,
+                                RenderPropertyContext {
+                                    property: *property,
+                                    value: elem,
                                     pointer: JsonPointer(path_segments),
                                     style,
-                                    search_term: search_term.as_ref(),
-                                    parent_status: ParentStatus::CollapsedRoot,
+                                    search_term: self.search_term_at(path_segments),
+                                    highlighted: false,
+                                    is_current_match: self.is_current_match(path_segments),
+                                    request_focus: false,
+                                    collapsing_state: Some(&mut state),
+                                    context_menu: None,
+                                    selection: None,
                                 },
                             );
-                        }
-                        JsonTreeValue::Expandable(entries, expandable_type) => {
-                            let nested_delimiters = match expandable_type {
-                                ExpandableType::Array => &ARRAY_DELIMITERS,
-                                ExpandableType::Object => &OBJECT_DELIMITERS,
-                            };
-
-                            let delimiter = if entries.is_empty() {
-                                nested_delimiters.collapsed_empty
-                            } else {
-                                nested_delimiters.collapsed
-                            };
-
-                            renderer.render_expandable_delimiter(
+                            renderer.render_spacing_delimiter(
                                 ui,
-                                RenderExpandableDelimiterContext {
-                                    delimiter,
-                                    value: elem,
-                                    pointer: JsonPointer(path_segments),
+                                RenderSpacingDelimiterContext {
+                                    delimiter: SpacingDelimiter::Colon,
                                     style,
-                                    collapsing_state: &mut state,
                                 },
                             );
                         }
-                    };
 
-                    let spacing = if idx == entries_len - 1 {
-                        SpacingDelimiter::Empty
-                    } else {
-                        SpacingDelimiter::Comma
-                    };
+                        match elem.to_json_tree_value() {
+                            JsonTreeValue::Base(value, display_value, value_type) => {
+                                renderer.render_value(
+                                    ui,
+                                    RenderBaseValueContext {
+                                        value,
+                                        display_value,
+                                        value_type,
+                                        pointer: JsonPointer(path_segments),
+                                        style,
+                                        search_term: self.search_term_at(path_segments),
+                                        is_current_match: self.is_current_match(path_segments),
+                                        parent_status: ParentStatus::CollapsedRoot,
+                                        formatted_number: None,
+                                        context_menu: None,
+                                        selection: None,
+                                        value_editor: None,
+                                        copy_on_double_click: false,
+                                    },
+                                );
+                            }
+                            JsonTreeValue::Expandable(entries, expandable_type) => {
+                                let nested_delimiters = match expandable_type {
+                                    ExpandableType::Array => &ARRAY_DELIMITERS,
+                                    ExpandableType::Object => &OBJECT_DELIMITERS,
+                                };
 
-                    renderer.render_spacing_delimiter(
-                        ui,
-                        RenderSpacingDelimiterContext {
-                            delimiter: spacing,
-                            style,
-                        },
-                    );
+                                let delimiter = if entries.is_empty() {
+                                    nested_delimiters.collapsed_empty
+                                } else {
+                                    nested_delimiters.collapsed
+                                };
 
-                    path_segments.pop();
-                }
+                                renderer.render_expandable_delimiter(
+                                    ui,
+                                    RenderExpandableDelimiterContext {
+                                        delimiter,
+                                        value: elem,
+                                        pointer: JsonPointer(path_segments),
+                                        style,
+                                        collapsing_state: &mut state,
+                                        context_menu: None,
+                                        selection: None,
+                                        bracket_matching: None,
+                                    },
+                                );
+                            }
+                        };
 
-                renderer.render_expandable_delimiter(
-                    ui,
-                    RenderExpandableDelimiterContext {
-                        delimiter: delimiters.closing,
-                        value: self.value,
-                        pointer: JsonPointer(path_segments),
-                        style,
-                        collapsing_state: &mut state,
-                    },
-                );
-            } else {
-                if let Some(property) = self.parent {
-                    renderer.render_property(
-                        ui,
-                        RenderPropertyContext {
-                            property,
-                            value: self.value,
-                            pointer: JsonPointer(path_segments),
-                            style,
-                            search_term: self.config.search_term.as_ref(),
-                            collapsing_state: Some(&mut state),
-                        },
-                    );
-                    renderer.render_spacing_delimiter(
-                        ui,
-                        RenderSpacingDelimiterContext {
-                            delimiter: SpacingDelimiter::Colon,
-                            style,
-                        },
-                    );
-                }
+                        let spacing = if idx == entries_len - 1 {
+                            SpacingDelimiter::Empty
+                        } else {
+                            SpacingDelimiter::Comma
+                        };
+
+                        renderer.render_spacing_delimiter(
+                            ui,
+                            RenderSpacingDelimiterContext {
+                                delimiter: spacing,
+                                style,
+                            },
+                        );
+
+                        path_segments.pop();
+                    }
 
-                if is_expanded {
                     renderer.render_expandable_delimiter(
                         ui,
                         RenderExpandableDelimiterContext {
-                            delimiter: delimiters.opening,
+                            delimiter: delimiters.closing,
                             value: self.value,
                             pointer: JsonPointer(path_segments),
                             style,
                             collapsing_state: &mut state,
+                            context_menu: None,
+                            selection: None,
+                            bracket_matching: None,
                         },
                     );
                 } else {
-                    let delimiter = if entries.is_empty() {
-                        delimiters.collapsed_empty
-                    } else {
-                        delimiters.collapsed
-                    };
-                    renderer.render_expandable_delimiter(
-                        ui,
-                        RenderExpandableDelimiterContext {
-                            delimiter,
-                            value: self.value,
-                            pointer: JsonPointer(path_segments),
-                            style,
-                            collapsing_state: &mut state,
-                        },
-                    );
+                    if let Some(property) = self.parent {
+                        renderer.render_property(
+                            ui,
+                            RenderPropertyContext {
+                                property,
+                                value: self.value,
+                                pointer: JsonPointer(path_segments),
+                                style,
+                                search_term: self.search_term_at(path_segments),
+                                highlighted: self.is_property_highlighted(path_segments),
+                                is_current_match: self.is_current_match(path_segments),
+                                request_focus: self.should_request_focus(path_segments),
+                                collapsing_state: Some(&mut state),
+                                context_menu: None,
+                                selection: None,
+                            },
+                        );
+                        renderer.render_spacing_delimiter(
+                            ui,
+                            RenderSpacingDelimiterContext {
+                                delimiter: SpacingDelimiter::Colon,
+                                style,
+                            },
+                        );
+                        self.align_to_value_column(ui);
+                    } else {
+                        self.show_root_label(ui, renderer);
+                    }
+
+                    if is_expanded {
+                        renderer.render_expandable_delimiter(
+                            ui,
+                            RenderExpandableDelimiterContext {
+                                delimiter: delimiters.opening,
+                                value: self.value,
+                                pointer: JsonPointer(path_segments),
+                                style,
+                                collapsing_state: &mut state,
+                                context_menu: None,
+                                selection: None,
+                                bracket_matching: None,
+                            },
+                        );
+                    } else {
+                        let delimiter = if entries.is_empty() {
+                            delimiters.collapsed_empty
+                        } else {
+                            delimiters.collapsed
+                        };
+                        renderer.render_expandable_delimiter(
+                            ui,
+                            RenderExpandableDelimiterContext {
+                                delimiter,
+                                value: self.value,
+                                pointer: JsonPointer(path_segments),
+                                style,
+                                collapsing_state: &mut state,
+                                context_menu: None,
+                                selection: None,
+                                bracket_matching: None,
+                            },
+                        );
+                        if let Some(match_count) = hidden_match_count {
+                            render_match_count_badge(ui, style, match_count);
+                        }
+                    }
+                    if let Some(summary) = &numeric_summary {
+                        render_trailing_comment(ui, style, summary);
+                    }
+                    if let Some(hash) = &subtree_hash {
+                        render_trailing_comment(ui, style, hash);
+                    }
+                    self.show_provenance_suffix(ui, &provenance);
                 }
-            }
+            })
         });
+        self.show_provenance_tooltip(&header_res.response, provenance);
 
         let toggle_buttons_hidden = style.toggle_buttons_state == ToggleButtonsState::Hidden;
         if toggle_buttons_hidden {
@@ -381,18 +1060,41 @@ impl<'a, 'b, T: ToJsonTreeValue> JsonTreeNode<'a, 'b, T> {
             ui.spacing_mut().indent = (ui.spacing().icon_width + ui.spacing().icon_spacing) / 2.0;
         }
 
+        let num_entries = entries.len();
+        let num_revealed_entries = self.reveal_entries(ui, path_id, num_entries, is_expanded);
+
+        skip_animation_if_low_cost(ui, style, &state);
         state.show_body_indented(&header_res.response, ui, |ui| {
-            for (property, elem) in entries {
+            let (ungrouped_entries, entry_groups) = self.group_entries(
+                path_segments,
+                expandable_type,
+                entries.into_iter().take(num_revealed_entries),
+            );
+
+            let mut show_entry = |ui: &mut Ui,
+                                  path_segments: &mut Vec<JsonPointerSegment<'a>>,
+                                  property: JsonPointerSegment<'a>,
+                                  elem: &'a T| {
                 let is_expandable = elem.is_expandable();
 
                 path_segments.push(property);
 
+                // Show all entries beneath a match.
+                let search_filter = self.search_filter.filter(|search_filter| {
+                    !search_filter
+                        .matched_ids
+                        .contains(&(self.make_persistent_id)(path_segments))
+                });
+
                 let mut add_nested_tree = |ui: &mut Ui| {
                     let nested_tree = JsonTreeNode {
                         value: elem,
                         parent: Some(property),
                         make_persistent_id: self.make_persistent_id,
                         config: self.config,
+                        style: self.style,
+                        subtree_expand: self.subtree_expand,
+                        search_filter,
                     };
 
                     nested_tree.show_impl(ui, path_segments, reset_path_ids, renderer);
@@ -415,26 +1117,84 @@ impl<'a, 'b, T: ToJsonTreeValue> JsonTreeNode<'a, 'b, T> {
                 }
 
                 path_segments.pop();
+            };
+
+            for (property, elem) in ungrouped_entries {
+                show_entry(ui, path_segments, property, elem);
             }
-        });
 
-        if is_expanded {
-            ui.horizontal_wrapped(|ui| {
-                if !toggle_buttons_hidden {
-                    let indent = ui.spacing().icon_width / 2.0;
-                    ui.add_space(indent);
-                }
-                renderer.render_expandable_delimiter(
+            for (heading, group_entries) in entry_groups {
+                self.show_entry_group(
                     ui,
-                    RenderExpandableDelimiterContext {
-                        delimiter: delimiters.closing,
-                        value: self.value,
-                        pointer: JsonPointer(path_segments),
-                        style,
-                        collapsing_state: &mut state,
+                    path_segments,
+                    path_id,
+                    &heading,
+                    group_entries.len(),
+                    |ui, path_segments| {
+                        for (property, elem) in group_entries {
+                            show_entry(ui, path_segments, property, elem);
+                        }
                     },
                 );
-            });
+            }
+
+            if num_revealed_entries < num_entries {
+                record_row(ui, path_segments);
+                render_expansion_progress(ui, style, num_revealed_entries, num_entries);
+                ui.ctx().request_repaint();
+            } else {
+                for field in &missing_fields {
+                    self.show_missing_field(ui, path_segments, path_id, renderer, field);
+                }
+                if self.config.count_hidden_entries && num_hidden_entries > 0 {
+                    self.show_hidden_entries_count(ui, path_segments, path_id, num_hidden_entries);
+                }
+            }
+        });
+
+        let mut subtree_bottom = header_res.response.rect.bottom();
+        if is_expanded {
+            record_row(ui, path_segments);
+            let closing_row_rect = ui
+                .horizontal_wrapped(|ui| {
+                    if !toggle_buttons_hidden {
+                        let indent = match style.closing_delimiters {
+                            JsonTreeClosingDelimiters::Compact => ui.spacing().icon_width / 2.0,
+                            JsonTreeClosingDelimiters::AlignedWithOpener => {
+                                ui.spacing().icon_width + ui.spacing().icon_spacing
+                            }
+                        };
+                        ui.add_space(indent);
+                    }
+                    renderer.render_expandable_delimiter(
+                        ui,
+                        RenderExpandableDelimiterContext {
+                            delimiter: delimiters.closing,
+                            value: self.value,
+                            pointer: JsonPointer(path_segments),
+                            style,
+                            collapsing_state: &mut state,
+                            context_menu: None,
+                            selection: None,
+                            bracket_matching: None,
+                        },
+                    );
+                })
+                .response
+                .rect;
+
+            subtree_bottom = closing_row_rect.bottom();
+
+            if let Some(subtree_background) = subtree_background {
+                let rect = Rect::from_x_y_ranges(
+                    header_res.response.rect.left()..=ui.max_rect().right(),
+                    header_res.response.rect.top()..=closing_row_rect.bottom(),
+                );
+                ui.painter().set(
+                    subtree_background,
+                    Shape::rect_filled(rect, 2.0, ui.visuals().faint_bg_color),
+                );
+            }
 
             if renderer.render_hook.is_some() {
                 // show_body_indented will store the CollapsingState,
@@ -443,19 +1203,876 @@ impl<'a, 'b, T: ToJsonTreeValue> JsonTreeNode<'a, 'b, T> {
                 state.store(ui.ctx());
             }
         }
+
+        if let Some((severity_background, tint)) = severity_background {
+            let rect = Rect::from_x_y_ranges(
+                header_res.response.rect.left()..=ui.max_rect().right(),
+                header_res.response.rect.top()..=subtree_bottom,
+            );
+            ui.painter()
+                .set(severity_background, Shape::rect_filled(rect, 2.0, tint));
+        }
+
+        if state.is_open() != is_expanded {
+            if let Some(on_toggle) = &self.config.on_toggle {
+                (on_toggle.borrow_mut())(JsonPointer(path_segments), state.is_open());
+            }
+            self.config.toggled.borrow_mut().push((
+                JsonPointer(path_segments).to_json_pointer_string(),
+                state.is_open(),
+            ));
+        }
+    }
+
+    /// Returns the entries of an array/object that are shown, i.e. only those that are or contain search matches
+    /// if search results are filtered.
+    fn filter_entries(
+        &self,
+        path_segments: &mut Vec<JsonPointerSegment<'a>>,
+        entries: Entries<'a, T>,
+    ) -> Entries<'a, T> {
+        let Some(search_filter) = self.search_filter else {
+            return entries;
+        };
+        entries
+            .into_iter()
+            .filter(|(property, _)| {
+                path_segments.push(*property);
+                let is_match = search_filter.contains(&(self.make_persistent_id)(path_segments));
+                path_segments.pop();
+                is_match
+            })
+            .collect()
+    }
+
+    /// Returns the provenance of the value at the path, configured via [`JsonTree::provenance`].
+    fn provenance(&self, path_segments: &[JsonPointerSegment]) -> Option<String> {
+        self.config
+            .provenance
+            .as_ref()
+            .and_then(|provenance| provenance(JsonPointer(path_segments)))
+    }
+
+    /// Shows the provenance of a value after it, with [`JsonTreeProvenanceDisplay::Suffix`].
+    fn show_provenance_suffix(&self, ui: &mut Ui, provenance: &Option<String>) {
+        if let Some(provenance) = provenance
+            .as_ref()
+            .filter(|_| self.style.provenance_display == JsonTreeProvenanceDisplay::Suffix)
+        {
+            render_trailing_comment(ui, self.style, provenance);
+        }
+    }
+
+    /// Shows the provenance of a value when its row is hovered, with [`JsonTreeProvenanceDisplay::Tooltip`].
+    fn show_provenance_tooltip(&self, row_response: &Response, provenance: Option<String>) {
+        if let Some(provenance) = provenance.filter(|_| {
+            self.style.provenance_display == JsonTreeProvenanceDisplay::Tooltip
+                // The row itself is not hovered while the pointer is over the widgets within it.
+                && row_response.contains_pointer()
+        }) {
+            row_response.show_tooltip_text(provenance);
+        }
+    }
+
+    /// Returns the search term to highlight at the path, unless it is outside the subtree configured via [`JsonTree::search_within`],
+    /// is beneath an object key excluded by the search term,
+    /// or is not a match because the words of the search term must all match within the same subtree.
+    fn search_term_at(&self, path_segments: &[JsonPointerSegment]) -> Option<&SearchTerm> {
+        let search_root = &self.config.search_root;
+        let is_within_search_root = search_root.is_empty()
+            || (path_segments.len() > search_root.len()
+                && path_segments
+                    .iter()
+                    .zip(search_root)
+                    .all(|(segment, unescaped)| segment_matches(segment, unescaped)));
+        self.config
+            .search_term
+            .as_ref()
+            .filter(|_| is_within_search_root)
+            .filter(|search_term| !search_term.excludes_path(path_segments))
+            .filter(|search_term| {
+                !search_term.matches_all_in_subtree()
+                    || self
+                        .config
+                        .search_matches
+                        .as_ref()
+                        .is_some_and(|search_matches| {
+                            search_matches
+                                .matched_ids
+                                .contains(&(self.make_persistent_id)(path_segments))
+                        })
+            })
+    }
+
+    /// Returns the fields configured via [`JsonTree::schema_fields`] that are missing from the object.
+    /// No fields are returned while search results are filtered.
+    fn missing_fields(
+        &self,
+        path_segments: &[JsonPointerSegment],
+        expandable_type: ExpandableType,
+        entries: &Entries<'a, T>,
+    ) -> Vec<JsonTreeSchemaField> {
+        let Some(schema_fields) =
+            self.config.schema_fields.as_ref().filter(|_| {
+                expandable_type == ExpandableType::Object && self.search_filter.is_none()
+            })
+        else {
+            return vec![];
+        };
+        let mut fields = schema_fields(JsonPointer(path_segments));
+        fields.retain(|field| {
+            !entries.iter().any(|(property, _)| {
+                matches!(property, JsonPointerSegment::Key(key) if *key == field.key)
+            })
+        });
+        fields
+    }
+
+    /// Shows a placeholder row for a field missing from the object, indented like its entries.
+    /// Clicking it inserts the field via [`JsonTree::on_insert_field`] if values are editable.
+    fn show_missing_field(
+        &self,
+        ui: &mut Ui,
+        path_segments: &[JsonPointerSegment<'a>],
+        path_id: Id,
+        renderer: &JsonTreeRenderer<'a, T>,
+        field: &JsonTreeSchemaField,
+    ) {
+        let mut field_segments = path_segments.to_vec();
+        field_segments.push(JsonPointerSegment::Key(&field.key));
+        record_row(ui, &field_segments);
+
+        let insertable = renderer.value_editor.can_insert_field(field);
+        ui.scope(|ui| {
+            ui.visuals_mut().indent_has_left_vline = false;
+            ui.spacing_mut().indent = ui.spacing().icon_width + ui.spacing().icon_spacing;
+            if self.style.toggle_buttons_state == ToggleButtonsState::Hidden {
+                ui.spacing_mut().indent /= 2.0;
+            }
+            ui.indent(path_id.with(("missing_field", &field.key)), |ui| {
+                let response = render_missing_field(ui, self.style, field, insertable);
+                if insertable && response.clicked() {
+                    renderer
+                        .value_editor
+                        .insert_field(JsonPointer(path_segments), field);
+                }
+            });
+        });
+    }
+
+    /// Shows a row counting the entries of the array/object hidden by [`JsonTree::filter_search_results`], indented like its entries.
+    fn show_hidden_entries_count(
+        &self,
+        ui: &mut Ui,
+        path_segments: &[JsonPointerSegment<'a>],
+        path_id: Id,
+        num_hidden_entries: usize,
+    ) {
+        record_row(ui, path_segments);
+        ui.scope(|ui| {
+            ui.visuals_mut().indent_has_left_vline = false;
+            ui.spacing_mut().indent = ui.spacing().icon_width + ui.spacing().icon_spacing;
+            if self.style.toggle_buttons_state == ToggleButtonsState::Hidden {
+                ui.spacing_mut().indent /= 2.0;
+            }
+            ui.indent(path_id.with("hidden_entries_count"), |ui| {
+                render_hidden_entries_count(ui, self.style, num_hidden_entries);
+            });
+        });
+    }
+
+    /// Returns whether the array/object is expanded by default, according to the `default_expand` setting,
+    /// or that of the subtree containing it.
+    fn default_open(&self, path_segments: &[JsonPointerSegment], path_id: Id) -> bool {
+        if let Some((base_len, expand)) = self.subtree_expand {
+            return expand_override_open(expand, path_segments.len() - base_len);
+        }
+        match &self.config.default_expand {
+            InnerExpand::All => true,
+            InnerExpand::None => false,
+            InnerExpand::ToLevel(num_levels_open, overrides) => overrides
+                .iter()
+                .filter(|(segments, _)| {
+                    segments.len() <= path_segments.len()
+                        && path_segments
+                            .iter()
+                            .zip(segments)
+                            .all(|(segment, unescaped)| segment_matches(segment, unescaped))
+                })
+                .max_by_key(|(segments, _)| segments.len())
+                .map_or(
+                    path_segments.len() <= usize::from(*num_levels_open),
+                    |(segments, expand)| {
+                        expand_override_open(*expand, path_segments.len() - segments.len())
+                    },
+                ),
+            InnerExpand::Ids(ids) => ids.contains(&path_id),
+            InnerExpand::SearchResults { expand_matches } => self
+                .config
+                .search_matches
+                .as_ref()
+                .is_some_and(|search_matches| {
+                    search_matches.match_counts.contains_key(&path_id)
+                        || (*expand_matches && search_matches.matched_ids.contains(&path_id))
+                }),
+        }
+    }
+
+    /// With [`JsonTree::condense_search_paths`], returns the properties of the chain of expanded arrays/objects,
+    /// starting with this one, that each show nothing but a single nested array/object and are not search matches themselves,
+    /// along with the entry at the end of the chain. Returns `None` if the chain would condense fewer than two levels,
+    /// or it was restored to its full form by clicking it.
+    fn find_condensed_path(
+        &self,
+        ui: &Ui,
+        path_segments: &mut Vec<JsonPointerSegment<'a>>,
+        path_id: Id,
+        entries: &Entries<'a, T>,
+    ) -> Option<(Vec<JsonPointerSegment<'a>>, (JsonPointerSegment<'a>, &'a T))> {
+        let search_matches = self.config.search_matches.as_ref()?;
+        if !self.config.condense_search_paths
+            || ui.data(|d| d.get_temp::<bool>(condensed_path_restored_id(path_id))) == Some(true)
+        {
+            return None;
+        }
+        let is_intermediate = |path_segments: &[JsonPointerSegment], entries: &Entries<'a, T>| {
+            !search_matches
+                .matched_ids
+                .contains(&(self.make_persistent_id)(path_segments))
+                && entries.len() == 1
+                && entries[0].1.is_expandable()
+        };
+        if !is_intermediate(path_segments, entries) {
+            return None;
+        }
+
+        let mut chain = vec![self.parent?];
+        let (mut property, mut elem) = entries[0];
+        let base_len = path_segments.len();
+        loop {
+            path_segments.push(property);
+            let id = (self.make_persistent_id)(path_segments);
+            let is_expanded = CollapsingState::load_with_default_open(
+                ui.ctx(),
+                id,
+                self.default_open(path_segments, id),
+            )
+            .is_open();
+            let JsonTreeValue::Expandable(mut nested_entries, _) = elem.to_json_tree_value() else {
+                break;
+            };
+            disambiguate_duplicate_keys(elem, &mut nested_entries);
+            let nested_entries = self.filter_entries(path_segments, nested_entries);
+            if !is_expanded || !is_intermediate(path_segments, &nested_entries) {
+                break;
+            }
+            chain.push(property);
+            (property, elem) = nested_entries[0];
+        }
+        path_segments.truncate(base_len);
+
+        (chain.len() >= 2).then_some((chain, (property, elem)))
+    }
+
+    /// Shows a chain of arrays/objects found via [`JsonTreeNode::find_condensed_path`] as a single row of their properties,
+    /// e.g. `…/data/items/41/`, followed by the array/object at the end of the chain.
+    /// Clicking the row restores the chain to its full form.
+    #[allow(clippy::too_many_arguments)]
+    fn show_condensed_path(
+        &self,
+        ui: &mut Ui,
+        path_segments: &mut Vec<JsonPointerSegment<'a>>,
+        reset_path_ids: &mut HashSet<Id>,
+        renderer: &mut JsonTreeRenderer<'a, T>,
+        path_id: Id,
+        chain: &[JsonPointerSegment<'a>],
+        property: JsonPointerSegment<'a>,
+        elem: &'a T,
+    ) {
+        let style = self.style;
+        let row_res = self.show_row(ui, path_segments, true, |ui, _| {
+            ui.horizontal(|ui| {
+                if style.toggle_buttons_state != ToggleButtonsState::Hidden {
+                    ui.add_space(ui.spacing().icon_width + ui.spacing().icon_spacing);
+                }
+                render_condensed_path(ui, style, chain)
+            })
+        });
+        let clicked = row_res.inner.clicked();
+        if clicked {
+            ui.ctx().request_repaint();
+        }
+
+        // Restore every level of the chain, so that its tail is not condensed again.
+        let mark_restored = |ui: &Ui, id: Id| {
+            if clicked {
+                ui.data_mut(|d| d.insert_temp(condensed_path_restored_id(id), true));
+            }
+        };
+        mark_restored(ui, path_id);
+        let base_len = path_segments.len();
+        for property in &chain[1..] {
+            path_segments.push(*property);
+            let id = (self.make_persistent_id)(path_segments);
+            reset_path_ids.insert(id);
+            mark_restored(ui, id);
+        }
+        path_segments.push(property);
+        let nested_tree = JsonTreeNode {
+            value: elem,
+            parent: Some(property),
+            make_persistent_id: self.make_persistent_id,
+            config: self.config,
+            style: self.style,
+            subtree_expand: self.subtree_expand,
+            // Show all entries beneath a match.
+            search_filter: self.search_filter.filter(|search_filter| {
+                !search_filter
+                    .matched_ids
+                    .contains(&(self.make_persistent_id)(path_segments))
+            }),
+        };
+        nested_tree.show_impl(ui, path_segments, reset_path_ids, renderer);
+        path_segments.truncate(base_len);
+    }
+
+    /// Splits the entries of an object into those that are ungrouped and those under each heading configured via
+    /// [`JsonTree::group_entries`](crate::JsonTree::group_entries), with groups in the order that their first entry appears.
+    fn group_entries(
+        &self,
+        path_segments: &[JsonPointerSegment],
+        expandable_type: ExpandableType,
+        entries: impl Iterator<Item = (JsonPointerSegment<'a>, &'a T)>,
+    ) -> (Entries<'a, T>, Vec<(String, Entries<'a, T>)>) {
+        let Some(entry_groups) = self
+            .config
+            .entry_groups
+            .as_ref()
+            .filter(|_| expandable_type == ExpandableType::Object)
+        else {
+            return (entries.collect(), vec![]);
+        };
+
+        let mut ungrouped_entries = vec![];
+        let mut groups: Vec<(String, Vec<_>)> = vec![];
+        for (property, elem) in entries {
+            let heading = match property {
+                JsonPointerSegment::Key(key) | JsonPointerSegment::DuplicateKey(key, _) => {
+                    entry_groups(JsonPointer(path_segments), key)
+                }
+                JsonPointerSegment::Index(_) => None,
+            };
+            match heading {
+                Some(heading) => match groups.iter_mut().find(|(h, _)| *h == heading) {
+                    Some((_, group_entries)) => group_entries.push((property, elem)),
+                    None => groups.push((heading, vec![(property, elem)])),
+                },
+                None => ungrouped_entries.push((property, elem)),
+            }
+        }
+        (ungrouped_entries, groups)
+    }
+
+    /// Shows a collapsible heading for a group of entries configured via [`JsonTree::group_entries`](crate::JsonTree::group_entries),
+    /// followed by the entries if the group is expanded.
+    fn show_entry_group(
+        &self,
+        ui: &mut Ui,
+        path_segments: &mut Vec<JsonPointerSegment<'a>>,
+        path_id: Id,
+        heading: &str,
+        num_entries: usize,
+        add_entries: impl FnOnce(&mut Ui, &mut Vec<JsonPointerSegment<'a>>),
+    ) {
+        let style = self.style;
+        let mut state = CollapsingState::load_with_default_open(
+            ui.ctx(),
+            path_id.with(("entry_group", heading)),
+            true,
+        );
+
+        record_row(ui, path_segments);
+        let header_res = ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 0.0;
+            if let Some(enabled) = style.toggle_buttons_state.enabled() {
+                ui.add_enabled_ui(enabled, |ui| {
+                    state.show_toggle_button(ui, paint_default_icon);
+                });
+            }
+            if render_group_heading(ui, style, heading, num_entries).clicked() {
+                state.toggle(ui);
+            }
+        });
+
+        skip_animation_if_low_cost(ui, self.style, &state);
+        state.show_body_indented(&header_res.response, ui, |ui| {
+            add_entries(ui, path_segments)
+        });
+    }
+
+    /// Shows the label configured via [`JsonTreeStyle::root_label`], if any, before the root value.
+    fn show_root_label(&self, ui: &mut Ui, renderer: &mut JsonTreeRenderer<'a, T>) {
+        if let Some(root_label) = &self.style.root_label {
+            render_root_label(ui, self.style, root_label);
+            renderer.render_spacing_delimiter(
+                ui,
+                RenderSpacingDelimiterContext {
+                    delimiter: SpacingDelimiter::Colon,
+                    style: self.style,
+                },
+            );
+            self.align_to_value_column(ui);
+        }
+    }
+
+    /// With [`JsonTreeLayout::Columns`], adds space so that the value that follows starts in the value column.
+    fn align_to_value_column(&self, ui: &mut Ui) {
+        if let Some(key_column) = &self.config.key_column {
+            key_column.align_to_value_column(ui);
+        }
+    }
+
+    /// Shows a row of the tree. With the `accesskit` feature enabled, the row is exposed to AccessKit
+    /// as a tree item whose author id is the JSON Pointer string of the row's value.
+    fn show_row<R>(
+        &self,
+        ui: &mut Ui,
+        path_segments: &mut Vec<JsonPointerSegment<'a>>,
+        is_expanded: bool,
+        add_row: impl FnOnce(&mut Ui, &mut Vec<JsonPointerSegment<'a>>) -> InnerResponse<R>,
+    ) -> InnerResponse<R> {
+        record_row(ui, path_segments);
+
+        let add_row = |ui: &mut Ui, path_segments: &mut Vec<JsonPointerSegment<'a>>| match &self
+            .config
+            .scroll_to_pointer
+        {
+            Some(scroll_to_pointer) if self.is_scroll_target(path_segments) => scroll_to_pointer
+                .show_row(
+                    ui,
+                    self.config.tree_state_id,
+                    &self.style.resolve_scroll_animation(),
+                    self.style.resolve_visuals(ui).highlight_color,
+                    |ui| add_row(ui, path_segments),
+                ),
+            _ => add_row(ui, path_segments),
+        };
+
+        #[cfg(feature = "accesskit")]
+        let row_res = {
+            use egui::accesskit::{Rect as AccessKitRect, Role};
+
+            let row_id = (self.make_persistent_id)(path_segments).with("row");
+            let pointer = JsonPointer(path_segments).to_json_pointer_string();
+            let ctx = ui.ctx().clone();
+
+            ctx.accesskit_node_builder(row_id, |node| {
+                node.set_role(Role::TreeItem);
+                node.set_author_id(pointer);
+            });
+            let row_res = ctx.with_accessibility_parent(row_id, || add_row(ui, path_segments));
+            ctx.accesskit_node_builder(row_id, |node| {
+                let rect = row_res.response.rect;
+                node.set_bounds(AccessKitRect {
+                    x0: rect.min.x.into(),
+                    y0: rect.min.y.into(),
+                    x1: rect.max.x.into(),
+                    y1: rect.max.y.into(),
+                });
+            });
+            row_res
+        };
+
+        #[cfg(not(feature = "accesskit"))]
+        let row_res = add_row(ui, path_segments);
+
+        if ui.is_rect_visible(row_res.response.rect) {
+            self.config.row_rects.borrow_mut().insert(
+                (self.make_persistent_id)(path_segments),
+                row_res.response.rect,
+            );
+        }
+
+        if let Some(drop_target) = &self.config.drop_target {
+            drop_target.record_row(path_segments, row_res.response.rect, is_expanded);
+        }
+
+        if let Some(scroll_markers) = &self.config.scroll_markers {
+            scroll_markers.record_row(
+                (self.make_persistent_id)(path_segments),
+                || JsonPointer(path_segments).to_json_pointer_string(),
+                row_res.response.rect,
+                is_expanded,
+                self.config.search_matches.as_deref(),
+            );
+        }
+
+        row_res
+    }
+
+    /// Returns whether a click now would expand/collapse an array/object recursively,
+    /// according to [`JsonTree::recursive_toggle_modifiers`].
+    fn is_recursive_toggle(&self, ui: &Ui) -> bool {
+        self.config
+            .recursive_toggle_modifiers
+            .is_some_and(|modifiers| ui.input(|i| i.modifiers.matches_logically(modifiers)))
+    }
+
+    /// Returns whether the row is the target of [`JsonTreeResponse::scroll_to_pointer`].
+    fn is_scroll_target(&self, path_segments: &[JsonPointerSegment]) -> bool {
+        self.config
+            .scroll_to_pointer
+            .as_ref()
+            .is_some_and(|scroll_to_pointer| {
+                scroll_to_pointer.is_target((self.make_persistent_id)(path_segments))
+            })
+    }
+
+    /// Returns whether the property of the value is highlighted in full, because the value was selected by a JSONPath expression,
+    /// or matched by the predicate of [`DefaultExpand::SearchWith`].
+    fn is_property_highlighted(&self, path_segments: &[JsonPointerSegment]) -> bool {
+        self.config.highlight_matched_properties
+            && self
+                .config
+                .search_matches
+                .as_ref()
+                .is_some_and(|search_matches| {
+                    search_matches
+                        .matched_ids
+                        .contains(&(self.make_persistent_id)(path_segments))
+                })
+    }
+
+    /// Returns whether the value is the current search match, as navigated to via [`JsonTreeResponse::scroll_to_match`].
+    fn is_current_match(&self, path_segments: &[JsonPointerSegment]) -> bool {
+        self.config
+            .current_match
+            .is_some_and(|current_match| current_match == (self.make_persistent_id)(path_segments))
+    }
+
+    /// Returns whether to move keyboard focus to the property of the value, because it is the current search match
+    /// and has not been focused since it was navigated to. See [`JsonTree::focus_follows_search`].
+    fn should_request_focus(&self, path_segments: &[JsonPointerSegment]) -> bool {
+        self.is_current_match(path_segments) && self.config.focus_current_match.take()
+    }
+
+    /// Returns whether a coarse placeholder should be shown instead of the next row,
+    /// because the frame budget has been exceeded and the row is far outside of the visible area.
+    fn should_show_placeholder(&self, ui: &Ui) -> bool {
+        let Some(frame_budget) = &self.config.frame_budget else {
+            return false;
+        };
+
+        let clip_rect = ui.clip_rect();
+        let far_clip_rect = clip_rect.expand2(vec2(0.0, clip_rect.height()));
+        let row_rect = Rect::from_min_size(
+            ui.cursor().min,
+            vec2(ui.available_width(), ui.spacing().interact_size.y),
+        );
+
+        let show_placeholder = !far_clip_rect.intersects(row_rect) && frame_budget.is_exceeded();
+        if show_placeholder {
+            frame_budget.degraded.set(true);
+        }
+        show_placeholder
+    }
+
+    /// Returns whether a collapsed root array/object should be abbreviated instead of previewing its entries,
+    /// because rendering is low-cost, or the frame budget was exceeded in the previous frame.
+    fn should_skip_preview(&self) -> bool {
+        if self.style.low_cost {
+            return true;
+        }
+        let Some(frame_budget) = &self.config.frame_budget else {
+            return false;
+        };
+
+        if frame_budget.exceeded_last_frame {
+            frame_budget.degraded.set(true);
+        }
+        frame_budget.exceeded_last_frame
+    }
+
+    /// Shows the full text of a long string value beneath its row, with its own scrolling and a field to find text within it.
+    /// Without text to find, matches of the search term of the tree are highlighted.
+    fn show_long_string_viewer(
+        &self,
+        ui: &mut Ui,
+        viewer_id: Id,
+        text: &str,
+        viewer: &JsonTreeLongStringViewer,
+        find: &mut String,
+        search_term: Option<&SearchTerm>,
+    ) {
+        let style = self.style;
+        ui.indent(viewer_id, |ui| {
+            Frame::group(ui.style()).show(ui, |ui| {
+                let find_term = ui
+                    .horizontal(|ui| {
+                        ui.label("Find:");
+                        ui.text_edit_singleline(find);
+                        let find_term = SearchTerm::parse(find, SearchOptions::default());
+                        if let Some(find_term) = &find_term {
+                            let match_count = find_term
+                                .find_match_ranges_in(
+                                    text,
+                                    SearchTarget::Value(BaseValueType::String),
+                                )
+                                .len();
+                            ui.label(if match_count == 1 {
+                                "1 match".to_string()
+                            } else {
+                                format!("{match_count} matches")
+                            });
+                        }
+                        find_term
+                    })
+                    .inner;
+
+                ScrollArea::vertical()
+                    .id_salt(viewer_id)
+                    .max_height(viewer.max_height)
+                    .auto_shrink([false, true])
+                    .show(ui, |ui| {
+                        render_long_string(
+                            ui,
+                            style,
+                            text,
+                            find_term.as_ref().or(search_term.filter(|search_term| {
+                                search_term.matches_values()
+                                    && search_term.matches_value_type(BaseValueType::String)
+                            })),
+                        );
+                    });
+            });
+        });
+    }
+
+    /// When an expansion budget is configured, returns how many entries of this array/object to show this frame,
+    /// so that revealing the entries of a large expanded subtree is spread across multiple frames.
+    fn reveal_entries(&self, ui: &Ui, path_id: Id, num_entries: usize, is_expanded: bool) -> usize {
+        let Some(remaining_budget) = &self.config.remaining_expansion_budget else {
+            return num_entries;
+        };
+
+        let revealed_entries_id = path_id.with("revealed_entries");
+
+        if !is_expanded {
+            ui.data_mut(|d| d.remove::<usize>(revealed_entries_id));
+            return num_entries;
+        }
+
+        let previously_revealed = ui
+            .data(|d| d.get_temp::<usize>(revealed_entries_id))
+            .unwrap_or_default()
+            .min(num_entries);
+        let newly_revealed = (num_entries - previously_revealed).min(remaining_budget.get());
+        remaining_budget.set(remaining_budget.get() - newly_revealed);
+
+        let revealed = previously_revealed + newly_revealed;
+        ui.data_mut(|d| d.insert_temp(revealed_entries_id, revealed));
+        revealed
+    }
+}
+
+/// Returns the id under which it is stored that a chain of arrays/objects condensed via [`JsonTree::condense_search_paths`],
+/// starting with the array/object with this path id, was clicked to restore it to its full form.
+fn condensed_path_restored_id(path_id: Id) -> Id {
+    path_id.with("condensed_path_restored")
+}
+
+/// Returns whether a background search completed since the tree was last shown.
+fn background_search_generation_changed(ui: &Ui, tree_state_id: Id, generation: u64) -> bool {
+    let generation_id = tree_state_id.with("background_search_generation");
+    let last_generation = ui.data_mut(|d| {
+        let last_generation = d.get_temp::<u64>(generation_id);
+        d.insert_temp(generation_id, generation);
+        last_generation
+    });
+    last_generation != Some(generation)
+}
+
+/// Returns whether the search term, identified by its key, differs from when the tree was last shown with a search term.
+fn search_term_changed(ui: &Ui, tree_state_id: Id, search_key: Id) -> bool {
+    let search_key_id = tree_state_id.with("applied_search_term");
+    let last_search_key = ui.data_mut(|d| {
+        let last_search_key = d.get_temp::<Id>(search_key_id);
+        d.insert_temp(search_key_id, search_key);
+        last_search_key
+    });
+    last_search_key != Some(search_key)
+}
+
+/// Resets the expanded state of all arrays/objects in the tree if the shared search term changed since the tree was last shown.
+fn reset_expanded_if_shared_search_changed<T: ToJsonTreeValue>(
+    ui: &Ui,
+    value: &T,
+    tree_state_id: Id,
+    generation: u64,
+    make_persistent_id: &dyn Fn(&[JsonPointerSegment]) -> Id,
+) {
+    let generation_id = tree_state_id.with("shared_search_generation");
+    let last_generation = ui.data_mut(|d| {
+        let last_generation = d.get_temp::<u64>(generation_id);
+        d.insert_temp(generation_id, generation);
+        last_generation
+    });
+    if last_generation.is_some_and(|last_generation| last_generation != generation) {
+        reset_expanded_in(ui, value, make_persistent_id);
+    }
+}
+
+fn reset_expanded_in<T: ToJsonTreeValue>(
+    ui: &Ui,
+    value: &T,
+    make_persistent_id: &dyn Fn(&[JsonPointerSegment]) -> Id,
+) {
+    for id in find_expandable_path_ids_in(value, make_persistent_id) {
+        if let Some(state) = CollapsingState::load(ui.ctx(), id) {
+            state.remove(ui.ctx());
+        }
     }
 }
 
-struct JsonTreeNodeConfig {
+/// Returns whether an array/object nested `depth` levels beneath the pointer of an [`ExpandOverride`] is expanded by default.
+fn expand_override_open(expand: ExpandOverride, depth: usize) -> bool {
+    match expand {
+        ExpandOverride::All => true,
+        ExpandOverride::None => false,
+        ExpandOverride::ToLevel(num_levels_open) => depth < usize::from(num_levels_open),
+    }
+}
+
+/// Finishes any animation of expanding/collapsing the array/object straight away if rendering is [`JsonTreeStyle::low_cost`].
+fn skip_animation_if_low_cost(ui: &Ui, style: &JsonTreeStyle, state: &CollapsingState) {
+    if style.low_cost {
+        ui.ctx()
+            .animate_bool_with_time(state.id(), state.is_open(), 0.0);
+    }
+}
+
+/// The search term applied by [`JsonTreeStyle::search_debounce`], and the search term waiting to be applied.
+#[derive(Clone)]
+struct DebouncedSearch {
+    applied: Option<SearchTerm>,
+    applied_id: Id,
+    /// The id of the search term waiting to be applied, and the time it was first seen.
+    pending: Option<(Id, f64)>,
+}
+
+/// Returns the search term to search for, which only changes once the given search term has stayed the same
+/// for the debounce duration, and whether it changed this frame.
+/// The first search term seen is applied straight away.
+fn debounce_search_term(
+    ui: &Ui,
+    tree_state_id: Id,
+    search_term: Option<SearchTerm>,
+    debounce: Duration,
+) -> (Option<SearchTerm>, bool) {
+    let id = tree_state_id.with("debounced_search");
+    let search_term_id = Id::new(&search_term);
+    let Some(mut debounced) = ui.data(|d| d.get_temp::<DebouncedSearch>(id)) else {
+        let debounced = DebouncedSearch {
+            applied: search_term.clone(),
+            applied_id: search_term_id,
+            pending: None,
+        };
+        ui.data_mut(|d| d.insert_temp(id, debounced));
+        return (search_term, false);
+    };
+
+    let now = ui.input(|i| i.time);
+    let applied = if search_term_id == debounced.applied_id {
+        debounced.pending = None;
+        false
+    } else {
+        let since = match debounced.pending {
+            Some((pending_id, since)) if pending_id == search_term_id => since,
+            _ => now,
+        };
+        let remaining = debounce.as_secs_f64() - (now - since);
+        if remaining <= 0.0 {
+            debounced.applied = search_term;
+            debounced.applied_id = search_term_id;
+            debounced.pending = None;
+            true
+        } else {
+            debounced.pending = Some((search_term_id, since));
+            ui.ctx()
+                .request_repaint_after(Duration::from_secs_f64(remaining));
+            false
+        }
+    };
+    let search_term = debounced.applied.clone();
+    ui.data_mut(|d| d.insert_temp(id, debounced));
+    (search_term, applied)
+}
+
+struct JsonTreeNodeConfig<'a> {
     default_expand: InnerExpand,
+    /// The number of entries that may still be newly revealed this frame, if an expansion budget is configured.
+    remaining_expansion_budget: Option<Cell<usize>>,
+    frame_budget: Option<FrameBudget>,
     style: JsonTreeStyle,
     search_term: Option<SearchTerm>,
+    search_matches: Option<Arc<SearchMatches>>,
+    /// Whether the properties of the matched values are highlighted in full, i.e. when selected by a JSONPath expression.
+    highlight_matched_properties: bool,
+    /// The id of the path of the current search match, if any.
+    current_match: Option<Id>,
+    /// Whether the current search match should receive keyboard focus when its row is shown.
+    focus_current_match: Cell<bool>,
+    /// The arrays/objects that were expanded (`true`) or collapsed (`false`) this frame.
+    toggled: RefCell<Vec<(String, bool)>>,
+    tree_state_id: Id,
+    scroll_to_pointer: Option<ScrollToPointer>,
+    scroll_markers: Option<ScrollMarkerRecorder>,
+    drop_target: Option<DropTargetRecorder>,
+    key_column: Option<KeyColumn>,
+    /// The screen rects of the rows that were visible this frame, by the id of their path.
+    row_rects: RefCell<HashMap<Id, Rect>>,
+    entry_groups: Option<Box<EntryGroups<'a>>>,
+    numeric_summaries: bool,
+    subtree_hashes: bool,
+    condense_search_paths: bool,
+    value_version: Option<u64>,
+    schema_fields: Option<Box<SchemaFields<'a>>>,
+    /// The unescaped segments of the JSON Pointer string beneath which the search term is matched,
+    /// configured via [`JsonTree::search_within`]. Empty if the whole value is searched.
+    search_root: Vec<String>,
+    provenance: Option<Box<Provenance<'a>>>,
+    /// Whether to count the entries hidden by [`JsonTree::filter_search_results`], configured via [`JsonTree::count_hidden_entries`].
+    count_hidden_entries: bool,
+    on_toggle: Option<RefCell<Box<ToggleHook<'a>>>>,
+    recursive_toggle_modifiers: Option<Modifiers>,
+    /// The subtrees configured via [`JsonTree::subtree`], whose render hooks are kept by the renderer at the same index.
+    subtrees: Vec<SubtreeOverride>,
 }
 
 #[derive(Debug, Clone)]
 enum InnerExpand {
     All,
     None,
-    ToLevel(u8),
-    Paths(HashSet<Id>),
+    /// The number of levels to expand, and overrides of it beneath the unescaped segments of JSON pointers.
+    ToLevel(u8, Vec<(Vec<String>, ExpandOverride)>),
+    SearchResults {
+        /// Whether the matched arrays/objects are expanded too, not just those containing matches.
+        expand_matches: bool,
+    },
+    /// The ids of the arrays/objects to expand, e.g. the ancestors of a JSON Pointer.
+    Ids(HashSet<Id>),
+}
+
+/// Tracks the time spent showing the tree this frame against the configured frame budget.
+struct FrameBudget {
+    deadline: Instant,
+    exceeded_last_frame: bool,
+    /// Whether any rendering was degraded this frame to stay within the budget.
+    degraded: Cell<bool>,
+}
+
+impl FrameBudget {
+    fn is_exceeded(&self) -> bool {
+        Instant::now() >= self.deadline
+    }
 }
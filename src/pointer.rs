@@ -1,6 +1,11 @@
 //! A JSON Pointer implementation for identifying specific values within a JSON document.
 
-use std::fmt;
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+use crate::identity::{parse_index, segment_matches};
 
 /// A JSON Pointer implementation.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -37,21 +42,182 @@ impl<'a, 'b> JsonPointer<'a, 'b> {
 }
 
 /// An individual segment of a [JsonPointer] - either an array index or object key.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum JsonPointerSegment<'a> {
     Index(usize),
     Key(&'a str),
+    /// A repeated occurrence of an object key, within an object that allows duplicate keys
+    /// via [`ToJsonTreeValue::allows_duplicate_keys`](crate::value::ToJsonTreeValue::allows_duplicate_keys).
+    /// The first occurrence of the key is a [`JsonPointerSegment::Key`], the second has an occurrence of `2`, and so on.
+    ///
+    /// Its JSON Pointer segment string is the key followed by `~` and the occurrence, e.g. `/a~2` for the second `a`.
+    /// This is not a valid RFC 6901 segment, so it differs from the segment string of any other key, e.g. `/a~02` for the key `a~2`.
+    /// Its [`Id`](egui::Id) is derived from its unescaped segment string however, so it is the same as that of the key `a~2`
+    /// within the same object, whose expanded state it then shares.
+    DuplicateKey(&'a str, usize),
 }
 
 impl<'a> fmt::Display for JsonPointerSegment<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            JsonPointerSegment::Key(key) => write!(f, "{}", key),
+            JsonPointerSegment::Key(key) | JsonPointerSegment::DuplicateKey(key, _) => {
+                write!(f, "{}", key)
+            }
             JsonPointerSegment::Index(idx) => write!(f, "{}", idx),
         }
     }
 }
 
+/// An array index is hashed the same as an object key of the same digits,
+/// and a duplicate key the same as an object key of its unescaped segment string, e.g. `a~2`,
+/// so that the [`Id`](egui::Id) of an array/object can be derived from a JSON Pointer string alone.
+/// Neither allocates: such object keys are hashed as their key, separator and occurrence.
+impl<'a> Hash for JsonPointerSegment<'a> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            JsonPointerSegment::Key(key) => match split_duplicate_key(key) {
+                Some((key, occurrence)) => hash_duplicate_key(key, occurrence, state),
+                None => key.hash(state),
+            },
+            JsonPointerSegment::DuplicateKey(key, occurrence) => {
+                hash_duplicate_key(key, *occurrence, state)
+            }
+            JsonPointerSegment::Index(idx) => {
+                let mut buf = [0u8; 20];
+                let mut start = buf.len();
+                let mut n = *idx;
+                loop {
+                    start -= 1;
+                    buf[start] = b'0' + (n % 10) as u8;
+                    n /= 10;
+                    if n == 0 {
+                        break;
+                    }
+                }
+                // The buffer only contains ASCII digits.
+                std::str::from_utf8(&buf[start..])
+                    .unwrap_or_default()
+                    .hash(state);
+            }
+        }
+    }
+}
+
+/// Splits an unescaped segment string of a duplicate key, e.g. `a~2`, into its key and occurrence, if it is one.
+fn split_duplicate_key(segment: &str) -> Option<(&str, usize)> {
+    let (key, occurrence) = segment.rsplit_once('~')?;
+    let occurrence = parse_index(occurrence).filter(|occurrence| *occurrence >= 2)?;
+    Some((key, occurrence))
+}
+
+fn hash_duplicate_key<H: Hasher>(key: &str, occurrence: usize, state: &mut H) {
+    key.hash(state);
+    state.write_u8(b'~');
+    occurrence.hash(state);
+}
+
+/// Parses a JSON Pointer string into its unescaped segments,
+/// returning `None` if the string is neither empty nor starts with `/`.
+pub(crate) fn parse_json_pointer_string(pointer: &str) -> Option<Vec<String>> {
+    if pointer.is_empty() {
+        return Some(vec![]);
+    }
+
+    Some(
+        pointer
+            .strip_prefix('/')?
+            .split('/')
+            .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+            .collect(),
+    )
+}
+
+/// A pattern that matches JSON Pointers, for configuring behaviour per path, e.g. `/items/*/name`.
+///
+/// Patterns are written like JSON Pointer strings, where a segment of:
+/// - `*` matches any single array index or object key,
+/// - `**` matches any number of segments, including none,
+/// - anything else matches that array index or object key only, with `~0` and `~1` escaping `~` and `/` as usual.
+/// ```rust
+/// # use egui_json_tree::pointer::PointerGlob;
+/// let glob = PointerGlob::new("/items/*/name").unwrap();
+/// assert!(glob.matches_str("/items/0/name"));
+/// assert!(!glob.matches_str("/items/0/tags/name"));
+///
+/// let glob = PointerGlob::new("/**/id").unwrap();
+/// assert!(glob.matches_str("/id"));
+/// assert!(glob.matches_str("/users/3/id"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PointerGlob {
+    segments: Vec<GlobSegment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum GlobSegment {
+    /// An unescaped array index or object key.
+    Literal(String),
+    /// `*`
+    Any,
+    /// `**`
+    AnyDepth,
+}
+
+impl PointerGlob {
+    /// Parses a pattern, returning `None` if it is neither empty nor starts with `/`.
+    /// The empty pattern matches only the whole document.
+    pub fn new(pattern: &str) -> Option<Self> {
+        let segments = pattern
+            .strip_prefix('/')
+            .map(|pattern| {
+                pattern
+                    .split('/')
+                    .map(|segment| match segment {
+                        "*" => GlobSegment::Any,
+                        "**" => GlobSegment::AnyDepth,
+                        _ => GlobSegment::Literal(segment.replace("~1", "/").replace("~0", "~")),
+                    })
+                    .collect()
+            })
+            .or_else(|| pattern.is_empty().then(Vec::new))?;
+        Some(Self { segments })
+    }
+
+    /// Returns whether the pattern matches the pointer, e.g. one given to a hook of [`JsonTree`](crate::JsonTree).
+    pub fn matches(&self, pointer: JsonPointer) -> bool {
+        glob_matches(&self.segments, pointer.0, &|segment, unescaped| {
+            segment_matches(segment, unescaped)
+        })
+    }
+
+    /// Returns whether the pattern matches the JSON Pointer string.
+    /// Returns `false` if the string is not a valid JSON Pointer.
+    pub fn matches_str(&self, pointer: &str) -> bool {
+        parse_json_pointer_string(pointer).is_some_and(|segments| {
+            glob_matches(&self.segments, &segments, &|segment, unescaped| {
+                segment == unescaped
+            })
+        })
+    }
+}
+
+fn glob_matches<S>(glob: &[GlobSegment], segments: &[S], eq: &dyn Fn(&S, &str) -> bool) -> bool {
+    match glob.split_first() {
+        None => segments.is_empty(),
+        Some((GlobSegment::AnyDepth, rest)) => {
+            (0..=segments.len()).any(|skip| glob_matches(rest, &segments[skip..], eq))
+        }
+        Some((glob_segment, rest)) => segments.split_first().is_some_and(|(segment, segments)| {
+            let segment_matches = match glob_segment {
+                GlobSegment::Literal(literal) => eq(segment, literal),
+                _ => true,
+            };
+            segment_matches && glob_matches(rest, segments, eq)
+        }),
+    }
+}
+
 impl<'a> JsonPointerSegment<'a> {
     pub fn to_json_pointer_segment_string(&self) -> String {
         match self {
@@ -59,12 +225,20 @@ impl<'a> JsonPointerSegment<'a> {
                 format!("/{}", key.replace('~', "~0").replace('/', "~1"))
             }
             JsonPointerSegment::Index(idx) => format!("/{}", idx),
+            JsonPointerSegment::DuplicateKey(key, occurrence) => {
+                format!(
+                    "/{}~{occurrence}",
+                    key.replace('~', "~0").replace('/', "~1")
+                )
+            }
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::hash::BuildHasher;
+
     use super::*;
 
     #[test]
@@ -147,4 +321,94 @@ mod tests {
             "/ /0/  ".to_string()
         );
     }
+
+    #[test]
+    fn index_segment_hashes_like_key_segment() {
+        let state = std::collections::hash_map::RandomState::new();
+        for idx in [0, 7, 10, 1234, usize::MAX] {
+            assert_eq!(
+                state.hash_one(JsonPointerSegment::Index(idx)),
+                state.hash_one(JsonPointerSegment::Key(&idx.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn duplicate_key_segment_is_disambiguated_by_occurrence() {
+        let path = [JsonPointerSegment::DuplicateKey("a/b", 2)];
+        let pointer = JsonPointer(&path);
+        assert_eq!(pointer.to_json_pointer_string(), "/a~1b~2".to_string());
+        assert_eq!(path[0].to_string(), "a/b");
+
+        let segments = parse_json_pointer_string(&pointer.to_json_pointer_string()).unwrap();
+        let state = std::collections::hash_map::RandomState::new();
+        assert_eq!(
+            state.hash_one(path[0]),
+            state.hash_one(JsonPointerSegment::Key(&segments[0]))
+        );
+        assert_ne!(
+            state.hash_one(path[0]),
+            state.hash_one(JsonPointerSegment::Key("a/b"))
+        );
+    }
+
+    #[test]
+    fn duplicate_key_segment_hashes_like_key_of_its_unescaped_segment_string() {
+        let duplicate_key = JsonPointerSegment::DuplicateKey("a", 2);
+        let key = JsonPointerSegment::Key("a~2");
+        assert_eq!(duplicate_key.to_json_pointer_segment_string(), "/a~2");
+        assert_eq!(key.to_json_pointer_segment_string(), "/a~02");
+
+        let state = std::collections::hash_map::RandomState::new();
+        assert_eq!(state.hash_one(duplicate_key), state.hash_one(key));
+        for other_key in ["a~02", "a~1", "a~", "a"] {
+            assert_ne!(
+                state.hash_one(duplicate_key),
+                state.hash_one(JsonPointerSegment::Key(other_key)),
+                "{other_key}"
+            );
+        }
+    }
+
+    #[test]
+    fn parses_json_pointer_strings() {
+        assert_eq!(parse_json_pointer_string(""), Some(vec![]));
+        assert_eq!(
+            parse_json_pointer_string("/foo/0//a~1b/m~0n"),
+            Some(vec![
+                "foo".to_string(),
+                "0".to_string(),
+                "".to_string(),
+                "a/b".to_string(),
+                "m~n".to_string()
+            ])
+        );
+        assert_eq!(parse_json_pointer_string("foo"), None);
+    }
+
+    #[test]
+    fn pointer_glob_matches_wildcards() {
+        let matches =
+            |pattern: &str, pointer: &str| PointerGlob::new(pattern).unwrap().matches_str(pointer);
+
+        assert!(matches("", ""));
+        assert!(!matches("", "/a"));
+        assert!(matches("/items/*/name", "/items/0/name"));
+        assert!(!matches("/items/*/name", "/items/name"));
+        assert!(matches("/**", ""));
+        assert!(matches("/**/id", "/id"));
+        assert!(matches("/**/id", "/a/0/id"));
+        assert!(!matches("/**/id", "/a/0/idx"));
+        assert!(matches("/a~1b/**/c", "/a~1b/x/y/c"));
+        assert!(!matches("/a", "a"));
+        assert!(PointerGlob::new("a").is_none());
+
+        let path = [
+            JsonPointerSegment::Key("items"),
+            JsonPointerSegment::Index(2),
+        ];
+        let glob = PointerGlob::new("/items/2").unwrap();
+        assert!(glob.matches(JsonPointer(&path)));
+        assert!(!glob.matches(JsonPointer(&path[..1])));
+    }
 }
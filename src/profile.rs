@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use crate::{
+    value::ToJsonTreeValue, EpochUnit, JsonTree, JsonTreeBracketMatching,
+    JsonTreeClosingDelimiters, JsonTreeLongStringViewer, JsonTreeSettings, JsonTreeSeverityStyling,
+    JsonTreeStyle, JsonTreeVisuals, ToggleButtonsState,
+};
+
+/// A shareable "view profile" for inspecting a particular kind of payload, bundling the [`JsonTreeSettings`]
+/// with the rest of the configuration of a [`JsonTree`] that can be described as data.
+///
+/// With the `serde` feature enabled, a profile can be exported to and imported from a single config document,
+/// e.g. a JSON file that is shared between users of a tool. Options missing from the document keep their defaults:
+/// ```rust
+/// # use egui_json_tree::{JsonTree, JsonTreeProfile, JsonTreeSeverityStyling};
+/// # egui::__run_test_ui(|ui| {
+/// # let value = serde_json::json!({ "logs": [{ "level": "error" }] });
+/// let mut profile = JsonTreeProfile::default();
+/// profile.severity_styling = Some(JsonTreeSeverityStyling::default());
+/// profile.filter_search_results = true;
+///
+/// profile.apply(JsonTree::new("profile-tree", &value)).show(ui);
+/// # });
+/// ```
+///
+/// Callbacks, such as render hooks, and the search term are not part of a profile, and are configured on the [`JsonTree`] as usual.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct JsonTreeProfile {
+    /// The user preferences, applied via [`JsonTreeSettings::apply`].
+    pub settings: JsonTreeSettings,
+    /// See [`JsonTreeStyle::visuals`].
+    pub visuals: Option<JsonTreeVisuals>,
+    /// See [`JsonTreeStyle::root_label`].
+    pub root_label: Option<String>,
+    /// See [`JsonTreeStyle::toggle_buttons_state`].
+    pub toggle_buttons_state: ToggleButtonsState,
+    /// See [`JsonTreeStyle::bracket_matching`].
+    pub bracket_matching: JsonTreeBracketMatching,
+    /// See [`JsonTreeStyle::closing_delimiters`].
+    pub closing_delimiters: JsonTreeClosingDelimiters,
+    /// See [`JsonTreeStyle::long_string_viewer`].
+    pub long_string_viewer: Option<JsonTreeLongStringViewer>,
+    /// See [`JsonTreeStyle::severity_styling`].
+    pub severity_styling: Option<JsonTreeSeverityStyling>,
+    /// See [`EpochTimestampConfig::pointer_overrides`](crate::EpochTimestampConfig::pointer_overrides).
+    pub epoch_pointer_overrides: HashMap<String, Option<EpochUnit>>,
+    /// See [`JsonTree::highlight_only`].
+    pub highlight_only: bool,
+    /// See [`JsonTree::filter_search_results`].
+    pub filter_search_results: bool,
+    /// See [`JsonTree::count_hidden_entries`].
+    pub count_hidden_entries: bool,
+    /// See [`JsonTree::condense_search_paths`].
+    pub condense_search_paths: bool,
+    /// See [`JsonTree::numeric_summaries`].
+    pub numeric_summaries: bool,
+    /// See [`JsonTree::subtree_hashes`].
+    pub subtree_hashes: bool,
+}
+
+impl JsonTreeProfile {
+    /// Returns a profile with the options of the given style, and the defaults of the options of a [`JsonTree`].
+    pub fn from_style(style: &JsonTreeStyle) -> Self {
+        Self {
+            settings: JsonTreeSettings {
+                density: style.density,
+                high_contrast: style.high_contrast,
+                abbreviate_root: style.abbreviate_root,
+                show_hidden_match_counts: style.show_hidden_match_counts,
+                uuid_display: style.uuid_display,
+                epoch_unit: style.epoch_timestamps.unit,
+                low_cost: style.low_cost,
+            },
+            visuals: style.visuals.clone(),
+            root_label: style.root_label.clone(),
+            toggle_buttons_state: style.toggle_buttons_state,
+            bracket_matching: style.bracket_matching,
+            closing_delimiters: style.closing_delimiters,
+            long_string_viewer: style.long_string_viewer,
+            severity_styling: style.severity_styling.clone(),
+            epoch_pointer_overrides: style.epoch_timestamps.pointer_overrides.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the given style with the style options of this profile applied, leaving all other options as they are.
+    pub fn apply_style(&self, style: JsonTreeStyle) -> JsonTreeStyle {
+        let mut style = self.settings.apply(style);
+        style.visuals.clone_from(&self.visuals);
+        style.root_label.clone_from(&self.root_label);
+        style.toggle_buttons_state = self.toggle_buttons_state;
+        style.bracket_matching = self.bracket_matching;
+        style.closing_delimiters = self.closing_delimiters;
+        style.long_string_viewer = self.long_string_viewer;
+        style.severity_styling.clone_from(&self.severity_styling);
+        style
+            .epoch_timestamps
+            .pointer_overrides
+            .clone_from(&self.epoch_pointer_overrides);
+        style
+    }
+
+    /// Returns the given tree with this profile applied to it and to its style, leaving all other options as they are.
+    pub fn apply<'a, T: ToJsonTreeValue>(&self, mut tree: JsonTree<'a, T>) -> JsonTree<'a, T> {
+        let style = tree.config.style.take().unwrap_or_default();
+        tree.config.style = Some(self.apply_style(style));
+        tree.config.highlight_only = self.highlight_only;
+        tree.config.filter_search_results = self.filter_search_results;
+        tree.config.count_hidden_entries = self.count_hidden_entries;
+        tree.config.condense_search_paths = self.condense_search_paths;
+        tree.config.numeric_summaries = self.numeric_summaries;
+        tree.config.subtree_hashes = self.subtree_hashes;
+        tree
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{JsonTreeBracketMatching, JsonTreeDensity, JsonTreeSeverityStyling, JsonTreeStyle};
+
+    use super::JsonTreeProfile;
+
+    #[test]
+    fn round_trips_style_options() {
+        let style = JsonTreeStyle::new()
+            .density(JsonTreeDensity::Compact)
+            .root_label("payload")
+            .bracket_matching(JsonTreeBracketMatching::Brackets)
+            .severity_styling(Some(JsonTreeSeverityStyling::default()));
+        let profile = JsonTreeProfile::from_style(&style);
+
+        let applied = profile.apply_style(JsonTreeStyle::new().abbreviation("…"));
+
+        assert_eq!(applied.density, JsonTreeDensity::Compact);
+        assert_eq!(applied.root_label.as_deref(), Some("payload"));
+        assert_eq!(applied.bracket_matching, JsonTreeBracketMatching::Brackets);
+        assert_eq!(applied.severity_styling, style.severity_styling);
+        assert_eq!(applied.abbreviation.as_deref(), Some("…"));
+        assert_eq!(JsonTreeProfile::from_style(&applied), profile);
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn applies_tree_options() {
+        let value = serde_json::json!({});
+        let profile = JsonTreeProfile {
+            filter_search_results: true,
+            numeric_summaries: true,
+            ..Default::default()
+        };
+
+        let tree = profile.apply(
+            crate::JsonTree::new("id", &value)
+                .style(JsonTreeStyle::new().root_label("body"))
+                .subtree_hashes(true),
+        );
+
+        assert!(tree.config.filter_search_results);
+        assert!(tree.config.numeric_summaries);
+        assert!(!tree.config.subtree_hashes);
+        let style = tree.config.style.as_ref().unwrap();
+        assert_eq!(style.root_label, None);
+        assert_eq!(style.epoch_timestamps.unit, None::<crate::EpochUnit>);
+    }
+
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    #[test]
+    fn deserializes_missing_options_as_defaults() {
+        let profile = JsonTreeProfile {
+            severity_styling: Some(JsonTreeSeverityStyling::default()),
+            filter_search_results: true,
+            ..Default::default()
+        };
+        let serialized = serde_json::to_string(&profile).unwrap();
+        assert_eq!(
+            serde_json::from_str::<JsonTreeProfile>(&serialized).unwrap(),
+            profile
+        );
+
+        let partial =
+            serde_json::from_str::<JsonTreeProfile>(r#"{"settings":{"density":"Spacious"}}"#);
+        assert_eq!(
+            partial.unwrap(),
+            JsonTreeProfile {
+                settings: crate::JsonTreeSettings {
+                    density: JsonTreeDensity::Spacious,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Recording and replaying of user interactions with a [`JsonTree`](crate::JsonTree), for regression testing complex interaction sequences.
+//!
+//! Record the interactions while using the tree in your app with a [`JsonTreeRecorder`],
+//! then replay them in a headless test with a [`JsonTreeReplay`]:
+//! ```rust
+//! # use egui_json_tree::{
+//! #     recording::{JsonTreeInteraction, JsonTreeReplay},
+//! #     DefaultExpand, JsonTree,
+//! # };
+//! let value = serde_json::json!({ "foo": { "bar": [1, 2] } });
+//! let mut replay = JsonTreeReplay::new([
+//!     JsonTreeInteraction::Expand { pointer: "/foo".to_string() },
+//!     JsonTreeInteraction::Search { term: "2".to_string() },
+//! ]);
+//!
+//! let ctx = egui::Context::default();
+//! while !replay.is_finished() {
+//!     let _ = ctx.run(Default::default(), |ctx| {
+//!         egui::CentralPanel::default().show(ctx, |ui| {
+//!             let response = JsonTree::new("replayed-tree", &value)
+//!                 .default_expand(DefaultExpand::SearchResults(replay.search_term()))
+//!                 .show(ui);
+//!             replay.step(ui.ctx(), &response);
+//!         });
+//!     });
+//! }
+//! ```
+//!
+//! With the `serde` feature enabled, [`JsonTreeInteraction`]s can be serialized, e.g. to store recordings alongside your tests.
+
+use std::collections::VecDeque;
+
+use egui::Context;
+
+use crate::JsonTreeResponse;
+
+/// A user interaction with a [`JsonTree`](crate::JsonTree).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JsonTreeInteraction {
+    /// The array/object at the JSON Pointer string was expanded.
+    Expand { pointer: String },
+    /// The array/object at the JSON Pointer string was collapsed.
+    Collapse { pointer: String },
+    /// The search term was changed.
+    Search { term: String },
+}
+
+/// Records the [`JsonTreeInteraction`]s with a [`JsonTree`](crate::JsonTree) across frames.
+#[derive(Debug, Clone, Default)]
+pub struct JsonTreeRecorder {
+    interactions: Vec<JsonTreeInteraction>,
+    search_term: String,
+}
+
+impl JsonTreeRecorder {
+    /// Creates a new, empty [`JsonTreeRecorder`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the search term passed to the tree this frame, if it has changed since the previous frame.
+    pub fn record_search(&mut self, search_term: &str) {
+        if self.search_term != search_term {
+            self.search_term = search_term.to_string();
+            self.interactions.push(JsonTreeInteraction::Search {
+                term: search_term.to_string(),
+            });
+        }
+    }
+
+    /// Records the arrays/objects that were expanded or collapsed this frame, from the response of showing the tree.
+    pub fn record_response(&mut self, response: &JsonTreeResponse) {
+        self.interactions
+            .extend(response.toggled.iter().map(|(pointer, is_open)| {
+                let pointer = pointer.clone();
+                if *is_open {
+                    JsonTreeInteraction::Expand { pointer }
+                } else {
+                    JsonTreeInteraction::Collapse { pointer }
+                }
+            }));
+    }
+
+    /// Returns the interactions recorded so far.
+    pub fn interactions(&self) -> &[JsonTreeInteraction] {
+        &self.interactions
+    }
+
+    /// Consumes the recorder, returning the recorded interactions.
+    pub fn into_interactions(self) -> Vec<JsonTreeInteraction> {
+        self.interactions
+    }
+}
+
+/// Replays recorded [`JsonTreeInteraction`]s onto a [`JsonTree`](crate::JsonTree), one interaction per frame.
+#[derive(Debug, Clone, Default)]
+pub struct JsonTreeReplay {
+    interactions: VecDeque<JsonTreeInteraction>,
+    search_term: String,
+}
+
+impl JsonTreeReplay {
+    /// Creates a new [`JsonTreeReplay`] of the given interactions.
+    pub fn new(interactions: impl IntoIterator<Item = JsonTreeInteraction>) -> Self {
+        Self {
+            interactions: interactions.into_iter().collect(),
+            search_term: String::new(),
+        }
+    }
+
+    /// Returns the search term resulting from the interactions replayed so far.
+    /// Pass this to [`DefaultExpand::SearchResults`](crate::DefaultExpand::SearchResults) when showing the tree.
+    pub fn search_term(&self) -> &str {
+        &self.search_term
+    }
+
+    /// Returns whether all interactions have been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.interactions.is_empty()
+    }
+
+    /// Applies the next interaction to the tree that provided the response, returning the interaction if there was one.
+    ///
+    /// Expanding or collapsing takes effect when the tree is next shown.
+    pub fn step(
+        &mut self,
+        ctx: &Context,
+        response: &JsonTreeResponse,
+    ) -> Option<JsonTreeInteraction> {
+        let interaction = self.interactions.pop_front()?;
+
+        match &interaction {
+            JsonTreeInteraction::Expand { pointer } => response.set_expanded(ctx, pointer, true),
+            JsonTreeInteraction::Collapse { pointer } => response.set_expanded(ctx, pointer, false),
+            JsonTreeInteraction::Search { term } => self.search_term.clone_from(term),
+        }
+
+        ctx.request_repaint();
+        Some(interaction)
+    }
+}
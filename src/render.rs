@@ -1,25 +1,44 @@
 //! Rendering implementation for a [`JsonTree`](crate::JsonTree).
 
-use std::fmt::Display;
+use std::{fmt::Display, ops::Range};
 
 use egui::{
     collapsing_header::CollapsingState,
     text::LayoutJob,
-    util::cache::{ComputerMut, FrameCache},
-    Color32, FontId, Label, Response, Sense, TextFormat, Ui,
+    util::cache::{CacheTrait, ComputerMut, FrameCache},
+    Color32, Context, CursorIcon, FontId, Galley, Label, Pos2, Rect, Response, Sense, Shape,
+    TextFormat, Ui,
 };
 
 use crate::{
+    bracket_matching::BracketMatching,
+    context_menu::ContextMenu,
     delimiters::{ExpandableDelimiter, SpacingDelimiter},
+    epoch::format_epoch_timestamp,
     pointer::{JsonPointer, JsonPointerSegment},
-    search::SearchTerm,
+    schema_fields::JsonTreeSchemaField,
+    search::{SearchTarget, SearchTerm},
+    snapshot::record_job,
+    split_view::Selection,
+    subtree::SubtreeRenderHooks,
+    uuid::{is_uuid, shorten_uuid},
     value::{BaseValueType, ToJsonTreeValue},
-    JsonTreeStyle, JsonTreeVisuals,
+    value_editor::ValueEditor,
+    JsonTreeStyle, JsonTreeVisuals, UuidDisplay,
 };
 
+/// How long "Copied" is shown after copying a value by double clicking it.
+const COPIED_NOTICE_SECS: f64 = 1.0;
+
 /// A closure for a user-defined custom rendering implementation.
 pub type RenderHook<'a, T> = dyn FnMut(&mut Ui, RenderContext<'a, '_, T>) + 'a;
 
+/// A closure for a user-defined formatter of number values.
+///
+/// It receives the number as it appears in the JSON document, and the JSON pointer to it.
+/// Returning `None` displays the number as normal.
+pub type NumberFormatter<'a> = dyn Fn(&str, JsonPointer) -> Option<String> + 'a;
+
 /// A trait for types that provide a default rendering implementation.
 pub trait DefaultRender {
     fn render_default(&self, ui: &mut Ui) -> Response;
@@ -79,15 +98,76 @@ pub struct RenderPropertyContext<'a, 'b, T: ToJsonTreeValue> {
     /// This can be used to toggle or check whether the array/object is expanded. Any mutations will be stored after the render hook.
     pub collapsing_state: Option<&'b mut CollapsingState>,
     pub(crate) search_term: Option<&'b SearchTerm>,
+    /// Whether the whole property is highlighted, i.e. its value was selected by a JSONPath expression.
+    pub(crate) highlighted: bool,
+    /// Whether the value under this property is the current search match.
+    pub(crate) is_current_match: bool,
+    /// Whether to move keyboard focus to this property, because search match navigation moved to its value.
+    pub(crate) request_focus: bool,
+    pub(crate) context_menu: Option<&'b ContextMenu<'a, T>>,
+    pub(crate) selection: Option<&'b Selection>,
+}
+
+impl<'a, 'b, T: ToJsonTreeValue> RenderPropertyContext<'a, 'b, T> {
+    /// Returns the screen rects of the search matches within the text of a galley painted at `galley_pos`,
+    /// e.g. to highlight matches when rendering the property with a custom galley.
+    /// See [`RenderBaseValueContext::search_match_rects`].
+    pub fn search_match_rects(&self, galley: &Galley, galley_pos: Pos2) -> Vec<Rect> {
+        search_match_rects(
+            self.key_search_term(),
+            SearchTarget::Key,
+            galley,
+            galley_pos,
+        )
+    }
+
+    /// Returns the byte ranges of the search matches within the object key, in order,
+    /// e.g. to reproduce the search highlighting when rendering the property with a custom widget.
+    /// Array indices are never matched.
+    /// See [`RenderBaseValueContext::search_match_ranges`].
+    pub fn search_match_ranges(&self) -> Vec<Range<usize>> {
+        match (self.property, self.key_search_term()) {
+            (
+                JsonPointerSegment::Key(_) | JsonPointerSegment::DuplicateKey(..),
+                Some(search_term),
+            ) => search_term.find_match_ranges_in(&self.property.to_string(), SearchTarget::Key),
+            _ => vec![],
+        }
+    }
+
+    fn key_search_term(&self) -> Option<&'b SearchTerm> {
+        self.search_term
+            .filter(|search_term| search_term.matches_keys())
+    }
 }
 
 impl<'a, 'b, T: ToJsonTreeValue> DefaultRender for RenderPropertyContext<'a, 'b, T> {
     fn render_default(&self, ui: &mut Ui) -> Response {
-        render_property(ui, self.style, &self.property, self.search_term)
+        let background = self
+            .selection
+            .map(|selection| (selection, ui.painter().add(Shape::Noop)));
+        let response = render_property(
+            ui,
+            self.style,
+            &self.property,
+            self.key_search_term(),
+            self.highlighted,
+            self.is_current_match,
+        );
+        if self.request_focus {
+            response.request_focus();
+        }
+        if let Some((selection, background)) = background {
+            selection.attach(ui, &response, self.pointer, background);
+        }
+        if let Some(context_menu) = self.context_menu {
+            context_menu.attach(&response, self.pointer, self.value);
+        }
+        response
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum ParentStatus {
     NoParent,
     ExpandedParent,
@@ -107,19 +187,118 @@ pub struct RenderBaseValueContext<'a, 'b, T: ToJsonTreeValue> {
     /// The [`JsonTreeStyle`] that the [`JsonTree`](crate::JsonTree) was configured with.
     pub style: &'b JsonTreeStyle,
     pub(crate) search_term: Option<&'b SearchTerm>,
+    /// Whether the value is the current search match.
+    pub(crate) is_current_match: bool,
     pub(crate) parent_status: ParentStatus,
+    pub(crate) formatted_number: Option<String>,
+    pub(crate) context_menu: Option<&'b ContextMenu<'a, T>>,
+    pub(crate) value_editor: Option<&'b ValueEditor<'a>>,
+    pub(crate) copy_on_double_click: bool,
+    pub(crate) selection: Option<&'b Selection>,
+}
+
+impl<'a, 'b, T: ToJsonTreeValue> RenderBaseValueContext<'a, 'b, T> {
+    /// The number as formatted by the formatter registered via [`JsonTree::format_numbers`](crate::JsonTree::format_numbers), if any.
+    /// The default rendering displays this instead of [`display_value`](Self::display_value).
+    pub fn formatted_number(&self) -> Option<&str> {
+        self.formatted_number.as_deref()
+    }
+
+    /// Returns the screen rects of the search matches within the text of a galley painted at `galley_pos`,
+    /// e.g. to highlight matches when rendering the value with a custom galley.
+    ///
+    /// A match that wraps across multiple rows of the galley has a rect for each row,
+    /// covering only the glyphs of the match on that row.
+    pub fn search_match_rects(&self, galley: &Galley, galley_pos: Pos2) -> Vec<Rect> {
+        search_match_rects(
+            self.value_search_term(),
+            SearchTarget::Value(self.value_type),
+            galley,
+            galley_pos,
+        )
+    }
+
+    /// Returns the byte ranges of the search matches within the text of [`display_value`](Self::display_value), in order,
+    /// e.g. to reproduce the search highlighting when rendering the value with a custom widget:
+    /// ```rust
+    /// # use egui::{text::LayoutJob, Color32, TextFormat};
+    /// # use egui_json_tree::{render::{DefaultRender, RenderContext}, DefaultExpand, JsonTree};
+    /// # egui::__run_test_ui(|ui| {
+    /// # let value = serde_json::json!({ "level": "error" });
+    /// JsonTree::new("custom-highlight-tree", &value)
+    ///     .default_expand(DefaultExpand::SearchResults("err"))
+    ///     .on_render(|ui, ctx| match ctx {
+    ///         RenderContext::BaseValue(ctx) => {
+    ///             let text = ctx.display_value.to_string();
+    ///             let mut job = LayoutJob::default();
+    ///             let mut start = 0;
+    ///             for range in ctx.search_match_ranges() {
+    ///                 job.append(&text[start..range.start], 0.0, TextFormat::default());
+    ///                 let highlighted = TextFormat {
+    ///                     background: Color32::YELLOW,
+    ///                     ..Default::default()
+    ///                 };
+    ///                 job.append(&text[range.clone()], 0.0, highlighted);
+    ///                 start = range.end;
+    ///             }
+    ///             job.append(&text[start..], 0.0, TextFormat::default());
+    ///             ui.label(job);
+    ///         }
+    ///         ctx => {
+    ///             ctx.render_default(ui);
+    ///         }
+    ///     })
+    ///     .show(ui);
+    /// # });
+    /// ```
+    ///
+    /// This is empty if there is no search term, or the value does not match it.
+    pub fn search_match_ranges(&self) -> Vec<Range<usize>> {
+        self.value_search_term()
+            .map_or_else(Vec::new, |search_term| {
+                search_term.find_match_ranges_in(
+                    &self.display_value.to_string(),
+                    SearchTarget::Value(self.value_type),
+                )
+            })
+    }
+
+    fn value_search_term(&self) -> Option<&'b SearchTerm> {
+        self.search_term.filter(|search_term| {
+            search_term.matches_values() && search_term.matches_value_type(self.value_type)
+        })
+    }
 }
 
 impl<'a, 'b, T: ToJsonTreeValue> DefaultRender for RenderBaseValueContext<'a, 'b, T> {
     fn render_default(&self, ui: &mut Ui) -> Response {
-        render_value(
-            ui,
-            self.style,
-            &self.display_value.to_string(),
-            &self.value_type,
-            self.search_term,
-            self.parent_status,
-        )
+        if let Some(response) = self
+            .value_editor
+            .and_then(|value_editor| value_editor.show_if_editing(ui, self))
+        {
+            return response;
+        }
+        let background = self
+            .selection
+            .map(|selection| (selection, ui.painter().add(Shape::Noop)));
+        let response = render_value(ui, self);
+        if let Some((selection, background)) = background {
+            selection.attach(ui, &response, self.pointer, background);
+        }
+        if let Some(context_menu) = self.context_menu {
+            context_menu.attach(&response, self.pointer, self.value);
+        }
+        if let Some(value_editor) = self.value_editor {
+            value_editor.attach(&response, self);
+        }
+        if self.copy_on_double_click
+            && !self
+                .value_editor
+                .is_some_and(|value_editor| value_editor.enabled)
+        {
+            attach_copy_on_double_click(ui, &response, &self.display_value.to_string());
+        }
+        response
     }
 }
 
@@ -136,11 +315,38 @@ pub struct RenderExpandableDelimiterContext<'a, 'b, T: ToJsonTreeValue> {
     /// The [`egui::collapsing_header::CollapsingState`] for the array or object that this delimiter belongs to.
     /// This can be used to toggle or check whether the array/object is expanded. Any mutations will be stored after the render hook.
     pub collapsing_state: &'b mut CollapsingState,
+    pub(crate) context_menu: Option<&'b ContextMenu<'a, T>>,
+    pub(crate) bracket_matching: Option<&'b BracketMatching>,
+    pub(crate) selection: Option<&'b Selection>,
 }
 
 impl<'a, 'b, T: ToJsonTreeValue> DefaultRender for RenderExpandableDelimiterContext<'a, 'b, T> {
     fn render_default(&self, ui: &mut Ui) -> Response {
-        render_delimiter(ui, self.style, self.delimiter.as_ref())
+        let background = self
+            .bracket_matching
+            .map(|bracket_matching| (bracket_matching, ui.painter().add(Shape::Noop)));
+        let selection_background = self
+            .selection
+            .map(|selection| (selection, ui.painter().add(Shape::Noop)));
+        let response = match (self.delimiter, &self.style.abbreviation) {
+            (ExpandableDelimiter::CollapsedArray, Some(abbreviation)) => {
+                render_delimiter(ui, self.style, &format!("[{abbreviation}]"))
+            }
+            (ExpandableDelimiter::CollapsedObject, Some(abbreviation)) => {
+                render_delimiter(ui, self.style, &format!("{{{abbreviation}}}"))
+            }
+            _ => render_delimiter(ui, self.style, self.delimiter.as_ref()),
+        };
+        if let Some((bracket_matching, background)) = background {
+            bracket_matching.attach(ui, &response, self.pointer, self.delimiter, background);
+        }
+        if let Some((selection, background)) = selection_background {
+            selection.attach(ui, &response, self.pointer, background);
+        }
+        if let Some(context_menu) = self.context_menu {
+            context_menu.attach(&response, self.pointer, self.value);
+        }
+        response
     }
 }
 
@@ -157,11 +363,29 @@ impl<'b> DefaultRender for RenderSpacingDelimiterContext<'b> {
 
 pub(crate) struct JsonTreeRenderer<'a, T: ToJsonTreeValue> {
     pub(crate) render_hook: Option<Box<RenderHook<'a, T>>>,
+    /// The render hooks of the subtrees configured via [`JsonTree::subtree`](crate::JsonTree::subtree), by the index of the subtree.
+    /// While a subtree with a render hook is shown, its hook is swapped with [`JsonTreeRenderer::render_hook`].
+    pub(crate) subtree_render_hooks: SubtreeRenderHooks<'a, T>,
+    pub(crate) number_formatter: Option<Box<NumberFormatter<'a>>>,
+    pub(crate) context_menu: ContextMenu<'a, T>,
+    pub(crate) value_editor: ValueEditor<'a>,
+    pub(crate) bracket_matching: Option<BracketMatching>,
+    pub(crate) copy_on_double_click: bool,
+    pub(crate) selection: Option<Selection>,
 }
 
 impl<'a, T: ToJsonTreeValue> Default for JsonTreeRenderer<'a, T> {
     fn default() -> Self {
-        Self { render_hook: None }
+        Self {
+            render_hook: None,
+            subtree_render_hooks: vec![],
+            number_formatter: None,
+            context_menu: Default::default(),
+            value_editor: Default::default(),
+            bracket_matching: None,
+            copy_on_double_click: false,
+            selection: None,
+        }
     }
 }
 
@@ -171,6 +395,11 @@ impl<'a, T: ToJsonTreeValue> JsonTreeRenderer<'a, T> {
         ui: &mut Ui,
         context: RenderPropertyContext<'a, 'b, T>,
     ) {
+        let context = RenderPropertyContext {
+            context_menu: Some(&self.context_menu),
+            selection: self.selection.as_ref(),
+            ..context
+        };
         match self.render_hook.as_mut() {
             Some(render_hook) => {
                 render_hook(ui, RenderContext::Property(context));
@@ -184,8 +413,22 @@ impl<'a, T: ToJsonTreeValue> JsonTreeRenderer<'a, T> {
     pub(crate) fn render_value<'b>(
         &mut self,
         ui: &mut Ui,
-        context: RenderBaseValueContext<'a, 'b, T>,
+        mut context: RenderBaseValueContext<'a, 'b, T>,
     ) {
+        if let Some(number_formatter) = self.number_formatter.as_ref() {
+            if context.value_type == BaseValueType::Number {
+                context.formatted_number =
+                    number_formatter(&context.display_value.to_string(), context.pointer);
+            }
+        }
+        let context = RenderBaseValueContext {
+            context_menu: Some(&self.context_menu),
+            value_editor: Some(&self.value_editor),
+            copy_on_double_click: self.copy_on_double_click,
+            selection: self.selection.as_ref(),
+            ..context
+        };
+
         match self.render_hook.as_mut() {
             Some(render_hook) => {
                 render_hook(ui, RenderContext::BaseValue(context));
@@ -201,6 +444,12 @@ impl<'a, T: ToJsonTreeValue> JsonTreeRenderer<'a, T> {
         ui: &mut Ui,
         context: RenderExpandableDelimiterContext<'a, 'b, T>,
     ) {
+        let context = RenderExpandableDelimiterContext {
+            context_menu: Some(&self.context_menu),
+            bracket_matching: self.bracket_matching.as_ref(),
+            selection: self.selection.as_ref(),
+            ..context
+        };
         match self.render_hook.as_mut() {
             Some(render_hook) => {
                 render_hook(ui, RenderContext::ExpandableDelimiter(context));
@@ -220,19 +469,47 @@ impl<'a, T: ToJsonTreeValue> JsonTreeRenderer<'a, T> {
     }
 }
 
+/// The inputs for laying out a non-recursive JSON value, used as the key for caching the resulting [`LayoutJob`].
+#[derive(Clone, Copy, Hash)]
+struct ValueLayoutJobKey<'a> {
+    visuals: &'a JsonTreeVisuals,
+    value_str: &'a str,
+    value_type: &'a BaseValueType,
+    search_term: Option<&'a SearchTerm>,
+    font_id: &'a FontId,
+    uuid_display: UuidDisplay,
+    annotation: Option<&'a str>,
+    formatted_number: Option<&'a str>,
+}
+
 #[derive(Default)]
 struct ValueLayoutJobCreator;
 
 impl ValueLayoutJobCreator {
-    fn create(
-        &self,
-        visuals: &JsonTreeVisuals,
-        value_str: &str,
-        value_type: &BaseValueType,
-        search_term: Option<&SearchTerm>,
-        font_id: &FontId,
-    ) -> LayoutJob {
-        let color = visuals.get_color(value_type);
+    fn create(&self, key: &ValueLayoutJobKey) -> LayoutJob {
+        let ValueLayoutJobKey {
+            visuals,
+            value_str,
+            value_type,
+            search_term,
+            font_id,
+            uuid_display,
+            annotation,
+            formatted_number,
+        } = *key;
+
+        let shortened_uuid;
+        let (value_str, color) = if is_displayed_as_uuid(value_str, value_type, uuid_display) {
+            let value_str = if uuid_display == UuidDisplay::Shortened {
+                shortened_uuid = shorten_uuid(value_str);
+                &shortened_uuid
+            } else {
+                value_str
+            };
+            (value_str, visuals.uuid_color)
+        } else {
+            (value_str, visuals.get_color(value_type))
+        };
         let add_quote_if_string = |job: &mut LayoutJob| {
             if *value_type == BaseValueType::String {
                 append(job, "\"", color, None, font_id)
@@ -240,66 +517,140 @@ impl ValueLayoutJobCreator {
         };
         let mut job = LayoutJob::default();
         add_quote_if_string(&mut job);
-        add_text_with_highlighting(
-            &mut job,
-            value_str,
-            color,
-            search_term,
-            visuals.highlight_color,
-            font_id,
-        );
+        match formatted_number {
+            // Highlight the entire formatted number if only the original number matches the search term,
+            // e.g. a search for "1000" should highlight "1,000".
+            Some(formatted_number)
+                if search_term.is_some_and(|search_term| {
+                    let target = SearchTarget::Value(*value_type);
+                    search_term
+                        .find_match_ranges_in(formatted_number, target)
+                        .is_empty()
+                        && !search_term
+                            .find_match_ranges_in(value_str, target)
+                            .is_empty()
+                }) =>
+            {
+                append(
+                    &mut job,
+                    formatted_number,
+                    color,
+                    Some(visuals.highlight_color),
+                    font_id,
+                );
+            }
+            _ => {
+                add_text_with_highlighting(
+                    &mut job,
+                    formatted_number.unwrap_or(value_str),
+                    color,
+                    search_term,
+                    SearchTarget::Value(*value_type),
+                    visuals.highlight_color,
+                    font_id,
+                );
+            }
+        }
         add_quote_if_string(&mut job);
+        if let Some(annotation) = annotation {
+            append(
+                &mut job,
+                &format!(" ({annotation})"),
+                visuals.punctuation_color,
+                None,
+                font_id,
+            );
+        }
         job
     }
 }
 
-impl
-    ComputerMut<
-        (
-            &JsonTreeVisuals,
-            &str,
-            &BaseValueType,
-            Option<&SearchTerm>,
-            &FontId,
-        ),
-        LayoutJob,
-    > for ValueLayoutJobCreator
-{
-    fn compute(
-        &mut self,
-        (visuals, value_str, value_type, search_term, font_id): (
-            &JsonTreeVisuals,
-            &str,
-            &BaseValueType,
-            Option<&SearchTerm>,
-            &FontId,
-        ),
-    ) -> LayoutJob {
-        self.create(visuals, value_str, value_type, search_term, font_id)
+impl<'a> ComputerMut<ValueLayoutJobKey<'a>, LayoutJob> for ValueLayoutJobCreator {
+    fn compute(&mut self, key: ValueLayoutJobKey<'a>) -> LayoutJob {
+        self.create(&key)
     }
 }
 
 type ValueLayoutJobCreatorCache = FrameCache<LayoutJob, ValueLayoutJobCreator>;
 
-fn render_value(
+fn render_value<T: ToJsonTreeValue>(
     ui: &mut Ui,
-    style: &JsonTreeStyle,
-    value_str: &str,
-    value_type: &BaseValueType,
-    search_term: Option<&SearchTerm>,
-    parent_status: ParentStatus,
+    context: &RenderBaseValueContext<'_, '_, T>,
 ) -> Response {
+    let style = context.style;
+    let value_str = &context.display_value.to_string();
+    let value_type = &context.value_type;
+    let formatted_number = context.formatted_number.as_deref();
+
+    let epoch_timestamp = if *value_type == BaseValueType::Number {
+        style
+            .epoch_timestamps
+            .resolve_unit(&context.pointer)
+            .and_then(|unit| format_epoch_timestamp(value_str, unit))
+    } else {
+        None
+    };
+
     let mut job = ui.ctx().memory_mut(|mem| {
-        mem.caches.cache::<ValueLayoutJobCreatorCache>().get((
-            style.resolve_visuals(ui),
-            value_str,
-            value_type,
-            search_term,
-            &style.resolve_font_id(ui),
-        ))
+        mem.caches
+            .cache::<ValueLayoutJobCreatorCache>()
+            .get(ValueLayoutJobKey {
+                visuals: &style.resolve_match_visuals(ui, context.is_current_match),
+                value_str,
+                value_type,
+                search_term: context.value_search_term(),
+                font_id: &style.resolve_element_font_id(ui, &style.font_families.values),
+                uuid_display: style.uuid_display,
+                annotation: epoch_timestamp.as_deref(),
+                formatted_number,
+            })
     });
-    job.wrap = style.resolve_value_text_wrapping(parent_status, ui);
-    render_job(ui, job)
+    job.wrap = style.resolve_value_text_wrapping(context.parent_status, ui);
+    let response = render_job(ui, job);
+
+    if formatted_number.is_some() {
+        return response.on_hover_text(value_str);
+    }
+
+    // Shows the full UUID on hover if it was shortened.
+    if style.uuid_display == UuidDisplay::Shortened
+        && is_displayed_as_uuid(value_str, value_type, style.uuid_display)
+    {
+        response.on_hover_text(value_str)
+    } else {
+        response
+    }
+}
+
+/// Copies the text of a value to the clipboard when its response is double clicked,
+/// and shows "Copied" next to it for [`COPIED_NOTICE_SECS`] afterwards.
+fn attach_copy_on_double_click(ui: &Ui, response: &Response, text: &str) {
+    let copied_at_id = response.id.with("copied_at");
+    let now = ui.input(|i| i.time);
+    if response.double_clicked() {
+        ui.ctx().copy_text(text.to_string());
+        ui.data_mut(|d| d.insert_temp(copied_at_id, now));
+    }
+
+    let Some(copied_at) = ui.data(|d| d.get_temp::<f64>(copied_at_id)) else {
+        return;
+    };
+    let elapsed = now - copied_at;
+    if elapsed < COPIED_NOTICE_SECS {
+        response.show_tooltip_text("Copied");
+        ui.ctx()
+            .request_repaint_after_secs((COPIED_NOTICE_SECS - elapsed) as f32);
+    } else {
+        ui.data_mut(|d| d.remove::<f64>(copied_at_id));
+    }
+}
+
+fn is_displayed_as_uuid(
+    value_str: &str,
+    value_type: &BaseValueType,
+    uuid_display: UuidDisplay,
+) -> bool {
+    uuid_display != UuidDisplay::Plain && *value_type == BaseValueType::String && is_uuid(value_str)
 }
 
 #[derive(Default)]
@@ -311,22 +662,35 @@ impl PropertyLayoutJobCreator {
         visuals: &JsonTreeVisuals,
         property: &JsonPointerSegment,
         search_term: Option<&SearchTerm>,
+        highlighted: bool,
         font_id: &FontId,
     ) -> LayoutJob {
         let mut job = LayoutJob::default();
+        let highlight_color = visuals.resolve_key_highlight_color();
+        let background = highlighted.then_some(highlight_color);
         match property {
             JsonPointerSegment::Index(_) => add_array_idx(
                 &mut job,
                 &property.to_string(),
                 visuals.array_idx_color,
+                background,
                 font_id,
             ),
-            JsonPointerSegment::Key(_) => add_object_key(
+            JsonPointerSegment::Key(_) | JsonPointerSegment::DuplicateKey(..) if highlighted => {
+                append(
+                    &mut job,
+                    &format!("\"{property}\""),
+                    visuals.object_key_color,
+                    background,
+                    font_id,
+                )
+            }
+            JsonPointerSegment::Key(_) | JsonPointerSegment::DuplicateKey(..) => add_object_key(
                 &mut job,
                 &property.to_string(),
                 visuals.object_key_color,
                 search_term,
-                visuals.highlight_color,
+                highlight_color,
                 font_id,
             ),
         };
@@ -340,6 +704,7 @@ impl<'a>
             &JsonTreeVisuals,
             &JsonPointerSegment<'a>,
             Option<&SearchTerm>,
+            bool,
             &FontId,
         ),
         LayoutJob,
@@ -347,14 +712,15 @@ impl<'a>
 {
     fn compute(
         &mut self,
-        (visuals, parent, search_term, font_id): (
+        (visuals, parent, search_term, highlighted, font_id): (
             &JsonTreeVisuals,
             &JsonPointerSegment,
             Option<&SearchTerm>,
+            bool,
             &FontId,
         ),
     ) -> LayoutJob {
-        self.create(visuals, parent, search_term, font_id)
+        self.create(visuals, parent, search_term, highlighted, font_id)
     }
 }
 
@@ -365,13 +731,16 @@ fn render_property(
     style: &JsonTreeStyle,
     property: &JsonPointerSegment,
     search_term: Option<&SearchTerm>,
+    highlighted: bool,
+    is_current_match: bool,
 ) -> Response {
     let job = ui.ctx().memory_mut(|mem| {
         mem.caches.cache::<PropertyLayoutJobCreatorCache>().get((
-            style.resolve_visuals(ui),
+            &style.resolve_match_visuals(ui, is_current_match),
             property,
             search_term,
-            &style.resolve_font_id(ui),
+            highlighted,
+            &style.resolve_element_font_id(ui, &style.font_families.keys),
         ))
     });
 
@@ -387,12 +756,26 @@ fn add_object_key(
     font_id: &FontId,
 ) {
     append(job, "\"", color, None, font_id);
-    add_text_with_highlighting(job, key_str, color, search_term, highlight_color, font_id);
+    add_text_with_highlighting(
+        job,
+        key_str,
+        color,
+        search_term,
+        SearchTarget::Key,
+        highlight_color,
+        font_id,
+    );
     append(job, "\"", color, None, font_id);
 }
 
-fn add_array_idx(job: &mut LayoutJob, idx_str: &str, color: Color32, font_id: &FontId) {
-    append(job, idx_str, color, None, font_id);
+fn add_array_idx(
+    job: &mut LayoutJob,
+    idx_str: &str,
+    color: Color32,
+    background: Option<Color32>,
+    font_id: &FontId,
+) {
+    append(job, idx_str, color, background, font_id);
 }
 
 fn add_text_with_highlighting(
@@ -400,27 +783,32 @@ fn add_text_with_highlighting(
     text_str: &str,
     text_color: Color32,
     search_term: Option<&SearchTerm>,
+    target: SearchTarget,
     highlight_color: Color32,
     font_id: &FontId,
 ) {
     if let Some(search_term) = search_term {
-        let matches = search_term.find_match_indices_in(text_str);
+        let matches = search_term.find_match_ranges_in(text_str, target);
         if !matches.is_empty() {
             let mut start = 0;
-            for match_idx in matches {
-                append(job, &text_str[start..match_idx], text_color, None, font_id);
-
-                let highlight_end_idx = match_idx + search_term.len();
+            for match_range in matches {
+                append(
+                    job,
+                    &text_str[start..match_range.start],
+                    text_color,
+                    None,
+                    font_id,
+                );
 
                 append(
                     job,
-                    &text_str[match_idx..highlight_end_idx],
+                    &text_str[match_range.clone()],
                     text_color,
                     Some(highlight_color),
                     font_id,
                 );
 
-                start = highlight_end_idx;
+                start = match_range.end;
             }
             append(job, &text_str[start..], text_color, None, font_id);
             return;
@@ -429,6 +817,46 @@ fn add_text_with_highlighting(
     append(job, text_str, text_color, None, font_id);
 }
 
+fn search_match_rects(
+    search_term: Option<&SearchTerm>,
+    target: SearchTarget,
+    galley: &Galley,
+    galley_pos: Pos2,
+) -> Vec<Rect> {
+    let Some(search_term) = search_term else {
+        return vec![];
+    };
+    let match_ranges = search_term.find_match_ranges_in(galley.text(), target);
+    if match_ranges.is_empty() {
+        return vec![];
+    }
+
+    let mut rects = vec![];
+    let mut byte_idx = 0;
+    for row in &galley.rows {
+        let mut current_rect: Option<Rect> = None;
+        for glyph in &row.glyphs {
+            if match_ranges.iter().any(|range| range.contains(&byte_idx)) {
+                let glyph_rect =
+                    Rect::from_x_y_ranges(glyph.pos.x..=glyph.max_x(), row.rect.y_range());
+                current_rect = Some(current_rect.map_or(glyph_rect, |rect| rect.union(glyph_rect)));
+            } else if let Some(rect) = current_rect.take() {
+                rects.push(rect);
+            }
+            byte_idx += glyph.chr.len_utf8();
+        }
+        rects.extend(current_rect);
+        if row.ends_with_newline {
+            byte_idx += '\n'.len_utf8();
+        }
+    }
+
+    rects
+        .into_iter()
+        .map(|rect| rect.translate(galley_pos.to_vec2()))
+        .collect()
+}
+
 fn append(
     job: &mut LayoutJob,
     text_str: &str,
@@ -456,12 +884,263 @@ fn render_delimiter(ui: &mut Ui, style: &JsonTreeStyle, delimiter_str: &str) ->
         delimiter_str,
         style.resolve_visuals(ui).punctuation_color,
         None,
+        &style.resolve_element_font_id(ui, &style.font_families.punctuation),
+    );
+    render_job(ui, job)
+}
+
+/// Renders the label configured via [`JsonTreeStyle::root_label`], styled like an object key.
+pub(crate) fn render_root_label(ui: &mut Ui, style: &JsonTreeStyle, root_label: &str) -> Response {
+    let mut job = LayoutJob::default();
+    append(
+        &mut job,
+        root_label,
+        style.resolve_visuals(ui).object_key_color,
+        None,
+        &style.resolve_element_font_id(ui, &style.font_families.keys),
+    );
+    render_job(ui, job)
+}
+
+/// Renders the heading of a group of object entries, followed by the number of entries in the group.
+pub(crate) fn render_group_heading(
+    ui: &mut Ui,
+    style: &JsonTreeStyle,
+    heading: &str,
+    num_entries: usize,
+) -> Response {
+    let visuals = style.resolve_visuals(ui);
+    let font_id = style.resolve_element_font_id(ui, &style.font_families.keys);
+
+    let mut job = LayoutJob::default();
+    append(&mut job, heading, visuals.object_key_color, None, &font_id);
+    append(
+        &mut job,
+        &format!(" ({num_entries})"),
+        visuals.punctuation_color,
+        None,
+        &font_id,
+    );
+    render_job(ui, job)
+}
+
+/// Renders a dimmed placeholder for a field missing from an object, e.g. `"port": <unset, default 8080>`.
+pub(crate) fn render_missing_field(
+    ui: &mut Ui,
+    style: &JsonTreeStyle,
+    field: &JsonTreeSchemaField,
+    insertable: bool,
+) -> Response {
+    let color = ui.visuals().weak_text_color();
+    let font_id = style.resolve_element_font_id(ui, &style.font_families.keys);
+
+    let placeholder = match &field.default {
+        Some(default) => format!("<unset, default {default}>"),
+        None => "<unset>".to_string(),
+    };
+    let mut job = LayoutJob::default();
+    append(
+        &mut job,
+        &format!("\"{}\"", field.key),
+        color,
+        None,
+        &font_id,
+    );
+    append(&mut job, ": ", color, None, &font_id);
+    append(&mut job, &placeholder, color, None, &font_id);
+    let response = render_job(ui, job);
+    if insertable {
+        response
+            .on_hover_cursor(CursorIcon::PointingHand)
+            .on_hover_text("Insert with the default value")
+    } else {
+        response
+    }
+}
+
+/// Renders the properties of a chain of condensed arrays/objects, e.g. `…/data/items/41/`.
+pub(crate) fn render_condensed_path(
+    ui: &mut Ui,
+    style: &JsonTreeStyle,
+    chain: &[JsonPointerSegment],
+) -> Response {
+    let visuals = style.resolve_visuals(ui);
+    let font_id = style.resolve_element_font_id(ui, &style.font_families.keys);
+
+    let mut job = LayoutJob::default();
+    append(&mut job, "…/", visuals.punctuation_color, None, &font_id);
+    for property in chain {
+        let color = match property {
+            JsonPointerSegment::Index(_) => visuals.array_idx_color,
+            JsonPointerSegment::Key(_) | JsonPointerSegment::DuplicateKey(..) => {
+                visuals.object_key_color
+            }
+        };
+        append(&mut job, &property.to_string(), color, None, &font_id);
+        append(&mut job, "/", visuals.punctuation_color, None, &font_id);
+    }
+    render_job(ui, job)
+        .on_hover_cursor(CursorIcon::PointingHand)
+        .on_hover_text("Show the full path")
+}
+
+/// Renders a badge showing the number of search matches within a collapsed array/object.
+pub(crate) fn render_match_count_badge(
+    ui: &mut Ui,
+    style: &JsonTreeStyle,
+    match_count: usize,
+) -> Response {
+    let visuals = style.resolve_visuals(ui);
+    let mut font_id = style.resolve_font_id(ui);
+    font_id.size *= 0.8;
+
+    let text = if match_count == 1 {
+        " 1 match ".to_string()
+    } else {
+        format!(" {match_count} matches ")
+    };
+
+    let mut job = LayoutJob::default();
+    append(
+        &mut job,
+        &text,
+        visuals.punctuation_color,
+        Some(visuals.highlight_color),
+        &font_id,
+    );
+
+    ui.add_space(ui.spacing().icon_spacing);
+    render_job(ui, job)
+}
+
+/// Renders a comment after the delimiter of an array/object, e.g. its numeric summary or subtree hash.
+pub(crate) fn render_trailing_comment(
+    ui: &mut Ui,
+    style: &JsonTreeStyle,
+    comment: &dyn Display,
+) -> Response {
+    let mut font_id = style.resolve_font_id(ui);
+    font_id.size *= 0.8;
+
+    let mut job = LayoutJob::default();
+    append(
+        &mut job,
+        &format!("  // {comment}"),
+        style.resolve_visuals(ui).punctuation_color,
+        None,
+        &font_id,
+    );
+    render_job(ui, job)
+}
+
+/// Renders the full text of a long string value within its viewer, highlighting the matches of the search term.
+/// The text is not recorded in the snapshot, as the value is already recorded for its row.
+pub(crate) fn render_long_string(
+    ui: &mut Ui,
+    style: &JsonTreeStyle,
+    text: &str,
+    search_term: Option<&SearchTerm>,
+) -> Response {
+    let visuals = style.resolve_visuals(ui);
+    let font_id = style.resolve_element_font_id(ui, &style.font_families.values);
+
+    let mut job = LayoutJob::default();
+    add_text_with_highlighting(
+        &mut job,
+        text,
+        visuals.string_color,
+        search_term,
+        SearchTarget::Value(BaseValueType::String),
+        visuals.highlight_color,
+        &font_id,
+    );
+    job.wrap.max_width = ui.available_width();
+    let galley = ui.fonts(|f| f.layout_job(job));
+    ui.add(Label::new(galley).selectable(true))
+}
+
+/// Renders the progress of revealing the entries of a large array/object across multiple frames.
+pub(crate) fn render_expansion_progress(
+    ui: &mut Ui,
+    style: &JsonTreeStyle,
+    num_revealed_entries: usize,
+    num_entries: usize,
+) -> Response {
+    let font_id = style.resolve_font_id(ui);
+
+    ui.horizontal(|ui| {
+        ui.add(egui::Spinner::new().size(font_id.size));
+
+        let mut job = LayoutJob::default();
+        append(
+            &mut job,
+            &format!("{num_revealed_entries} of {num_entries} entries shown..."),
+            style.resolve_visuals(ui).punctuation_color,
+            None,
+            &font_id,
+        );
+        render_job(ui, job)
+    })
+    .inner
+}
+
+pub(crate) fn render_search_progress(ui: &mut Ui, style: &JsonTreeStyle) -> Response {
+    let font_id = style.resolve_font_id(ui);
+
+    ui.horizontal(|ui| {
+        ui.add(egui::Spinner::new().size(font_id.size));
+
+        let mut job = LayoutJob::default();
+        append(
+            &mut job,
+            "Searching...",
+            style.resolve_visuals(ui).punctuation_color,
+            None,
+            &font_id,
+        );
+        render_job(ui, job)
+    })
+    .inner
+}
+
+pub(crate) fn render_hidden_entries_count(
+    ui: &mut Ui,
+    style: &JsonTreeStyle,
+    num_hidden_entries: usize,
+) -> Response {
+    let text = match num_hidden_entries {
+        1 => "1 entry hidden".to_string(),
+        n => format!("{n} entries hidden"),
+    };
+    let mut job = LayoutJob::default();
+    append(
+        &mut job,
+        &text,
+        ui.visuals().weak_text_color(),
+        None,
         &style.resolve_font_id(ui),
     );
     render_job(ui, job)
 }
 
+/// Returns the number of [`LayoutJob`]s currently cached for rendering values and properties.
+pub(crate) fn num_cached_layout_jobs(ctx: &Context) -> usize {
+    ctx.memory_mut(|mem| {
+        mem.caches.cache::<ValueLayoutJobCreatorCache>().len()
+            + mem.caches.cache::<PropertyLayoutJobCreatorCache>().len()
+    })
+}
+
+/// Clears the [`LayoutJob`]s cached for rendering values and properties.
+pub(crate) fn clear_layout_job_caches(ctx: &Context) {
+    ctx.memory_mut(|mem| {
+        *mem.caches.cache::<ValueLayoutJobCreatorCache>() = Default::default();
+        *mem.caches.cache::<PropertyLayoutJobCreatorCache>() = Default::default();
+    });
+}
+
 fn render_job(ui: &mut Ui, job: LayoutJob) -> Response {
+    record_job(ui, &job);
     let galley = ui.fonts(|f| f.layout_job(job));
     ui.add(Label::new(galley).sense(Sense::click_and_drag()))
 }
@@ -1,10 +1,48 @@
-use std::collections::HashSet;
+use std::{
+    collections::{HashMap, HashSet},
+    mem::size_of,
+};
 
-use egui::{collapsing_header::CollapsingState, Id, Ui};
+use egui::{
+    collapsing_header::CollapsingState, scroll_area::ScrollAreaOutput, text::LayoutJob, Area,
+    Context, Frame, Id, InnerResponse, Order, Rect, Ui, UiKind,
+};
+
+use crate::{
+    identity::PathIds,
+    pointer::parse_json_pointer_string,
+    render::{clear_layout_job_caches, num_cached_layout_jobs},
+    scroll::ScrollToPointer,
+    scroll_markers::show_scroll_markers,
+    search::{
+        clear_cached_search, current_match_id, focus_current_match_id, for_each_expandable_path_in,
+    },
+    search_history::clear_search_history,
+    value::ToJsonTreeValue,
+    JsonPathError, JsonTreeDropTarget, JsonTreeScrollMarker, JsonTreeSnapshot,
+};
+
+/// Approximate overhead in bytes of a single entry in egui's memory, in addition to the size of the stored value.
+const MEMORY_ENTRY_OVERHEAD: usize = size_of::<Id>() + 32;
 
 /// The response from showing a [`JsonTree`](crate::JsonTree).
 pub struct JsonTreeResponse {
     pub(crate) collapsing_state_ids: HashSet<Id>,
+    pub(crate) degraded: bool,
+    pub(crate) path_ids: PathIds,
+    /// JSON Pointer strings of the arrays/objects that were expanded (`true`) or collapsed (`false`) this frame.
+    pub(crate) toggled: Vec<(String, bool)>,
+    pub(crate) snapshot: Option<JsonTreeSnapshot>,
+    pub(crate) scroll_markers: Vec<JsonTreeScrollMarker>,
+    /// The screen rects of the rows that were visible this frame, by the id of their path.
+    pub(crate) row_rects: HashMap<Id, Rect>,
+    pub(crate) json_path_error: Option<JsonPathError>,
+    pub(crate) search_match_pointers: Vec<String>,
+    /// The index within `search_match_pointers` of the current match, if it is still a match.
+    pub(crate) current_match: Option<usize>,
+    /// The recent search terms of the tree, most recent first.
+    pub(crate) search_history: Vec<String>,
+    pub(crate) drop_target: Option<JsonTreeDropTarget>,
 }
 
 impl JsonTreeResponse {
@@ -20,4 +58,450 @@ impl JsonTreeResponse {
             }
         }
     }
+
+    /// Returns whether rendering was degraded this frame because the frame budget configured via
+    /// [`JsonTreeStyle::frame_budget`](crate::JsonTreeStyle::frame_budget) was exceeded,
+    /// e.g. so that the app can indicate that the tree is showing a simplified view.
+    pub fn is_degraded(&self) -> bool {
+        self.degraded
+    }
+
+    /// Returns the number of object entries and array elements that matched the search term this frame,
+    /// or that were selected by the expression of [`DefaultExpand::JsonPath`](crate::DefaultExpand::JsonPath),
+    /// e.g. to show "12 matches" next to a search box.
+    ///
+    /// An entry whose key and value both match counts once.
+    /// This is `0` if there is no search term, or if only highlighting via [`JsonTree::highlight_only`](crate::JsonTree::highlight_only).
+    pub fn search_match_count(&self) -> usize {
+        self.search_match_pointers.len()
+    }
+
+    /// Returns the JSON Pointer strings of the matches counted by [`JsonTreeResponse::search_match_count`].
+    /// The matches of a search term are in document order.
+    pub fn search_match_pointers(&self) -> &[String] {
+        &self.search_match_pointers
+    }
+
+    /// Returns the index within [`JsonTreeResponse::search_match_pointers`] of the current match,
+    /// i.e. the match last navigated to via [`JsonTreeResponse::scroll_to_match`] or its neighbours,
+    /// e.g. to show "3 of 12" next to a search box.
+    ///
+    /// Returns `None` if no match has been navigated to, or it no longer matches the search term.
+    pub fn current_match(&self) -> Option<usize> {
+        self.current_match
+    }
+
+    /// Makes the match at the given index within [`JsonTreeResponse::search_match_pointers`] the current match,
+    /// and scrolls it into view via [`JsonTreeResponse::scroll_to_pointer`].
+    /// With [`JsonTree::focus_follows_search`](crate::JsonTree::focus_follows_search), its key or array index also receives keyboard focus.
+    /// The index wraps around the number of matches. Has no effect if there are no matches.
+    pub fn scroll_to_match(&self, ctx: &Context, index: usize) {
+        if self.search_match_pointers.is_empty() {
+            return;
+        }
+        let pointer = &self.search_match_pointers[index % self.search_match_pointers.len()];
+        let tree_state_id = self.path_ids.tree_state_id();
+        ctx.data_mut(|d| {
+            d.insert_temp(current_match_id(tree_state_id), pointer.clone());
+            d.insert_temp(focus_current_match_id(tree_state_id), true);
+        });
+        self.scroll_to_pointer(ctx, pointer);
+    }
+
+    /// Steps to the match after the current match, or the first match if there is no current match,
+    /// wrapping around to the first match after the last. See [`JsonTreeResponse::scroll_to_match`].
+    /// ```rust
+    /// # use egui::{Key, ScrollArea};
+    /// # use egui_json_tree::{DefaultExpand, JsonTree};
+    /// # egui::__run_test_ui(|ui| {
+    /// # let value = serde_json::json!({ "name": "Alice", "friend": { "name": "Bob" } });
+    /// let response = ScrollArea::vertical()
+    ///     .show(ui, |ui| {
+    ///         JsonTree::new("match-navigation-tree", &value)
+    ///             .default_expand(DefaultExpand::SearchResults("name"))
+    ///             .show(ui)
+    ///     })
+    ///     .inner;
+    /// if ui.input(|i| i.key_pressed(Key::F3) && i.modifiers.shift) {
+    ///     response.scroll_to_previous_match(ui.ctx());
+    /// } else if ui.input(|i| i.key_pressed(Key::F3)) {
+    ///     response.scroll_to_next_match(ui.ctx());
+    /// }
+    /// # });
+    /// ```
+    pub fn scroll_to_next_match(&self, ctx: &Context) {
+        self.scroll_to_match(ctx, self.current_match.map_or(0, |current| current + 1));
+    }
+
+    /// Steps to the match before the current match, or the last match if there is no current match,
+    /// wrapping around to the last match before the first. See [`JsonTreeResponse::scroll_to_match`].
+    pub fn scroll_to_previous_match(&self, ctx: &Context) {
+        let len = self.search_match_pointers.len();
+        self.scroll_to_match(
+            ctx,
+            self.current_match
+                .map_or(len.saturating_sub(1), |current| current + len - 1),
+        );
+    }
+
+    /// Returns the recent search terms of the [`JsonTree`](crate::JsonTree) that provided this response, most recent first,
+    /// e.g. to offer a dropdown of past searches next to a search box.
+    ///
+    /// A search term is remembered when the tree is shown with it, up to the 10 most recent.
+    /// A search term that extends or shortens the most recent one replaces it, so that only the final search term is remembered while typing.
+    /// Searches for several search terms, e.g. [`DefaultExpand::SearchResultsAll`](crate::DefaultExpand::SearchResultsAll), are not remembered.
+    /// ```rust
+    /// # use egui_json_tree::{DefaultExpand, JsonTree};
+    /// # egui::__run_test_ui(|ui| {
+    /// # let value = serde_json::json!({ "level": "error" });
+    /// # let mut search_input = String::new();
+    /// ui.text_edit_singleline(&mut search_input);
+    /// let response = JsonTree::new("history-tree", &value)
+    ///     .default_expand(DefaultExpand::SearchResults(&search_input))
+    ///     .show(ui);
+    /// egui::ComboBox::from_label("Recent searches")
+    ///     .selected_text(search_input.clone())
+    ///     .show_ui(ui, |ui| {
+    ///         for search_term in response.search_history() {
+    ///             ui.selectable_value(&mut search_input, search_term.clone(), search_term);
+    ///         }
+    ///     });
+    /// # });
+    /// ```
+    pub fn search_history(&self) -> &[String] {
+        &self.search_history
+    }
+
+    /// Forgets the recent search terms of the [`JsonTree`](crate::JsonTree) that provided this response.
+    /// See [`JsonTreeResponse::search_history`].
+    pub fn clear_search_history(&self, ctx: &Context) {
+        clear_search_history(ctx, self.path_ids.tree_state_id());
+    }
+
+    /// Returns the error from parsing the expression of [`DefaultExpand::JsonPath`](crate::DefaultExpand::JsonPath),
+    /// if it is invalid, e.g. to show it alongside the input of the expression.
+    pub fn json_path_error(&self) -> Option<&JsonPathError> {
+        self.json_path_error.as_ref()
+    }
+
+    /// Returns a structured model of what was rendered this frame,
+    /// if enabled via [`JsonTree::record_snapshot`](crate::JsonTree::record_snapshot).
+    pub fn snapshot(&self) -> Option<&JsonTreeSnapshot> {
+        self.snapshot.as_ref()
+    }
+
+    /// Returns an estimate of the memory retained in egui memory by the [`JsonTree`](crate::JsonTree) that provided this response.
+    ///
+    /// The expanded state is only counted for arrays/objects that were visited this frame,
+    /// i.e. those that are visible, or all of them when searching.
+    /// The cached text layouts are shared by all [`JsonTree`](crate::JsonTree)s in the same [`Context`].
+    pub fn memory_usage(&self, ctx: &Context) -> JsonTreeMemoryUsage {
+        let num_expanded_states = self
+            .collapsing_state_ids
+            .iter()
+            .filter(|id| CollapsingState::load(ctx, **id).is_some())
+            .count();
+        let num_cached_layouts = num_cached_layout_jobs(ctx);
+
+        JsonTreeMemoryUsage {
+            num_expanded_states,
+            expanded_states_bytes: num_expanded_states
+                * (size_of::<CollapsingState>() + MEMORY_ENTRY_OVERHEAD),
+            num_cached_layouts,
+            cached_layouts_bytes: num_cached_layouts
+                * (size_of::<LayoutJob>() + MEMORY_ENTRY_OVERHEAD),
+        }
+    }
+
+    /// Frees memory that can be recomputed, i.e. the cached text layouts shared by all [`JsonTree`](crate::JsonTree)s in the same [`Context`],
+    /// and the search matches of this [`JsonTree`](crate::JsonTree) cached via [`JsonTree::value_version`](crate::JsonTree::value_version).
+    ///
+    /// To also free the expanded state of this [`JsonTree`](crate::JsonTree), see [`JsonTreeResponse::reset_expanded`].
+    pub fn trim_caches(&self, ctx: &Context) {
+        clear_layout_job_caches(ctx);
+        clear_cached_search(ctx, self.path_ids.tree_state_id());
+    }
+
+    /// Scrolls the value at the given JSON Pointer string into view when the tree is next shown,
+    /// expanding its ancestor arrays/objects if necessary.
+    ///
+    /// How the view moves, and whether the row of the value is briefly pulsed afterwards,
+    /// is configured via [`JsonTreeStyle::scroll_animation`](crate::JsonTreeStyle::scroll_animation).
+    /// The tree should be shown within an [`egui::ScrollArea`].
+    ///
+    /// Has no effect if the string is not a valid JSON Pointer, or the value is not shown shortly afterwards.
+    pub fn scroll_to_pointer(&self, ctx: &Context, pointer: &str) {
+        let Some(segments) = parse_json_pointer_string(pointer) else {
+            return;
+        };
+        for ancestor_len in 0..segments.len() {
+            set_open(ctx, self.path_id(&segments[..ancestor_len]), true);
+        }
+        ScrollToPointer::request(ctx, self.path_ids.tree_state_id(), self.path_id(&segments));
+        ctx.request_repaint();
+    }
+
+    /// Expands the array/object at the given JSON Pointer string and all of its ancestors when the tree is next shown,
+    /// e.g. to reveal a value in the tree when it is selected elsewhere in your app.
+    /// If the pointer is to a non-recursive value, its ancestors are expanded to reveal it.
+    ///
+    /// To also scroll the value into view, see [`JsonTreeResponse::scroll_to_pointer`].
+    /// Has no effect if the string is not a valid JSON Pointer.
+    pub fn expand_pointer(&self, ctx: &Context, pointer: &str) {
+        let Some(segments) = parse_json_pointer_string(pointer) else {
+            return;
+        };
+        for len in 0..=segments.len() {
+            set_open(ctx, self.path_id(&segments[..len]), true);
+        }
+        ctx.request_repaint();
+    }
+
+    /// Collapses the array/object at the given JSON Pointer string when the tree is next shown,
+    /// leaving the expanded state of its ancestors and descendants as it is.
+    ///
+    /// Has no effect if the string is not a valid JSON Pointer.
+    pub fn collapse_pointer(&self, ctx: &Context, pointer: &str) {
+        self.set_expanded(ctx, pointer, false);
+        ctx.request_repaint();
+    }
+
+    /// Copies the expanded state of every array/object within `value`, i.e. the value shown by this tree,
+    /// to the arrays/objects at the same JSON pointers in another tree, when that tree is next shown.
+    ///
+    /// Arrays/objects whose expanded state has not been stored in this tree, e.g. because they have never been visible,
+    /// are reset to respect the `default_expand` setting of the other tree.
+    /// This is useful for keeping two trees expanded identically, e.g. the left and right panes of a diff view.
+    /// To keep them in sync as either is toggled, see [`JsonTreeResponse::mirror_toggled_to`].
+    pub fn copy_expanded_to<T: ToJsonTreeValue>(
+        &self,
+        ctx: &Context,
+        value: &T,
+        other: &JsonTreeResponse,
+    ) {
+        for_each_expandable_path_in(value, &mut |path_segments| {
+            let id = self.path_ids.id(path_segments);
+            let other_id = other.path_ids.id(path_segments);
+            match CollapsingState::load(ctx, id) {
+                Some(state) => set_open(ctx, other_id, state.is_open()),
+                None => {
+                    if let Some(other_state) = CollapsingState::load(ctx, other_id) {
+                        other_state.remove(ctx);
+                    }
+                }
+            }
+        });
+        ctx.request_repaint();
+    }
+
+    /// Expands/collapses the arrays/objects at the same JSON pointers in another tree as were
+    /// expanded/collapsed by the user in this tree this frame, when that tree is next shown.
+    ///
+    /// Call this every frame in both directions to mirror the expanded state of two trees live,
+    /// e.g. the left and right panes of a diff view.
+    /// Changes applied to a tree by this method are not themselves mirrored back.
+    /// ```rust
+    /// # use egui_json_tree::JsonTree;
+    /// # egui::__run_test_ui(|ui| {
+    /// # let old = serde_json::json!({ "id": 1 });
+    /// # let new = serde_json::json!({ "id": 2 });
+    /// let (left, right) = ui
+    ///     .columns(2, |columns| {
+    ///         let left = JsonTree::new("left", &old).show(&mut columns[0]);
+    ///         let right = JsonTree::new("right", &new).show(&mut columns[1]);
+    ///         (left, right)
+    ///     });
+    /// left.mirror_toggled_to(ui.ctx(), &right);
+    /// right.mirror_toggled_to(ui.ctx(), &left);
+    /// # });
+    /// ```
+    pub fn mirror_toggled_to(&self, ctx: &Context, other: &JsonTreeResponse) {
+        for (pointer, is_open) in &self.toggled {
+            other.set_expanded(ctx, pointer, *is_open);
+        }
+        if !self.toggled.is_empty() {
+            ctx.request_repaint();
+        }
+    }
+
+    /// Returns the stored expanded state of every array/object within `value`, i.e. the value shown by this tree,
+    /// e.g. to save it with your app state and restore it on startup via
+    /// [`JsonTree::restore_expansion_state`](crate::JsonTree::restore_expansion_state).
+    ///
+    /// Arrays/objects whose expanded state has not been stored, e.g. because they have never been visible, are omitted.
+    /// Requires the `persistence` feature.
+    #[cfg(feature = "persistence")]
+    pub fn expansion_state<T: ToJsonTreeValue>(
+        &self,
+        ctx: &Context,
+        value: &T,
+    ) -> crate::JsonTreeExpansionState {
+        crate::JsonTreeExpansionState::capture(ctx, &self.path_ids, value)
+    }
+
+    /// Returns the gap between entries targeted by a drag hovering the tree this frame, if the tree accepts its payload
+    /// via [`JsonTree::drop_targets`](crate::JsonTree::drop_targets).
+    pub fn drop_target(&self) -> Option<&JsonTreeDropTarget> {
+        self.drop_target.as_ref()
+    }
+
+    /// Returns the markers for the scroll bar track configured via [`JsonTree::scroll_markers`](crate::JsonTree::scroll_markers)
+    /// and [`JsonTree::search_match_scroll_markers`](crate::JsonTree::search_match_scroll_markers),
+    /// positioned at the rows that were shown this frame, e.g. to paint them in a custom way.
+    pub fn scroll_markers(&self) -> &[JsonTreeScrollMarker] {
+        &self.scroll_markers
+    }
+
+    /// Paints the [`JsonTreeResponse::scroll_markers`] on the vertical scroll bar track of the [`egui::ScrollArea`]
+    /// that the tree was shown in. Clicking a marker scrolls its value into view via [`JsonTreeResponse::scroll_to_pointer`].
+    ///
+    /// Returns the JSON Pointer string of the marker that was clicked, if any.
+    /// ```rust
+    /// # use egui::{Color32, ScrollArea};
+    /// # use egui_json_tree::JsonTree;
+    /// # egui::__run_test_ui(|ui| {
+    /// # let value = serde_json::json!({ "items": [1, "two", 3] });
+    /// let output = ScrollArea::vertical().show(ui, |ui| {
+    ///     JsonTree::new("scroll-markers-tree", &value)
+    ///         .scroll_markers([("/items/1", Color32::RED)])
+    ///         .show(ui)
+    /// });
+    /// output.inner.show_scroll_markers(ui, &output);
+    /// # });
+    /// ```
+    pub fn show_scroll_markers<R>(
+        &self,
+        ui: &Ui,
+        scroll_area_output: &ScrollAreaOutput<R>,
+    ) -> Option<&str> {
+        let id = self.path_ids.tree_state_id().with("scroll_markers");
+        let clicked = show_scroll_markers(ui, id, scroll_area_output, &self.scroll_markers)?;
+        self.scroll_to_pointer(ui.ctx(), &clicked.pointer);
+        Some(&clicked.pointer)
+    }
+
+    /// Returns the screen rect of the row of the value at the given JSON Pointer string,
+    /// if it was shown this frame and is at least partially visible,
+    /// e.g. to draw custom overlays or highlights anchored to specific values.
+    ///
+    /// Returns `None` if the value is hidden within a collapsed array/object, scrolled out of view,
+    /// or the string is not a valid JSON Pointer.
+    /// ```rust
+    /// # use egui::{Color32, Stroke};
+    /// # use egui_json_tree::JsonTree;
+    /// # egui::__run_test_ui(|ui| {
+    /// # let value = serde_json::json!({ "name": "Alice" });
+    /// let response = JsonTree::new("rect-of-tree", &value).show(ui);
+    /// if let Some(rect) = response.rect_of("/name") {
+    ///     ui.painter().rect_stroke(rect, 2.0, Stroke::new(1.0, Color32::RED));
+    /// }
+    /// # });
+    /// ```
+    pub fn rect_of(&self, pointer: &str) -> Option<Rect> {
+        let id = self.collapsing_state_id(pointer)?;
+        self.row_rects.get(&id).copied()
+    }
+
+    /// Shows a popup anchored below the row of the value at the given JSON Pointer string,
+    /// e.g. to explain a field in response to app logic rather than a user interaction.
+    /// Call this every frame for as long as the popup should be open.
+    ///
+    /// If the row is not visible when the popup is opened, it is scrolled into view via [`JsonTreeResponse::scroll_to_pointer`],
+    /// expanding its ancestor arrays/objects, and the popup is shown once the row is visible.
+    /// Returns `None` while the row is not visible.
+    /// ```rust
+    /// # use egui_json_tree::JsonTree;
+    /// # egui::__run_test_ui(|ui| {
+    /// # let value = serde_json::json!({ "retries": 3 });
+    /// # let explain_retries = true;
+    /// let response = JsonTree::new("popup-tree", &value).show(ui);
+    /// if explain_retries {
+    ///     response.show_popup_at_pointer(ui, "/retries", |ui| {
+    ///         ui.label("How many times a failed request is retried.");
+    ///     });
+    /// }
+    /// # });
+    /// ```
+    pub fn show_popup_at_pointer<R>(
+        &self,
+        ui: &Ui,
+        pointer: &str,
+        add_contents: impl FnOnce(&mut Ui) -> R,
+    ) -> Option<InnerResponse<R>> {
+        let ctx = ui.ctx();
+        let popup_id = self.path_ids.tree_state_id().with("pointer_popup");
+        let pass_nr = ctx.cumulative_pass_nr();
+        let was_open = ctx.data_mut(|d| {
+            let previous = d.get_temp::<(String, u64)>(popup_id);
+            d.insert_temp(popup_id, (pointer.to_string(), pass_nr));
+            previous.is_some_and(|(previous_pointer, previous_pass_nr)| {
+                previous_pointer == pointer && previous_pass_nr + 1 >= pass_nr
+            })
+        });
+
+        let Some(rect) = self.rect_of(pointer) else {
+            // Only scroll when the popup is opened, so that the user may scroll away from it afterwards.
+            if !was_open {
+                self.scroll_to_pointer(ctx, pointer);
+            }
+            return None;
+        };
+
+        let area = Area::new(popup_id.with(pointer))
+            .kind(UiKind::Popup)
+            .order(Order::Foreground)
+            .fixed_pos(rect.left_bottom())
+            .constrain(true)
+            .show(ctx, |ui| {
+                Frame::popup(ui.style())
+                    .show(ui, |ui| add_contents(ui))
+                    .inner
+            });
+        Some(area)
+    }
+
+    /// Returns the [`Id`] of the expanded state of the array/object at the given JSON Pointer string,
+    /// or `None` if the string is not a valid JSON Pointer.
+    pub(crate) fn collapsing_state_id(&self, pointer: &str) -> Option<Id> {
+        parse_json_pointer_string(pointer).map(|segments| self.path_id(&segments))
+    }
+
+    /// Expands (`true`) or collapses (`false`) the array/object at the given JSON Pointer string
+    /// when the tree is next shown.
+    pub(crate) fn set_expanded(&self, ctx: &Context, pointer: &str, open: bool) {
+        if let Some(id) = self.collapsing_state_id(pointer) {
+            set_open(ctx, id, open);
+        }
+    }
+
+    fn path_id(&self, segments: &[String]) -> Id {
+        self.path_ids.id_of_segments(segments)
+    }
+}
+
+fn set_open(ctx: &Context, id: Id, open: bool) {
+    let mut state = CollapsingState::load_with_default_open(ctx, id, !open);
+    state.set_open(open);
+    state.store(ctx);
+}
+
+/// An estimate of the memory retained in egui memory by a [`JsonTree`](crate::JsonTree).
+/// See [`JsonTreeResponse::memory_usage`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JsonTreeMemoryUsage {
+    /// The number of arrays/objects with a stored expanded state.
+    pub num_expanded_states: usize,
+    /// The approximate size in bytes of the stored expanded states.
+    pub expanded_states_bytes: usize,
+    /// The number of cached text layouts.
+    pub num_cached_layouts: usize,
+    /// The approximate size in bytes of the cached text layouts, excluding the text itself.
+    pub cached_layouts_bytes: usize,
+}
+
+impl JsonTreeMemoryUsage {
+    /// The approximate total size in bytes.
+    pub fn total_bytes(&self) -> usize {
+        self.expanded_states_bytes + self.cached_layouts_bytes
+    }
 }
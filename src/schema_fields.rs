@@ -0,0 +1,31 @@
+use crate::pointer::JsonPointer;
+
+/// A closure that returns the fields defined for the object at the JSON pointer, e.g. by a JSON Schema.
+pub(crate) type SchemaFields<'a> = dyn Fn(JsonPointer) -> Vec<JsonTreeSchemaField> + 'a;
+
+/// A field that an object may have, shown as a placeholder row if it is missing.
+/// See [`JsonTree::schema_fields`](crate::JsonTree::schema_fields).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JsonTreeSchemaField {
+    /// The key of the field.
+    pub key: String,
+    /// The default value of the field, if any, in the same format as the text passed to
+    /// [`JsonTree::on_value_edit`](crate::JsonTree::on_value_edit), e.g. `8080` or `localhost`.
+    pub default: Option<String>,
+}
+
+impl JsonTreeSchemaField {
+    /// A field with the given key and no default value.
+    pub fn new(key: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            default: None,
+        }
+    }
+
+    /// Sets the default value of the field.
+    pub fn with_default(mut self, default: impl Into<String>) -> Self {
+        self.default = Some(default.into());
+        self
+    }
+}
@@ -0,0 +1,117 @@
+use std::f32::consts::PI;
+
+use egui::{style::ScrollAnimation, vec2, Align, Color32, Context, Id, InnerResponse, Shape, Ui};
+
+use crate::JsonTreeScrollAnimation;
+
+/// How long to wait for the row of a requested pointer to be shown before giving up, e.g. because the pointer does not exist.
+const FIND_TARGET_TIMEOUT_SECS: f64 = 1.0;
+
+/// An in-progress request to scroll a row of the tree into view,
+/// made via [`JsonTreeResponse::scroll_to_pointer`](crate::JsonTreeResponse::scroll_to_pointer).
+#[derive(Debug, Clone)]
+pub(crate) struct ScrollToPointer {
+    target_id: Id,
+    requested_at: f64,
+    /// The time at which the row was first shown, and its vertical distance from the centre of the visible area at that time.
+    started: Option<(f64, f32)>,
+}
+
+impl ScrollToPointer {
+    fn id(tree_state_id: Id) -> Id {
+        tree_state_id.with("scroll_to_pointer")
+    }
+
+    /// Requests that the row with the given [`Id`] is scrolled into view when the tree is next shown,
+    /// replacing any previous request.
+    pub(crate) fn request(ctx: &Context, tree_state_id: Id, target_id: Id) {
+        let request = Self {
+            target_id,
+            requested_at: ctx.input(|i| i.time),
+            started: None,
+        };
+        ctx.data_mut(|d| d.insert_temp(Self::id(tree_state_id), request));
+    }
+
+    /// Loads the current request, discarding it if its row was never shown.
+    pub(crate) fn load(ui: &Ui, tree_state_id: Id) -> Option<Self> {
+        let id = Self::id(tree_state_id);
+        let request = ui.data(|d| d.get_temp::<Self>(id))?;
+        if request.started.is_none()
+            && ui.input(|i| i.time) - request.requested_at > FIND_TARGET_TIMEOUT_SECS
+        {
+            ui.data_mut(|d| d.remove::<Self>(id));
+            return None;
+        }
+        Some(request)
+    }
+
+    pub(crate) fn is_target(&self, row_id: Id) -> bool {
+        self.target_id == row_id
+    }
+
+    /// Shows the target row, scrolling it towards the centre of the visible area and pulsing its background.
+    pub(crate) fn show_row<R>(
+        &self,
+        ui: &mut Ui,
+        tree_state_id: Id,
+        animation: &JsonTreeScrollAnimation,
+        pulse_color: Color32,
+        add_row: impl FnOnce(&mut Ui) -> InnerResponse<R>,
+    ) -> InnerResponse<R> {
+        let background = ui.painter().add(Shape::Noop);
+        let row_res = add_row(ui);
+        let rect = row_res.response.rect;
+
+        let now = ui.input(|i| i.time);
+        let distance = rect.center().y - ui.clip_rect().center().y;
+        let (started_at, start_distance) = self.started.unwrap_or((now, distance));
+
+        let scroll_secs = animation.duration.as_secs_f32();
+        let elapsed = (now - started_at) as f32;
+        let progress = if scroll_secs > 0.0 {
+            (elapsed / scroll_secs).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        let target_distance = start_distance * (1.0 - (animation.easing)(progress));
+        // Centre a rect offset from the row, so that the row ends up `target_distance` from the centre.
+        ui.scroll_to_rect_animation(
+            rect.translate(vec2(0.0, -target_distance)),
+            Some(Align::Center),
+            ScrollAnimation::none(),
+        );
+
+        let pulse_secs = animation.pulse_duration.as_secs_f32();
+        let pulse_progress = if pulse_secs > 0.0 {
+            (elapsed - scroll_secs) / pulse_secs
+        } else {
+            1.0
+        };
+        if (0.0..1.0).contains(&pulse_progress) {
+            let alpha = (PI * pulse_progress).sin();
+            ui.painter().set(
+                background,
+                Shape::rect_filled(
+                    rect,
+                    ui.visuals().widgets.noninteractive.rounding,
+                    pulse_color.gamma_multiply(alpha),
+                ),
+            );
+        }
+
+        let id = Self::id(tree_state_id);
+        if progress < 1.0 || pulse_progress < 1.0 {
+            let request = Self {
+                started: Some((started_at, start_distance)),
+                ..self.clone()
+            };
+            ui.data_mut(|d| d.insert_temp(id, request));
+            ui.ctx().request_repaint();
+        } else {
+            ui.data_mut(|d| d.remove::<Self>(id));
+        }
+
+        row_res
+    }
+}
@@ -0,0 +1,159 @@
+use std::{cell::RefCell, collections::HashMap};
+
+use egui::{scroll_area::ScrollAreaOutput, vec2, Color32, CursorIcon, Id, Rangef, Rect, Sense, Ui};
+
+use crate::{
+    pointer::{parse_json_pointer_string, JsonPointerSegment},
+    search::SearchMatches,
+};
+
+/// The minimum height of a marker on the scroll bar track, so that markers for single rows of long documents remain visible.
+const MIN_MARKER_HEIGHT: f32 = 2.0;
+
+/// A marker on the scroll bar track indicating where a value of interest is located in the tree,
+/// like the overview ruler of a code editor.
+/// See [`JsonTree::scroll_markers`](crate::JsonTree::scroll_markers) and [`JsonTreeResponse::show_scroll_markers`](crate::JsonTreeResponse::show_scroll_markers).
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonTreeScrollMarker {
+    /// The JSON Pointer string of the marked value, which is scrolled into view when the marker is clicked.
+    pub pointer: String,
+    pub color: Color32,
+    /// The vertical extent in screen coordinates of the row that was shown for the marked value this frame.
+    /// If the value is hidden within a collapsed array/object, this is the row of that array/object.
+    pub y_range: Rangef,
+}
+
+/// Collects the positions of marked rows while the tree is shown.
+pub(crate) struct ScrollMarkerRecorder {
+    search_match_color: Option<Color32>,
+    /// The pointer and color of each marked value, by the id of its path.
+    targets: HashMap<Id, (String, Color32)>,
+    /// The pointer and color of the first marked value within each array/object, by the id of its path.
+    containing: HashMap<Id, (String, Color32)>,
+    markers: RefCell<Vec<JsonTreeScrollMarker>>,
+}
+
+impl ScrollMarkerRecorder {
+    pub(crate) fn new(
+        markers: &[(String, Color32)],
+        search_match_color: Option<Color32>,
+        make_persistent_id: &dyn Fn(&[JsonPointerSegment]) -> Id,
+    ) -> Self {
+        let mut targets = HashMap::new();
+        let mut containing = HashMap::new();
+
+        for (pointer, color) in markers {
+            let Some(segments) = parse_json_pointer_string(pointer) else {
+                continue;
+            };
+            let path_segments = segments
+                .iter()
+                .map(|segment| JsonPointerSegment::Key(segment))
+                .collect::<Vec<_>>();
+            for ancestor_len in 0..path_segments.len() {
+                containing
+                    .entry(make_persistent_id(&path_segments[..ancestor_len]))
+                    .or_insert_with(|| (pointer.clone(), *color));
+            }
+            targets
+                .entry(make_persistent_id(&path_segments))
+                .or_insert_with(|| (pointer.clone(), *color));
+        }
+
+        Self {
+            search_match_color,
+            targets,
+            containing,
+            markers: RefCell::new(vec![]),
+        }
+    }
+
+    /// Records markers for a row that was shown, if its value is marked or it is a collapsed array/object containing a marked value.
+    pub(crate) fn record_row(
+        &self,
+        path_id: Id,
+        pointer: impl FnOnce() -> String,
+        rect: Rect,
+        is_expanded: bool,
+        search_matches: Option<&SearchMatches>,
+    ) {
+        let mut markers = self.markers.borrow_mut();
+
+        if let (Some(color), Some(search_matches)) = (self.search_match_color, search_matches) {
+            if search_matches.matched_ids.contains(&path_id)
+                || (!is_expanded && search_matches.match_counts.contains_key(&path_id))
+            {
+                markers.push(JsonTreeScrollMarker {
+                    pointer: pointer(),
+                    color,
+                    y_range: rect.y_range(),
+                });
+            }
+        }
+
+        // Recorded after any search match marker of the same row, so that it is painted on top and takes precedence when clicked.
+        let marked = self.targets.get(&path_id).or_else(|| {
+            (!is_expanded)
+                .then(|| self.containing.get(&path_id))
+                .flatten()
+        });
+        if let Some((pointer, color)) = marked {
+            markers.push(JsonTreeScrollMarker {
+                pointer: pointer.clone(),
+                color: *color,
+                y_range: rect.y_range(),
+            });
+        }
+    }
+
+    pub(crate) fn finish(self) -> Vec<JsonTreeScrollMarker> {
+        self.markers.into_inner()
+    }
+}
+
+/// Paints the markers on the vertical scroll bar track of the scroll area, returning the marker that was clicked, if any.
+pub(crate) fn show_scroll_markers<'m, R>(
+    ui: &Ui,
+    id: Id,
+    scroll_area_output: &ScrollAreaOutput<R>,
+    markers: &'m [JsonTreeScrollMarker],
+) -> Option<&'m JsonTreeScrollMarker> {
+    let inner_rect = scroll_area_output.inner_rect;
+    let content_height = scroll_area_output.content_size.y.max(inner_rect.height());
+    if markers.is_empty() || content_height <= 0.0 {
+        return None;
+    }
+    let content_top = inner_rect.top() - scroll_area_output.state.offset.y;
+    let scale = inner_rect.height() / content_height;
+
+    let scroll_style = ui.spacing().scroll;
+    let track_x_range = if scroll_style.floating {
+        let right = inner_rect.right() - scroll_style.bar_outer_margin;
+        Rangef::new(right - scroll_style.bar_width, right)
+    } else {
+        let left = inner_rect.right() + scroll_style.bar_inner_margin;
+        Rangef::new(left, left + scroll_style.bar_width)
+    };
+
+    let mut clicked = None;
+    for (idx, marker) in markers.iter().enumerate() {
+        let top = inner_rect.top() + (marker.y_range.min - content_top) * scale;
+        let height = (marker.y_range.span() * scale).max(MIN_MARKER_HEIGHT);
+        let rect = Rect::from_x_y_ranges(track_x_range, Rangef::new(top, top + height));
+
+        ui.painter().rect_filled(rect, 0.0, marker.color);
+
+        let response = ui
+            .interact(
+                rect.expand2(vec2(0.0, MIN_MARKER_HEIGHT)),
+                id.with(idx),
+                Sense::click(),
+            )
+            .on_hover_cursor(CursorIcon::PointingHand)
+            .on_hover_text(&marker.pointer);
+        if response.clicked() {
+            clicked = Some(marker);
+        }
+    }
+    clicked
+}
@@ -1,82 +1,1000 @@
-use std::collections::HashSet;
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    ops::Range,
+    sync::Arc,
+};
 
-use egui::Id;
+use egui::{Context, Id};
 
 use crate::{
-    pointer::JsonPointerSegment,
-    value::{ExpandableType, JsonTreeValue, ToJsonTreeValue},
+    fuzzy::fuzzy_match,
+    identity::{segment_matches, PathIds},
+    pointer::{parse_json_pointer_string, JsonPointer, JsonPointerSegment},
+    value::{
+        disambiguate_duplicate_keys, BaseValueType, ExpandableType, JsonTreeValue, ToJsonTreeValue,
+    },
 };
+/// Options for how a search term is matched against the keys and values of a [`JsonTree`](crate::JsonTree), and how its matches are expanded.
+/// Options for how a search term is matched against the keys and values of a [`JsonTree`](crate::JsonTree).
+/// See [`DefaultExpand::SearchResultsWith`](crate::DefaultExpand::SearchResultsWith).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SearchOptions {
+    /// If `true`, only text with the same letter case as the search term matches, e.g. `ID` does not match `id`.
+    ///
+    /// Otherwise, letter case is ignored using Unicode case folding, e.g. `STRASSE` matches `Straße`,
+    /// and `ПРИВЕТ` matches `привет`. Defaults to `false`.
+    pub case_sensitive: bool,
+    /// If `true`, text matches if it contains the characters of the search term in order, but not necessarily next to each other,
+    /// e.g. `usrnm` matches `username`. Matches whose characters are too far apart are ignored.
+    ///
+    /// Otherwise, text matches only if it contains the search term exactly. Defaults to `false`.
+    pub fuzzy: bool,
+    /// If `true`, text matches only where the search term is a whole word, i.e. is not directly preceded or followed by
+    /// a letter, digit or `_`, e.g. `id` matches `user id` and `id-1`, but not `valid`, `uuid` or `user_id`.
+    /// Ignored if [`SearchOptions::fuzzy`] is `true`. Defaults to `false`.
+    pub whole_word: bool,
+    /// Whether the search term is matched against object keys, non-recursive values, or both.
+    /// Defaults to [`SearchScope::KeysAndValues`].
+    pub scope: SearchScope,
+    /// If set, only the first this many characters of a non-recursive value are matched and highlighted,
+    /// so that a very long string, e.g. an embedded base64 blob, does not dominate the time taken to search.
+    /// Object keys are matched in full. Defaults to `None`.
+    pub max_value_chars: Option<usize>,
+    /// Whether the whitespace-separated words of the search term are matched as a whole, or separately.
+    /// Defaults to [`SearchWords::Phrase`].
+    pub words: SearchWords,
+    /// If `true`, an array/object whose object key matches the search term is expanded too, not just the arrays/objects containing it,
+    /// e.g. to reveal the entries of `"config": {...}` when searching for `config`. Defaults to `false`.
+    pub expand_match_children: bool,
+    /// If `true`, a search term of the form `type:null`, `type:bool`, `type:number` or `type:string` matches
+    /// every non-recursive value of that type in full instead, e.g. to find stray `null`s. Object keys are not matched.
+    ///
+    /// Words of the search term prefixed with `-` then also exclude any object key or non-recursive value that contains them,
+    /// e.g. `error -deprecation` matches `"error: timeout"` but not `"error: deprecation warning"`.
+    /// Everything beneath an excluded object key is excluded too, e.g. to suppress a noisy `"debug": {...}` section.
+    /// A word of a `-` followed by a digit, e.g. `-1`, is matched as text, as is a search term consisting only of excluding words.
+    ///
+    /// Otherwise, the search term is always matched as text, e.g. `type:string` matches the text `type:string`. Defaults to `false`.
+    pub query_syntax: bool,
+}
+
+/// What a search term is matched against. See [`SearchOptions::scope`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SearchScope {
+    /// Match both object keys and non-recursive values.
+    #[default]
+    KeysAndValues,
+    /// Match object keys only, e.g. to find a field by name without matches in unrelated string values.
+    Keys,
+    /// Match non-recursive values only.
+    Values,
+}
+
+/// How the whitespace-separated words of a search term are matched. See [`SearchOptions::words`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum SearchWords {
+    /// Match the search term as a whole, e.g. `error timeout` matches `error timeout`, but not `timeout error`.
+    #[default]
+    Phrase,
+    /// Match each word separately, so that an object key or non-recursive value matches only if it contains every word,
+    /// in any order, e.g. `error timeout` matches `timeout error`.
+    AllInKeyOrValue,
+    /// Match each word separately, so that any object key or non-recursive value that contains a word matches,
+    /// but only within the deepest object entries and array elements that contain every word in their keys, values or descendants,
+    /// e.g. `error timeout` matches both values of `[{"level": "error", "reason": "timeout"}, {"level": "error"}]`,
+    /// but not the value of the second element.
+    AllInSubtree,
+}
+
+/// One or more search terms, combined so that text matches if it matches all or any of them.
+#[derive(Debug, Clone, Hash)]
+pub struct SearchTerm {
+    patterns: Vec<SearchPattern>,
+    /// Whether text matches only if it matches every pattern, rather than any of them.
+    match_all: bool,
+    /// Whether an object entry or array element matches only within the deepest object entries and array elements
+    /// that contain matches of every pattern. See [`SearchWords::AllInSubtree`].
+    match_all_in_subtree: bool,
+}
 
+/// A single search term, matched according to its options.
 #[derive(Debug, Clone, Hash)]
-pub struct SearchTerm(String);
+struct SearchPattern {
+    term: String,
+    options: SearchOptions,
+    /// The type of values to match, for a search term such as `type:null`, which matches every such value in full.
+    value_type: Option<BaseValueType>,
+    /// Whether text that matches the search term is excluded from matching, for a word such as `-debug`.
+    negated: bool,
+}
+
+/// An object key or non-recursive value, matched by the predicate of [`DefaultExpand::SearchWith`](crate::DefaultExpand::SearchWith).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchCandidate<'v> {
+    /// An object key. Array indices are not matched.
+    Key(&'v str),
+    /// A non-recursive value, as displayed without quotes, e.g. `hello` for the string `"hello"`, and its type.
+    Value(&'v str, BaseValueType),
+}
+
+/// The text that a [`SearchTerm`] is matched against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SearchTarget {
+    /// An object key.
+    Key,
+    /// A non-recursive value of this type.
+    Value(BaseValueType),
+}
+
+/// The prefix of a search term that matches values by type, e.g. `type:null`.
+const TYPE_PREFIX: &str = "type:";
+
+/// The prefix of a word in a search term that excludes text containing the rest of the word, e.g. `-debug`.
+const EXCLUSION_PREFIX: char = '-';
+
+/// The results of searching a JSON document for a [`SearchTerm`].
+#[derive(Debug, Default)]
+pub(crate) struct SearchMatches {
+    /// The ids of the arrays/objects that contain matches, which should be expanded to reveal them,
+    /// mapped to the number of matches they contain.
+    pub(crate) match_counts: HashMap<Id, usize>,
+    /// The ids of the matched object entries and array elements.
+    pub(crate) matched_ids: HashSet<Id>,
+    /// The JSON Pointer strings of the matched object entries and array elements, in document order.
+    pub(crate) matched_pointers: Vec<String>,
+}
+
+impl SearchMatches {
+    /// Returns whether the node with this id is a match, or contains a match.
+    pub(crate) fn contains(&self, id: &Id) -> bool {
+        self.matched_ids.contains(id) || self.match_counts.contains_key(id)
+    }
+
+    /// Returns the matches at the given JSON Pointer strings, e.g. found by a [`JsonTreeBackgroundSearch`](crate::JsonTreeBackgroundSearch).
+    /// The ids of the arrays/objects containing the matches are added to `reset_path_ids`.
+    pub(crate) fn from_pointers(
+        pointers: &[String],
+        path_ids: &PathIds,
+        abbreviate_root: bool,
+        reset_path_ids: &mut HashSet<Id>,
+    ) -> Self {
+        let mut search_matches = Self::default();
+        for pointer in pointers {
+            let Some(segments) = parse_json_pointer_string(pointer) else {
+                continue;
+            };
+            if !search_matches
+                .matched_ids
+                .insert(path_ids.id_of_segments(&segments))
+            {
+                continue;
+            }
+            search_matches.matched_pointers.push(pointer.clone());
+            for i in 0..segments.len() {
+                let id = path_ids.id_of_segments(&segments[..i]);
+                reset_path_ids.insert(id);
+                *search_matches.match_counts.entry(id).or_default() += 1;
+            }
+        }
+
+        if !abbreviate_root && search_matches.match_counts.len() == 1 {
+            // The only match was a top level key or value - no need to expand anything.
+            search_matches.match_counts.clear();
+        }
+
+        search_matches
+    }
+
+    /// Returns the matches of the search identified by `search_key`, finding them only if they were not already found
+    /// for the same search and version of the value. The ids of the arrays/objects that were searched are added to `reset_path_ids`.
+    /// If no version is given, the matches are found every frame.
+    ///
+    /// If the matches of the same search were found for an earlier version of the value,
+    /// `find` is given that version and those matches, e.g. to update them incrementally.
+    pub(crate) fn load_or_find(
+        ctx: &Context,
+        tree_state_id: Id,
+        value_version: Option<u64>,
+        search_key: Id,
+        reset_path_ids: &mut HashSet<Id>,
+        find: impl FnOnce(Option<(u64, &Self)>, &mut HashSet<Id>) -> Self,
+    ) -> Arc<Self> {
+        let Some(value_version) = value_version else {
+            return Arc::new(find(None, reset_path_ids));
+        };
+
+        let id = cached_search_id(tree_state_id);
+        let cached = ctx
+            .data(|d| d.get_temp::<CachedSearch>(id))
+            .filter(|(_, key, ..)| *key == search_key);
+        let (search_matches, searched_path_ids) = match cached {
+            Some((version, _, search_matches, searched_path_ids)) if version == value_version => {
+                (search_matches, searched_path_ids)
+            }
+            _ => {
+                let mut searched_path_ids = HashSet::new();
+                let previous = cached
+                    .as_ref()
+                    .filter(|(version, ..)| *version < value_version);
+                if let Some((_, _, _, previous_searched_path_ids)) = previous {
+                    searched_path_ids.extend(previous_searched_path_ids.iter().copied());
+                }
+                let search_matches = Arc::new(find(
+                    previous.map(|(version, _, search_matches, _)| (*version, &**search_matches)),
+                    &mut searched_path_ids,
+                ));
+                let searched_path_ids = Arc::new(searched_path_ids);
+                ctx.data_mut(|d| {
+                    d.insert_temp::<CachedSearch>(
+                        id,
+                        (
+                            value_version,
+                            search_key,
+                            Arc::clone(&search_matches),
+                            Arc::clone(&searched_path_ids),
+                        ),
+                    );
+                });
+                (search_matches, searched_path_ids)
+            }
+        };
+        reset_path_ids.extend(searched_path_ids.iter().copied());
+        search_matches
+    }
+}
+
+/// The version of the value and key of a search, its matches, and the ids of the arrays/objects that were searched.
+type CachedSearch = (u64, Id, Arc<SearchMatches>, Arc<HashSet<Id>>);
+
+fn cached_search_id(tree_state_id: Id) -> Id {
+    tree_state_id.with("cached_search")
+}
+
+/// Frees the search matches cached via [`SearchMatches::load_or_find`].
+pub(crate) fn clear_cached_search(ctx: &Context, tree_state_id: Id) {
+    ctx.data_mut(|d| d.remove::<CachedSearch>(cached_search_id(tree_state_id)));
+}
+
+/// Returns the id under which the JSON Pointer string of the current search match is stored,
+/// as navigated to via [`JsonTreeResponse::scroll_to_match`](crate::JsonTreeResponse::scroll_to_match).
+pub(crate) fn current_match_id(tree_state_id: Id) -> Id {
+    tree_state_id.with("current_match")
+}
+
+/// Returns the id under which a request to move keyboard focus to the current search match is stored,
+/// until its row is shown. See [`JsonTree::focus_follows_search`](crate::JsonTree::focus_follows_search).
+pub(crate) fn focus_current_match_id(tree_state_id: Id) -> Id {
+    tree_state_id.with("focus_current_match")
+}
 
 impl SearchTerm {
-    pub(crate) fn parse(search_str: &str) -> Option<Self> {
-        SearchTerm::is_valid(search_str).then_some(Self(search_str.to_ascii_lowercase()))
+    pub(crate) fn parse(search_str: &str, options: SearchOptions) -> Option<Self> {
+        Self::parse_all(&[search_str], options, true)
     }
 
-    fn is_valid(search_str: &str) -> bool {
-        !search_str.is_empty()
+    /// Parses several search terms, ignoring any that are empty.
+    /// If `match_all` is `true`, text matches only if it matches every search term, otherwise if it matches any of them.
+    /// Unless [`SearchOptions::words`] is [`SearchWords::Phrase`], each word of a search term is parsed as a search term of its own.
+    ///
+    /// If [`SearchOptions::query_syntax`] is `true`, words of a search term prefixed with `-`, e.g. `error -debug`, are removed from it,
+    /// and instead exclude any text that contains them, and everything beneath an object key that contains them,
+    /// unless the search term consists of nothing else.
+    pub(crate) fn parse_all(
+        search_strs: &[&str],
+        options: SearchOptions,
+        match_all: bool,
+    ) -> Option<Self> {
+        let mut patterns = vec![];
+        for search_str in search_strs {
+            let (search_str, excluded_strs) = if options.query_syntax {
+                split_exclusions(search_str)
+            } else {
+                (Cow::Borrowed(*search_str), vec![])
+            };
+            match options.words {
+                SearchWords::Phrase => {
+                    patterns.extend(SearchPattern::parse(&search_str, options, false));
+                }
+                SearchWords::AllInKeyOrValue | SearchWords::AllInSubtree => {
+                    patterns.extend(
+                        search_str
+                            .split_whitespace()
+                            .filter_map(|word| SearchPattern::parse(word, options, false)),
+                    );
+                }
+            }
+            patterns.extend(
+                excluded_strs
+                    .into_iter()
+                    .filter_map(|excluded_str| SearchPattern::parse(excluded_str, options, true)),
+            );
+        }
+        patterns
+            .iter()
+            .any(|pattern| !pattern.negated)
+            .then_some(Self {
+                patterns,
+                match_all: match_all && options.words != SearchWords::AllInSubtree,
+                match_all_in_subtree: options.words == SearchWords::AllInSubtree,
+            })
     }
 
-    pub(crate) fn find_match_indices_in(&self, other: &str) -> Vec<usize> {
-        other
-            .to_ascii_lowercase()
-            .match_indices(&self.0)
-            .map(|(idx, _)| idx)
-            .collect()
+    /// Returns the patterns that text must match, rather than those that exclude it.
+    fn positive_patterns(&self) -> impl Iterator<Item = &SearchPattern> {
+        self.patterns.iter().filter(|pattern| !pattern.negated)
+    }
+
+    /// Returns whether the predicate holds for all positive patterns, or for any of them, according to how they are combined.
+    fn combine(&self, f: impl Fn(&SearchPattern) -> bool) -> bool {
+        if self.match_all {
+            self.positive_patterns().all(f)
+        } else {
+            self.positive_patterns().any(f)
+        }
+    }
+
+    /// Returns whether the text of the target is excluded from matching by a negated pattern.
+    fn is_excluded(&self, other: &str, target: SearchTarget) -> bool {
+        self.patterns.iter().any(|pattern| {
+            pattern.negated
+                && pattern.matches_target(target)
+                && pattern.matches(pattern.searched_text(other, target))
+        })
+    }
+
+    /// Returns whether anything at the path is excluded from matching,
+    /// because the path passes through an object key that is excluded by a negated pattern.
+    pub(crate) fn excludes_path(&self, path_segments: &[JsonPointerSegment]) -> bool {
+        self.patterns.iter().any(|pattern| pattern.negated)
+            && path_segments.iter().any(|segment| match segment {
+                JsonPointerSegment::Key(key) | JsonPointerSegment::DuplicateKey(key, _) => {
+                    self.is_excluded(key, SearchTarget::Key)
+                }
+                JsonPointerSegment::Index(_) => false,
+            })
     }
 
-    pub(crate) fn len(&self) -> usize {
-        self.0.len()
+    /// Returns whether the search term is matched against object keys.
+    pub(crate) fn matches_keys(&self) -> bool {
+        self.combine(SearchPattern::matches_keys)
+    }
+
+    /// Returns whether the search term is matched against non-recursive values of this type.
+    pub(crate) fn matches_value_type(&self, value_type: BaseValueType) -> bool {
+        self.combine(|pattern| pattern.matches_value_type(value_type))
+    }
+
+    /// Returns whether the search term is matched against non-recursive values.
+    pub(crate) fn matches_values(&self) -> bool {
+        self.combine(SearchPattern::matches_values)
+    }
+
+    /// Returns the byte ranges of the matches of the search term within the text of the target, in order.
+    /// If text must match every pattern, no ranges are returned unless it does.
+    pub(crate) fn find_match_ranges_in(
+        &self,
+        other: &str,
+        target: SearchTarget,
+    ) -> Vec<Range<usize>> {
+        if self.is_excluded(other, target) {
+            return vec![];
+        }
+        let mut ranges = vec![];
+        let mut type_ranges = vec![];
+        for pattern in self.positive_patterns() {
+            let pattern_ranges = if pattern.matches_target(target) {
+                pattern.find_match_ranges_in(pattern.searched_text(other, target))
+            } else {
+                vec![]
+            };
+            if self.match_all && pattern_ranges.is_empty() {
+                return vec![];
+            }
+            if self.match_all && pattern.value_type.is_some() {
+                // A type only filters which values match, unless nothing else is matched.
+                type_ranges.extend(pattern_ranges);
+            } else {
+                ranges.extend(pattern_ranges);
+            }
+        }
+        if ranges.is_empty() {
+            ranges = type_ranges;
+        }
+        merge_ranges(ranges)
     }
 
+    /// Searches the value for matches of the search term. If `search_root` is not empty, only the descendants of the array/object
+    /// at those unescaped segments of a JSON Pointer string are searched, and nothing matches if there is no array/object there.
     pub(crate) fn find_matching_paths_in<T: ToJsonTreeValue>(
         &self,
         value: &T,
+        search_root: &[String],
         abbreviate_root: bool,
         make_persistent_id: &dyn Fn(&[JsonPointerSegment]) -> Id,
         reset_path_ids: &mut HashSet<Id>,
-    ) -> HashSet<Id> {
-        let mut search_match_path_ids = HashSet::new();
-
-        search_impl(
+    ) -> SearchMatches {
+        let subtrees_matching_all = self.match_all_in_subtree.then(|| {
+            self.find_subtrees_matching_all_in(
+                value,
+                search_root,
+                abbreviate_root,
+                make_persistent_id,
+            )
+        });
+        find_paths_matching_in(
             value,
-            self,
-            &mut vec![],
-            &mut search_match_path_ids,
+            &|pointer, candidate| {
+                self.is_match(pointer, candidate)
+                    && subtrees_matching_all.as_ref().map_or(true, |subtrees| {
+                        (search_root.len() + 1..=pointer.0.len()).any(|len| {
+                            subtrees
+                                .contains(&JsonPointer(&pointer.0[..len]).to_json_pointer_string())
+                        })
+                    })
+            },
+            search_root,
+            abbreviate_root,
             make_persistent_id,
             reset_path_ids,
+        )
+    }
+
+    /// Returns whether the object key or non-recursive value at the path matches the search term.
+    fn is_match(&self, pointer: JsonPointer, candidate: SearchCandidate) -> bool {
+        !self.excludes_path(pointer.0)
+            && match candidate {
+                SearchCandidate::Key(key) => self.matches(key, SearchTarget::Key),
+                SearchCandidate::Value(text, value_type) => {
+                    self.matches(text, SearchTarget::Value(value_type))
+                }
+            }
+    }
+
+    /// Updates the matches of the search term found in an earlier version of the value, given the JSON Pointer strings
+    /// of the only subtrees that changed since, by searching just those subtrees.
+    /// Returns `None` if the value must be searched in full instead, e.g. if the search root itself changed.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn update_matching_paths_in<T: ToJsonTreeValue>(
+        &self,
+        value: &T,
+        previous: &SearchMatches,
+        changed_pointers: &[String],
+        search_root: &[String],
+        abbreviate_root: bool,
+        path_ids: &PathIds,
+        reset_path_ids: &mut HashSet<Id>,
+    ) -> Option<SearchMatches> {
+        // Whether a value matches depends on the rest of its subtree.
+        if self.match_all_in_subtree {
+            return None;
+        }
+        let mut changed = changed_pointers
+            .iter()
+            .map(|pointer| parse_json_pointer_string(pointer))
+            .collect::<Option<Vec<_>>>()?;
+        if changed
+            .iter()
+            .any(|segments| search_root.starts_with(segments))
+        {
+            return None;
+        }
+        // Subtrees outside of the search root are not searched, and those within another changed subtree are searched with it.
+        changed.retain(|segments| segments.starts_with(search_root));
+        changed.sort();
+        changed.dedup_by(|segments, ancestor| segments.starts_with(ancestor));
+        let is_changed = |segments: &[String]| {
+            changed
+                .iter()
+                .any(|changed_segments| segments.starts_with(changed_segments))
+        };
+
+        let mut pointers = previous
+            .matched_pointers
+            .iter()
+            .filter_map(|pointer| Some((parse_json_pointer_string(pointer)?, pointer.clone())))
+            .filter(|(segments, _)| !is_changed(segments))
+            .collect::<Vec<_>>();
+        let make_persistent_id = |path_segments: &[JsonPointerSegment]| path_ids.id(path_segments);
+        let mut document_order = DocumentOrder::new(value);
+        for segments in &changed {
+            // The subtree may have been removed.
+            let Some((mut path_segments, subtree)) = find_path_in(value, segments) else {
+                continue;
+            };
+            if subtree.is_expandable() {
+                reset_path_ids.insert(make_persistent_id(&path_segments));
+            }
+            let mut subtree_matches = SearchMatches::default();
+            if let Some(
+                property @ (JsonPointerSegment::Key(_) | JsonPointerSegment::DuplicateKey(..)),
+            ) = path_segments.last()
+            {
+                if self.is_match(
+                    JsonPointer(&path_segments),
+                    SearchCandidate::Key(&property.to_string()),
+                ) {
+                    update_matches(&path_segments, &mut subtree_matches, &make_persistent_id);
+                }
+            }
+            search_impl(
+                subtree,
+                &|pointer, candidate| self.is_match(pointer, candidate),
+                &mut path_segments,
+                &mut subtree_matches,
+                &make_persistent_id,
+                reset_path_ids,
+            );
+
+            // The matches of the subtree are in document order, and follow every earlier match outside of it.
+            let idx = pointers.partition_point(|(other, _)| {
+                document_order.compare(other, segments) == std::cmp::Ordering::Less
+            });
+            pointers.splice(
+                idx..idx,
+                subtree_matches
+                    .matched_pointers
+                    .into_iter()
+                    .filter_map(|pointer| Some((parse_json_pointer_string(&pointer)?, pointer))),
+            );
+        }
+
+        let pointers = pointers
+            .into_iter()
+            .map(|(_, pointer)| pointer)
+            .collect::<Vec<_>>();
+        Some(SearchMatches::from_pointers(
+            &pointers,
+            path_ids,
+            abbreviate_root,
+            reset_path_ids,
+        ))
+    }
+
+    /// Returns the JSON Pointer strings of the deepest object entries and array elements beneath the search root
+    /// whose keys, values or descendants contain matches of every positive pattern.
+    fn find_subtrees_matching_all_in<T: ToJsonTreeValue>(
+        &self,
+        value: &T,
+        search_root: &[String],
+        abbreviate_root: bool,
+        make_persistent_id: &dyn Fn(&[JsonPointerSegment]) -> Id,
+    ) -> HashSet<String> {
+        let num_patterns = self.positive_patterns().count();
+        // For each subtree, whether each positive pattern matches within it.
+        let matched_patterns = RefCell::new(HashMap::<String, Vec<bool>>::new());
+        find_paths_matching_in(
+            value,
+            &|pointer, candidate| {
+                let (text, target) = match candidate {
+                    SearchCandidate::Key(key) => (key, SearchTarget::Key),
+                    SearchCandidate::Value(text, value_type) => {
+                        (text, SearchTarget::Value(value_type))
+                    }
+                };
+                if self.is_excluded(text, target) || self.excludes_path(pointer.0) {
+                    return false;
+                }
+                let mut matched_patterns = matched_patterns.borrow_mut();
+                for (idx, pattern) in self.positive_patterns().enumerate() {
+                    if pattern.matches_target(target)
+                        && pattern.matches(pattern.searched_text(text, target))
+                    {
+                        for len in search_root.len() + 1..=pointer.0.len() {
+                            let subtree = JsonPointer(&pointer.0[..len]).to_json_pointer_string();
+                            matched_patterns
+                                .entry(subtree)
+                                .or_insert_with(|| vec![false; num_patterns])[idx] = true;
+                        }
+                    }
+                }
+                false
+            },
+            search_root,
+            abbreviate_root,
+            make_persistent_id,
+            &mut HashSet::new(),
         );
+        let subtrees: HashSet<String> = matched_patterns
+            .into_inner()
+            .into_iter()
+            .filter(|(_, matched)| matched.iter().all(|matched| *matched))
+            .map(|(subtree, _)| subtree)
+            .collect();
+        // Only keep the deepest subtrees, so that the words must match close together.
+        let ancestors: HashSet<&str> = subtrees
+            .iter()
+            .flat_map(|subtree| {
+                subtree
+                    .match_indices('/')
+                    .filter(|(idx, _)| *idx > 0)
+                    .map(|(idx, _)| &subtree[..idx])
+            })
+            .collect();
+        subtrees
+            .iter()
+            .filter(|subtree| !ancestors.contains(subtree.as_str()))
+            .cloned()
+            .collect()
+    }
 
-        if !abbreviate_root && search_match_path_ids.len() == 1 {
-            // The only match was a top level key or value - no need to expand anything.
-            search_match_path_ids.clear();
+    /// Returns whether only the object entries and array elements within the deepest object entries and array elements
+    /// that contain matches of every pattern are matched. See [`SearchWords::AllInSubtree`].
+    pub(crate) fn matches_all_in_subtree(&self) -> bool {
+        self.match_all_in_subtree
+    }
+
+    /// Returns whether the text of the target matches the search term.
+    fn matches<V: ToString + ?Sized>(&self, other: &V, target: SearchTarget) -> bool {
+        let other = other.to_string();
+        self.combine(|pattern| {
+            pattern.matches_target(target) && pattern.matches(pattern.searched_text(&other, target))
+        }) && !self.is_excluded(&other, target)
+    }
+}
+
+impl SearchPattern {
+    fn parse(search_str: &str, options: SearchOptions, negated: bool) -> Option<Self> {
+        SearchPattern::is_valid(search_str).then(|| Self {
+            term: options.normalize(search_str).into_owned(),
+            options,
+            value_type: options
+                .query_syntax
+                .then(|| parse_value_type(search_str))
+                .flatten(),
+            negated,
+        })
+    }
+
+    fn is_valid(search_str: &str) -> bool {
+        !search_str.is_empty()
+    }
+
+    fn matches_keys(&self) -> bool {
+        self.value_type.is_none() && self.options.scope != SearchScope::Values
+    }
+
+    fn matches_value_type(&self, value_type: BaseValueType) -> bool {
+        self.value_type.map_or(true, |t| t == value_type)
+    }
+
+    fn matches_values(&self) -> bool {
+        self.options.scope != SearchScope::Keys
+    }
+
+    fn matches_target(&self, target: SearchTarget) -> bool {
+        match target {
+            SearchTarget::Key => self.matches_keys(),
+            SearchTarget::Value(value_type) => {
+                self.matches_values() && self.matches_value_type(value_type)
+            }
         }
+    }
 
-        search_match_path_ids
+    /// Returns the part of the text of the target that is searched, i.e. the start of a value longer than [`SearchOptions::max_value_chars`].
+    fn searched_text<'t>(&self, other: &'t str, target: SearchTarget) -> &'t str {
+        match (target, self.options.max_value_chars) {
+            (SearchTarget::Value(_), Some(max_value_chars)) => other
+                .char_indices()
+                .nth(max_value_chars)
+                .map_or(other, |(end, _)| &other[..end]),
+            _ => other,
+        }
     }
 
-    fn matches<V: ToString + ?Sized>(&self, other: &V) -> bool {
-        other.to_string().to_ascii_lowercase().contains(&self.0)
+    /// Returns the byte ranges of the matches of the search term within the text, in order.
+    fn find_match_ranges_in(&self, other: &str) -> Vec<Range<usize>> {
+        if self.value_type.is_some() {
+            return vec![Range {
+                start: 0,
+                end: other.len(),
+            }];
+        }
+        if self.options.case_sensitive || other.is_ascii() {
+            let normalized = self.options.normalize(other);
+            return self.find_normalized_match_ranges_in(&normalized, other);
+        }
+
+        // Case folding may change the length of the text, so map the matches back to the original text.
+        let folded = FoldedText::new(other);
+        let mut ranges: Vec<Range<usize>> = vec![];
+        for range in self.find_normalized_match_ranges_in(&folded.text, &folded.original_chars) {
+            let start = folded.original_ranges[range.start].start;
+            let end = folded.original_ranges[range.end - 1].end;
+            match ranges.last_mut() {
+                // Matches within the folded form of the same character, e.g. "s" within "ß" folded to "ss".
+                Some(last) if start < last.end => last.end = last.end.max(end),
+                _ => ranges.push(start..end),
+            }
+        }
+        ranges
     }
+
+    /// Returns the byte ranges of the matches of the search term within the normalized text.
+    /// `original` has the same characters as the normalized text before normalization, for detecting the start of words.
+    fn find_normalized_match_ranges_in(
+        &self,
+        normalized: &str,
+        original: &str,
+    ) -> Vec<Range<usize>> {
+        if self.options.fuzzy {
+            return fuzzy_match(&self.term, normalized, original).unwrap_or_default();
+        }
+        self.exact_match_ranges_in(normalized).collect()
+    }
+
+    /// Returns the byte ranges of the exact matches of the search term within the normalized text,
+    /// which must be whole words if [`SearchOptions::whole_word`] is set.
+    fn exact_match_ranges_in<'t>(
+        &'t self,
+        normalized: &'t str,
+    ) -> impl Iterator<Item = Range<usize>> + 't {
+        normalized
+            .match_indices(&self.term)
+            .map(|(idx, _)| idx..idx + self.term.len())
+            .filter(|range| !self.options.whole_word || is_whole_word(normalized, range))
+    }
+
+    fn matches(&self, other: &str) -> bool {
+        if self.value_type.is_some() {
+            return true;
+        }
+        if self.options.fuzzy {
+            !self.find_match_ranges_in(other).is_empty()
+        } else {
+            self.exact_match_ranges_in(&self.options.normalize(other))
+                .next()
+                .is_some()
+        }
+    }
+}
+
+impl SearchOptions {
+    /// Returns the text in the form in which it is compared with the search term.
+    /// The length in bytes of ASCII text is preserved, so that match indices apply to the original text.
+    fn normalize<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        if self.case_sensitive {
+            Cow::Borrowed(text)
+        } else if text.is_ascii() {
+            Cow::Owned(text.to_ascii_lowercase())
+        } else {
+            Cow::Owned(FoldedText::new(text).text)
+        }
+    }
+}
+
+/// Text with its letter case folded for case-insensitive comparison, e.g. `Straße` is folded to `strasse`,
+/// with a mapping back to the original text.
+struct FoldedText {
+    text: String,
+    /// For each character of the folded text, the character of the original text that it was folded from.
+    original_chars: String,
+    /// For each byte of the folded text, the byte range of the character of the original text that it was folded from.
+    original_ranges: Vec<Range<usize>>,
+}
+
+impl FoldedText {
+    fn new(original: &str) -> Self {
+        let mut folded = Self {
+            text: String::with_capacity(original.len()),
+            original_chars: String::with_capacity(original.len()),
+            original_ranges: Vec::with_capacity(original.len()),
+        };
+        for (idx, c) in original.char_indices() {
+            let original_range = idx..idx + c.len_utf8();
+            let mut push = |folded_char: char| {
+                folded.text.push(folded_char);
+                folded.original_chars.push(c);
+                folded
+                    .original_ranges
+                    .extend((0..folded_char.len_utf8()).map(|_| original_range.clone()));
+            };
+            match c {
+                // Full case folding of characters whose lowercase form differs from their folded form.
+                'ß' | 'ẞ' => "ss".chars().for_each(&mut push),
+                'ς' => push('σ'),
+                _ => c.to_lowercase().for_each(&mut push),
+            }
+        }
+        folded
+    }
+}
+
+/// Splits the words prefixed with `-` from a search term, e.g. `error -debug` into `error` and `debug`,
+/// keeping the whitespace between the other words as is. Words such as `-1` are kept, so that negative numbers can be searched for.
+/// The search term is returned unchanged if it has no such words, or nothing else.
+fn split_exclusions(search_str: &str) -> (Cow<'_, str>, Vec<&str>) {
+    // Each word is followed by the whitespace character that ends it, if any.
+    let (excluded, included): (Vec<_>, Vec<_>) = search_str
+        .split_inclusive(char::is_whitespace)
+        .partition(|word| is_exclusion(word.trim_end()));
+    if excluded.is_empty() || included.iter().all(|word| word.trim().is_empty()) {
+        return (Cow::Borrowed(search_str), vec![]);
+    }
+    let excluded = excluded
+        .into_iter()
+        .map(|word| &word.trim_end()[EXCLUSION_PREFIX.len_utf8()..])
+        .collect();
+    (Cow::Owned(included.concat().trim().to_owned()), excluded)
+}
+
+/// Returns whether a word of a search term excludes text containing the rest of the word, e.g. `-debug` but not `-1`.
+fn is_exclusion(word: &str) -> bool {
+    word.strip_prefix(EXCLUSION_PREFIX)
+        .and_then(|rest| rest.chars().next())
+        .is_some_and(|c| !c.is_ascii_digit())
+}
+
+/// Returns the type of a search term such as `type:null`, ignoring letter case,
+/// or `None` if the search term is not of that form and is matched as text.
+fn parse_value_type(search_str: &str) -> Option<BaseValueType> {
+    let prefix = search_str.get(..TYPE_PREFIX.len())?;
+    if !prefix.eq_ignore_ascii_case(TYPE_PREFIX) {
+        return None;
+    }
+    match search_str[TYPE_PREFIX.len()..]
+        .trim()
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "null" => Some(BaseValueType::Null),
+        "bool" | "boolean" => Some(BaseValueType::Bool),
+        "number" => Some(BaseValueType::Number),
+        "string" => Some(BaseValueType::String),
+        _ => None,
+    }
+}
+
+/// Sorts the ranges and merges those that overlap.
+fn merge_ranges(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.sort_by_key(|range| range.start);
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start < last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// Returns whether the range of the text is neither directly preceded nor followed by a letter, digit or `_`.
+fn is_whole_word(text: &str, range: &Range<usize>) -> bool {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+    !text[..range.start]
+        .chars()
+        .next_back()
+        .is_some_and(is_word_char)
+        && !text[range.end..].chars().next().is_some_and(is_word_char)
+}
+
+/// Compares the unescaped segments of JSON Pointer strings by the order of the values they point to within a value,
+/// remembering the entries of the objects compared within.
+struct DocumentOrder<'v, T> {
+    value: &'v T,
+    /// The properties of the objects with these unescaped segments, or `None` for arrays.
+    properties: HashMap<Vec<String>, Option<Vec<JsonPointerSegment<'v>>>>,
+}
+
+impl<'v, T: ToJsonTreeValue> DocumentOrder<'v, T> {
+    fn new(value: &'v T) -> Self {
+        Self {
+            value,
+            properties: HashMap::new(),
+        }
+    }
+
+    fn compare(&mut self, a: &[String], b: &[String]) -> std::cmp::Ordering {
+        match a.iter().zip(b).position(|(a, b)| a != b) {
+            Some(idx) => self
+                .position(&a[..idx], &a[idx])
+                .cmp(&self.position(&a[..idx], &b[idx])),
+            // An array/object precedes its descendants.
+            None => a.len().cmp(&b.len()),
+        }
+    }
+
+    /// Returns the position of the entry among the entries of the array/object, or `usize::MAX` if there is no such entry.
+    fn position(&mut self, parent: &[String], segment: &str) -> usize {
+        let value = self.value;
+        let properties = self.properties.entry(parent.to_vec()).or_insert_with(|| {
+            let (_, parent) = find_path_in(value, parent)?;
+            match parent.to_json_tree_value() {
+                JsonTreeValue::Expandable(mut entries, ExpandableType::Object) => {
+                    disambiguate_duplicate_keys(parent, &mut entries);
+                    Some(entries.into_iter().map(|(property, _)| property).collect())
+                }
+                _ => None,
+            }
+        });
+        match properties {
+            Some(properties) => properties
+                .iter()
+                .position(|property| segment_matches(property, segment)),
+            None => segment.parse().ok(),
+        }
+        .unwrap_or(usize::MAX)
+    }
+}
+
+/// Returns the path to the value at the unescaped segments of a JSON Pointer string, along with the value, if it exists.
+pub(crate) fn find_path_in<'a, T: ToJsonTreeValue>(
+    value: &'a T,
+    segments: &[String],
+) -> Option<(Vec<JsonPointerSegment<'a>>, &'a T)> {
+    let mut path_segments = vec![];
+    let mut current = value;
+    for segment in segments {
+        let JsonTreeValue::Expandable(mut entries, _) = current.to_json_tree_value() else {
+            return None;
+        };
+        disambiguate_duplicate_keys(current, &mut entries);
+        let (property, elem) = entries
+            .into_iter()
+            .find(|(property, _)| segment_matches(property, segment))?;
+        path_segments.push(property);
+        current = elem;
+    }
+    Some((path_segments, current))
+}
+
+/// Searches the value for the object keys and non-recursive values that satisfy the predicate, e.g. that match a search term.
+/// If `search_root` is not empty, only the descendants of the array/object at those unescaped segments of a JSON Pointer string
+/// are searched, and nothing matches if there is no array/object there.
+pub(crate) fn find_paths_matching_in<T: ToJsonTreeValue>(
+    value: &T,
+    predicate: &dyn Fn(JsonPointer, SearchCandidate) -> bool,
+    search_root: &[String],
+    abbreviate_root: bool,
+    make_persistent_id: &dyn Fn(&[JsonPointerSegment]) -> Id,
+    reset_path_ids: &mut HashSet<Id>,
+) -> SearchMatches {
+    let mut search_matches = SearchMatches::default();
+    let Some((mut path_segments, value)) = find_path_in(value, search_root) else {
+        return search_matches;
+    };
+    if !search_root.is_empty() && !value.is_expandable() {
+        return search_matches;
+    }
+
+    search_impl(
+        value,
+        predicate,
+        &mut path_segments,
+        &mut search_matches,
+        make_persistent_id,
+        reset_path_ids,
+    );
+
+    if !abbreviate_root && search_matches.match_counts.len() == 1 {
+        // The only match was a top level key or value - no need to expand anything.
+        search_matches.match_counts.clear();
+    }
+
+    search_matches
 }
 
 fn search_impl<'a, T: ToJsonTreeValue>(
     value: &'a T,
-    search_term: &SearchTerm,
+    predicate: &dyn Fn(JsonPointer, SearchCandidate) -> bool,
     path_segments: &mut Vec<JsonPointerSegment<'a>>,
-    search_match_path_ids: &mut HashSet<Id>,
+    search_matches: &mut SearchMatches,
     make_persistent_id: &dyn Fn(&[JsonPointerSegment]) -> Id,
     reset_path_ids: &mut HashSet<Id>,
 ) {
     match value.to_json_tree_value() {
-        JsonTreeValue::Base(_, display_value, _) => {
-            if search_term.matches(display_value) {
-                update_matches(path_segments, search_match_path_ids, make_persistent_id);
+        JsonTreeValue::Base(_, display_value, value_type) => {
+            let text = display_value.to_string();
+            if predicate(
+                JsonPointer(path_segments),
+                SearchCandidate::Value(&text, value_type),
+            ) {
+                update_matches(path_segments, search_matches, make_persistent_id);
             }
         }
-        JsonTreeValue::Expandable(entries, expandable_type) => {
+        JsonTreeValue::Expandable(mut entries, expandable_type) => {
+            disambiguate_duplicate_keys(value, &mut entries);
             for (property, val) in entries.iter() {
                 path_segments.push(*property);
 
@@ -85,15 +1003,20 @@ fn search_impl<'a, T: ToJsonTreeValue>(
                 }
 
                 // Ignore matches for indices in an array.
-                if expandable_type == ExpandableType::Object && search_term.matches(property) {
-                    update_matches(path_segments, search_match_path_ids, make_persistent_id);
+                if expandable_type == ExpandableType::Object
+                    && predicate(
+                        JsonPointer(path_segments),
+                        SearchCandidate::Key(&property.to_string()),
+                    )
+                {
+                    update_matches(path_segments, search_matches, make_persistent_id);
                 }
 
                 search_impl(
                     *val,
-                    search_term,
+                    predicate,
                     path_segments,
-                    search_match_path_ids,
+                    search_matches,
                     make_persistent_id,
                     reset_path_ids,
                 );
@@ -103,12 +1026,425 @@ fn search_impl<'a, T: ToJsonTreeValue>(
     };
 }
 
-fn update_matches(
+/// Returns the ids of all arrays/objects in the value, including the value itself.
+pub(crate) fn find_expandable_path_ids_in<T: ToJsonTreeValue>(
+    value: &T,
+    make_persistent_id: &dyn Fn(&[JsonPointerSegment]) -> Id,
+) -> HashSet<Id> {
+    let mut ids = HashSet::new();
+    for_each_expandable_path_in(value, &mut |path_segments| {
+        ids.insert(make_persistent_id(path_segments));
+    });
+    ids
+}
+
+/// Calls `f` with the path to each array/object in the value, including the value itself.
+pub(crate) fn for_each_expandable_path_in<T: ToJsonTreeValue>(
+    value: &T,
+    f: &mut dyn FnMut(&[JsonPointerSegment]),
+) {
+    fn for_each_impl<'a, T: ToJsonTreeValue>(
+        value: &'a T,
+        path_segments: &mut Vec<JsonPointerSegment<'a>>,
+        f: &mut dyn FnMut(&[JsonPointerSegment]),
+    ) {
+        if let JsonTreeValue::Expandable(mut entries, _) = value.to_json_tree_value() {
+            disambiguate_duplicate_keys(value, &mut entries);
+            f(path_segments);
+            for (property, val) in entries {
+                path_segments.push(property);
+                for_each_impl(val, path_segments, f);
+                path_segments.pop();
+            }
+        }
+    }
+
+    for_each_impl(value, &mut vec![], f);
+}
+
+pub(crate) fn update_matches(
     path_segments: &[JsonPointerSegment],
-    search_match_path_ids: &mut HashSet<Id>,
+    search_matches: &mut SearchMatches,
     make_persistent_id: &dyn Fn(&[JsonPointerSegment]) -> Id,
 ) {
+    // An object entry whose key and value both match is a single match.
+    if !search_matches
+        .matched_ids
+        .insert(make_persistent_id(path_segments))
+    {
+        return;
+    }
+    search_matches
+        .matched_pointers
+        .push(JsonPointer(path_segments).to_json_pointer_string());
+
     for i in 0..path_segments.len() {
-        search_match_path_ids.insert(make_persistent_id(&path_segments[0..i]));
+        *search_matches
+            .match_counts
+            .entry(make_persistent_id(&path_segments[0..i]))
+            .or_default() += 1;
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use super::*;
+
+    const STRING: SearchTarget = SearchTarget::Value(BaseValueType::String);
+
+    #[test]
+    fn counts_matches_within_each_array_and_object() {
+        let value =
+            serde_json::json!({"foo": [1, "grep", {"grep": 2}], "bar": "grep", "baz": null});
+        let make_persistent_id = |path_segments: &[JsonPointerSegment]| Id::new(path_segments);
+
+        let search_matches = SearchTerm::parse("grep", SearchOptions::default())
+            .unwrap()
+            .find_matching_paths_in(&value, &[], false, &make_persistent_id, &mut HashSet::new());
+
+        let root_id = make_persistent_id(&[]);
+        let foo_id = make_persistent_id(&[JsonPointerSegment::Key("foo")]);
+        let foo_2_id =
+            make_persistent_id(&[JsonPointerSegment::Key("foo"), JsonPointerSegment::Index(2)]);
+
+        assert_eq!(search_matches.match_counts.len(), 3);
+        assert_eq!(search_matches.match_counts[&root_id], 3);
+        assert_eq!(search_matches.match_counts[&foo_id], 2);
+        assert_eq!(search_matches.match_counts[&foo_2_id], 1);
+        assert_eq!(search_matches.matched_ids.len(), 3);
+        let mut matched_pointers = search_matches.matched_pointers;
+        // The order of keys depends on whether serde_json's `preserve_order` feature is enabled.
+        matched_pointers.sort();
+        assert_eq!(matched_pointers, ["/bar", "/foo/1", "/foo/2/grep"]);
+    }
+
+    #[test]
+    fn counts_matching_key_and_value_once() {
+        let value = serde_json::json!({"grep": "grep"});
+        let make_persistent_id = |path_segments: &[JsonPointerSegment]| Id::new(path_segments);
+
+        let search_matches = SearchTerm::parse("grep", SearchOptions::default())
+            .unwrap()
+            .find_matching_paths_in(&value, &[], true, &make_persistent_id, &mut HashSet::new());
+
+        assert_eq!(search_matches.match_counts[&make_persistent_id(&[])], 1);
+        assert_eq!(search_matches.matched_pointers, ["/grep"]);
+    }
+
+    #[test]
+    fn matches_letter_case_only_if_case_sensitive() {
+        let case_insensitive = SearchTerm::parse("Id", SearchOptions::default()).unwrap();
+        assert!(case_insensitive.matches("ID", STRING));
+        assert_eq!(
+            case_insensitive.find_match_ranges_in("id, ID", STRING),
+            vec![0..2, 4..6]
+        );
+
+        let options = SearchOptions {
+            case_sensitive: true,
+            ..Default::default()
+        };
+        let case_sensitive = SearchTerm::parse("ID", options).unwrap();
+        assert!(!case_sensitive.matches("id", STRING));
+        assert_eq!(
+            case_sensitive.find_match_ranges_in("id, ID", STRING),
+            vec![4..6]
+        );
+    }
+
+    #[test]
+    fn folds_unicode_letter_case() {
+        let search_term = SearchTerm::parse("STRASSE", SearchOptions::default()).unwrap();
+        assert!(search_term.matches("Hauptstraße", STRING));
+        assert_eq!(
+            search_term.find_match_ranges_in("Hauptstraße 1", STRING),
+            vec![5..12]
+        );
+
+        let search_term = SearchTerm::parse("ß", SearchOptions::default()).unwrap();
+        assert_eq!(
+            search_term.find_match_ranges_in("STRASSE", STRING),
+            vec![4..6]
+        );
+
+        let search_term = SearchTerm::parse("привет", SearchOptions::default()).unwrap();
+        assert!(search_term.matches("ПРИВЕТ мир", STRING));
+        assert_eq!(
+            search_term.find_match_ranges_in("«ПРИВЕТ»", STRING),
+            vec![2..14]
+        );
+
+        let search_term = SearchTerm::parse("s", SearchOptions::default()).unwrap();
+        assert_eq!(search_term.find_match_ranges_in("aßa", STRING), vec![1..3]);
+    }
+
+    #[test]
+    fn matches_characters_in_order_if_fuzzy() {
+        let options = SearchOptions {
+            fuzzy: true,
+            ..Default::default()
+        };
+        let fuzzy = SearchTerm::parse("UsrNm", options).unwrap();
+        assert!(fuzzy.matches("user_name", STRING));
+        assert!(!fuzzy.matches("name_of_user", STRING));
+        assert_eq!(
+            fuzzy.find_match_ranges_in("user_name", STRING),
+            vec![0..2, 3..4, 5..6, 7..8]
+        );
+    }
+
+    #[test]
+    fn matches_whole_words_only_if_whole_word() {
+        let options = SearchOptions {
+            whole_word: true,
+            ..Default::default()
+        };
+        let whole_word = SearchTerm::parse("id", options).unwrap();
+        assert!(whole_word.matches("user id", STRING));
+        assert!(whole_word.matches("ID", STRING));
+        assert!(!whole_word.matches("valid", STRING));
+        assert!(!whole_word.matches("user_id", STRING));
+        assert_eq!(
+            whole_word.find_match_ranges_in("uuid, id-1, width, (id)", STRING),
+            vec![6..8, 20..22]
+        );
+    }
+
+    #[test]
+    fn matches_values_by_type() {
+        let value = serde_json::json!({"a": null, "b": [true, "null", null], "null": 1});
+        let make_persistent_id = |path_segments: &[JsonPointerSegment]| Id::new(path_segments);
+
+        let options = SearchOptions {
+            query_syntax: true,
+            ..Default::default()
+        };
+        let search_term = SearchTerm::parse("Type:NULL", options).unwrap();
+        let mut matched_pointers = search_term
+            .find_matching_paths_in(&value, &[], false, &make_persistent_id, &mut HashSet::new())
+            .matched_pointers;
+        // The order of keys depends on whether serde_json's `preserve_order` feature is enabled.
+        matched_pointers.sort();
+        assert_eq!(matched_pointers, ["/a", "/b/2"]);
+        assert_eq!(
+            search_term.find_match_ranges_in("null", SearchTarget::Value(BaseValueType::Null)),
+            vec![Range { start: 0, end: 4 }]
+        );
+
+        // Without the query syntax, the search term is matched as text.
+        let search_term = SearchTerm::parse("type:null", SearchOptions::default()).unwrap();
+        assert!(search_term.matches("type:null", STRING));
+        assert!(!search_term.matches("null", SearchTarget::Value(BaseValueType::Null)));
+
+        for (search_str, value_type) in [
+            ("type:bool", Some(BaseValueType::Bool)),
+            ("type: boolean", Some(BaseValueType::Bool)),
+            ("type:number", Some(BaseValueType::Number)),
+            ("type:string", Some(BaseValueType::String)),
+            ("type:array", None),
+            ("types:null", None),
+        ] {
+            assert_eq!(parse_value_type(search_str), value_type, "{search_str}");
+        }
+    }
+
+    #[test]
+    fn matches_all_or_any_search_terms() {
+        let options = SearchOptions {
+            query_syntax: true,
+            ..Default::default()
+        };
+        let all = SearchTerm::parse_all(&["error", "type:string"], options, true).unwrap();
+        assert!(all.matches("Error: timeout", STRING));
+        assert!(!all.matches("error", SearchTarget::Key));
+        assert!(!all.matches("timeout", STRING));
+        // The type only filters which values match.
+        assert_eq!(all.find_match_ranges_in("an error", STRING), vec![3..8]);
+
+        let any = SearchTerm::parse_all(&["error", "timeout", ""], options, false).unwrap();
+        assert!(any.matches("timeout", SearchTarget::Key));
+        assert!(!any.matches("refused", STRING));
+        assert_eq!(
+            any.find_match_ranges_in("timeout error", STRING),
+            vec![0..7, 8..13]
+        );
+
+        assert!(SearchTerm::parse_all(&["", ""], options, true).is_none());
+    }
+
+    #[test]
+    fn excludes_text_matching_negated_words() {
+        let options = SearchOptions {
+            query_syntax: true,
+            ..Default::default()
+        };
+        let search_term = SearchTerm::parse("error  -deprecation -type:null", options).unwrap();
+        assert!(search_term.matches("Error: timeout", STRING));
+        assert!(!search_term.matches("error: Deprecation warning", STRING));
+        assert!(!search_term.matches("error", SearchTarget::Value(BaseValueType::Null)));
+        assert_eq!(
+            search_term.find_match_ranges_in("deprecation error", STRING),
+            vec![]
+        );
+
+        let path = |keys: &[&'static str]| {
+            keys.iter()
+                .map(|key| JsonPointerSegment::Key(key))
+                .collect::<Vec<_>>()
+        };
+        assert!(search_term.excludes_path(&path(&["logs", "deprecations"])));
+        assert!(!search_term.excludes_path(&path(&["logs", "errors"])));
+
+        // A search term consisting only of negated words is matched as is.
+        let search_term = SearchTerm::parse("-debug", options).unwrap();
+        assert!(search_term.matches("-debug", STRING));
+        assert!(!search_term.matches("debug", STRING));
+
+        // Negative numbers are matched as text, and the whitespace between the other words is kept.
+        let search_term = SearchTerm::parse("x  -1\t-y", options).unwrap();
+        assert!(search_term.matches("x  -1", STRING));
+        assert!(!search_term.matches("x  -1 y", STRING));
+        assert!(!search_term.matches("x -1", STRING));
+
+        // Without the query syntax, the search term is matched as text.
+        let search_term =
+            SearchTerm::parse("error -deprecation", SearchOptions::default()).unwrap();
+        assert!(search_term.matches("error -deprecation", STRING));
+        assert!(!search_term.matches("error: timeout", STRING));
+    }
+
+    #[test]
+    fn searches_only_within_search_root() {
+        let value = serde_json::json!({
+            "raw_payload": {"status": "error"},
+            "summary": {"status": "error", "items": ["error"]}
+        });
+        let make_persistent_id = |path_segments: &[JsonPointerSegment]| Id::new(path_segments);
+        let search_term = SearchTerm::parse("error", SearchOptions::default()).unwrap();
+        let matched_pointers = |search_root: &[&str]| {
+            let search_root = search_root
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>();
+            let mut matched_pointers = search_term
+                .find_matching_paths_in(
+                    &value,
+                    &search_root,
+                    false,
+                    &make_persistent_id,
+                    &mut HashSet::new(),
+                )
+                .matched_pointers;
+            matched_pointers.sort();
+            matched_pointers
+        };
+
+        assert_eq!(
+            matched_pointers(&["summary"]),
+            ["/summary/items/0", "/summary/status"]
+        );
+        assert_eq!(
+            matched_pointers(&["summary", "items"]),
+            ["/summary/items/0"]
+        );
+        assert!(matched_pointers(&["summary", "status"]).is_empty());
+        assert!(matched_pointers(&["missing"]).is_empty());
+    }
+
+    #[test]
+    fn matches_only_within_scope() {
+        let value = serde_json::json!({"level": "info", "message": "level too high"});
+        let make_persistent_id = |path_segments: &[JsonPointerSegment]| Id::new(path_segments);
+        let level_id = make_persistent_id(&[JsonPointerSegment::Key("level")]);
+        let message_id = make_persistent_id(&[JsonPointerSegment::Key("message")]);
+
+        for (scope, expected_ids) in [
+            (SearchScope::KeysAndValues, vec![level_id, message_id]),
+            (SearchScope::Keys, vec![level_id]),
+            (SearchScope::Values, vec![message_id]),
+        ] {
+            let options = SearchOptions {
+                scope,
+                ..Default::default()
+            };
+            let search_matches = SearchTerm::parse("level", options)
+                .unwrap()
+                .find_matching_paths_in(
+                    &value,
+                    &[],
+                    false,
+                    &make_persistent_id,
+                    &mut HashSet::new(),
+                );
+            assert_eq!(search_matches.matched_ids, HashSet::from_iter(expected_ids));
+        }
+    }
+
+    #[test]
+    fn matches_only_the_start_of_long_values() {
+        let options = SearchOptions {
+            max_value_chars: Some(5),
+            ..Default::default()
+        };
+        let search_term = SearchTerm::parse("ab", options).unwrap();
+        assert!(search_term.matches("xxxab", STRING));
+        assert!(!search_term.matches("xxxxab", STRING));
+        assert!(search_term.matches("xxxxab", SearchTarget::Key));
+        assert_eq!(
+            search_term.find_match_ranges_in("éabxab", STRING),
+            vec![2..4]
+        );
+
+        let options = SearchOptions {
+            query_syntax: true,
+            ..options
+        };
+        let type_term = SearchTerm::parse("type:string", options).unwrap();
+        assert_eq!(
+            type_term.find_match_ranges_in("abcdefgh", STRING),
+            vec![0..5]
+        );
+    }
+
+    #[test]
+    fn matches_words_separately() {
+        let words = |words| SearchOptions {
+            words,
+            query_syntax: true,
+            ..Default::default()
+        };
+
+        let phrase = SearchTerm::parse("error timeout", words(SearchWords::Phrase)).unwrap();
+        assert!(!phrase.matches("timeout error", STRING));
+
+        let all_in_key_or_value =
+            SearchTerm::parse("error  timeout -debug", words(SearchWords::AllInKeyOrValue))
+                .unwrap();
+        assert!(all_in_key_or_value.matches("timeout error", STRING));
+        assert!(!all_in_key_or_value.matches("error", STRING));
+        assert!(!all_in_key_or_value.matches("debug: timeout error", STRING));
+        assert_eq!(
+            all_in_key_or_value.find_match_ranges_in("timeout error", STRING),
+            vec![0..7, 8..13]
+        );
+
+        let value = serde_json::json!({
+            "a": [{"level": "error", "reason": "timeout"}, {"level": "error"}],
+            "error": "timeout",
+            "b": "timeout"
+        });
+        let mut matched_pointers =
+            SearchTerm::parse("error timeout", words(SearchWords::AllInSubtree))
+                .unwrap()
+                .find_matching_paths_in(
+                    &value,
+                    &[],
+                    false,
+                    &|path_segments| Id::new(path_segments),
+                    &mut HashSet::new(),
+                )
+                .matched_pointers;
+        matched_pointers.sort();
+        assert_eq!(matched_pointers, ["/a/0/level", "/a/0/reason", "/error"]);
     }
 }
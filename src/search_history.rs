@@ -0,0 +1,68 @@
+use egui::{Context, Id};
+
+/// The number of recent search terms remembered for each [`JsonTree`](crate::JsonTree).
+const MAX_SEARCH_HISTORY: usize = 10;
+
+fn search_history_id(tree_state_id: Id) -> Id {
+    tree_state_id.with("search_history")
+}
+
+/// Records the search term as the most recent search of the tree, and returns the recent search terms, most recent first.
+///
+/// A search term that extends or shortens the most recent search term replaces it,
+/// so that only the final search term is remembered while it is being typed.
+pub(crate) fn record_search(ctx: &Context, tree_state_id: Id, search_term: &str) -> Vec<String> {
+    let id = search_history_id(tree_state_id);
+    ctx.data_mut(|d| {
+        let history = d.get_temp_mut_or_default::<Vec<String>>(id);
+        let search_term = search_term.trim();
+        if search_term.is_empty() || history.first().is_some_and(|latest| latest == search_term) {
+            return history.clone();
+        }
+        if history.first().is_some_and(|latest| {
+            search_term.starts_with(latest.as_str()) || latest.starts_with(search_term)
+        }) {
+            history.remove(0);
+        }
+        history.retain(|earlier| earlier != search_term);
+        history.insert(0, search_term.to_owned());
+        history.truncate(MAX_SEARCH_HISTORY);
+        history.clone()
+    })
+}
+
+/// Returns the recent search terms of the tree, most recent first.
+pub(crate) fn load_search_history(ctx: &Context, tree_state_id: Id) -> Vec<String> {
+    ctx.data(|d| d.get_temp::<Vec<String>>(search_history_id(tree_state_id)))
+        .unwrap_or_default()
+}
+
+/// Forgets the recent search terms of the tree.
+pub(crate) fn clear_search_history(ctx: &Context, tree_state_id: Id) {
+    ctx.data_mut(|d| d.remove::<Vec<String>>(search_history_id(tree_state_id)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remembers_final_search_terms_most_recent_first() {
+        let ctx = Context::default();
+        let id = Id::new("tree");
+        for search_term in ["e", "err", "error", "", "warn", "  ", "err", "error"] {
+            record_search(&ctx, id, search_term);
+        }
+        assert_eq!(load_search_history(&ctx, id), ["error", "warn"]);
+
+        for i in 0..MAX_SEARCH_HISTORY {
+            record_search(&ctx, id, &format!("{i}/"));
+        }
+        let history = load_search_history(&ctx, id);
+        assert_eq!(history.len(), MAX_SEARCH_HISTORY);
+        assert_eq!(history[0], format!("{}/", MAX_SEARCH_HISTORY - 1));
+
+        clear_search_history(&ctx, id);
+        assert!(load_search_history(&ctx, id).is_empty());
+    }
+}
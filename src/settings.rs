@@ -0,0 +1,170 @@
+use egui::{Response, Ui};
+
+use crate::{EpochUnit, JsonTreeDensity, JsonTreeStyle, UuidDisplay};
+
+/// User preferences for how a [`JsonTree`](crate::JsonTree) is displayed, with a ready-made settings UI.
+///
+/// With the `serde` feature enabled, the settings can be stored alongside the rest of your app's state,
+/// e.g. via `eframe::set_value` and `eframe::get_value`, so that they survive restarts:
+/// ```rust
+/// # use egui_json_tree::{JsonTree, JsonTreeSettings, JsonTreeStyle};
+/// # egui::__run_test_ui(|ui| {
+/// # let value = serde_json::json!({ "created": 1700000000 });
+/// let mut settings = JsonTreeSettings::default();
+///
+/// ui.collapsing("Settings", |ui| settings.ui(ui));
+///
+/// JsonTree::new("settings-tree", &value)
+///     .style(settings.apply(JsonTreeStyle::new()))
+///     .show(ui);
+/// # });
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct JsonTreeSettings {
+    /// See [`JsonTreeStyle::density`].
+    pub density: JsonTreeDensity,
+    /// See [`JsonTreeStyle::high_contrast`].
+    pub high_contrast: bool,
+    /// See [`JsonTreeStyle::abbreviate_root`].
+    pub abbreviate_root: bool,
+    /// See [`JsonTreeStyle::show_hidden_match_counts`].
+    pub show_hidden_match_counts: bool,
+    /// See [`JsonTreeStyle::uuid_display`].
+    pub uuid_display: UuidDisplay,
+    /// The unit of number values to humanize as timestamps, if any.
+    /// See [`EpochTimestampConfig::unit`](crate::EpochTimestampConfig::unit).
+    pub epoch_unit: Option<EpochUnit>,
+    /// See [`JsonTreeStyle::low_cost`].
+    pub low_cost: bool,
+}
+
+impl JsonTreeSettings {
+    /// Returns the given style with these settings applied, leaving all other options as they are.
+    pub fn apply(&self, mut style: JsonTreeStyle) -> JsonTreeStyle {
+        style.density = self.density;
+        style.high_contrast = self.high_contrast;
+        style.abbreviate_root = self.abbreviate_root;
+        style.show_hidden_match_counts = self.show_hidden_match_counts;
+        style.uuid_display = self.uuid_display;
+        style.epoch_timestamps.unit = self.epoch_unit;
+        style.low_cost = self.low_cost;
+        style
+    }
+
+    /// Shows controls for editing the settings. The response is marked as changed if any setting changed.
+    pub fn ui(&mut self, ui: &mut Ui) -> Response {
+        let mut changed = false;
+
+        let mut response = ui
+            .vertical(|ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Density:");
+                    for (density, text) in [
+                        (JsonTreeDensity::Compact, "Compact"),
+                        (JsonTreeDensity::Comfortable, "Comfortable"),
+                        (JsonTreeDensity::Spacious, "Spacious"),
+                    ] {
+                        changed |= ui
+                            .selectable_value(&mut self.density, density, text)
+                            .changed();
+                    }
+                });
+                changed |= ui
+                    .checkbox(&mut self.high_contrast, "High contrast colors")
+                    .changed();
+                changed |= ui
+                    .checkbox(&mut self.abbreviate_root, "Abbreviate collapsed root")
+                    .changed();
+                changed |= ui
+                    .checkbox(
+                        &mut self.show_hidden_match_counts,
+                        "Show match counts of collapsed arrays/objects",
+                    )
+                    .changed();
+                ui.horizontal(|ui| {
+                    ui.label("UUIDs:");
+                    for (uuid_display, text) in [
+                        (UuidDisplay::Plain, "Plain"),
+                        (UuidDisplay::Highlighted, "Highlighted"),
+                        (UuidDisplay::Shortened, "Shortened"),
+                    ] {
+                        changed |= ui
+                            .selectable_value(&mut self.uuid_display, uuid_display, text)
+                            .changed();
+                    }
+                });
+                changed |= ui
+                    .checkbox(&mut self.low_cost, "Low-cost rendering")
+                    .changed();
+                ui.horizontal(|ui| {
+                    ui.label("Timestamps:");
+                    for (epoch_unit, text) in [
+                        (None, "Off"),
+                        (Some(EpochUnit::Auto), "Auto"),
+                        (Some(EpochUnit::Seconds), "Seconds"),
+                        (Some(EpochUnit::Milliseconds), "Milliseconds"),
+                    ] {
+                        changed |= ui
+                            .selectable_value(&mut self.epoch_unit, epoch_unit, text)
+                            .changed();
+                    }
+                });
+            })
+            .response;
+
+        if changed {
+            response.mark_changed();
+        }
+        response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{EpochUnit, JsonTreeDensity, JsonTreeStyle, UuidDisplay};
+
+    use super::JsonTreeSettings;
+
+    #[test]
+    fn applies_settings_to_style() {
+        let settings = JsonTreeSettings {
+            density: JsonTreeDensity::Compact,
+            uuid_display: UuidDisplay::Shortened,
+            epoch_unit: Some(EpochUnit::Seconds),
+            ..Default::default()
+        };
+
+        let style = settings.apply(JsonTreeStyle::new().root_label("body"));
+
+        assert_eq!(style.density, JsonTreeDensity::Compact);
+        assert_eq!(style.uuid_display, UuidDisplay::Shortened);
+        assert_eq!(style.epoch_timestamps.unit, Some(EpochUnit::Seconds));
+        assert_eq!(style.root_label.as_deref(), Some("body"));
+    }
+
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    #[test]
+    fn deserializes_missing_settings_as_defaults() {
+        let settings = JsonTreeSettings {
+            high_contrast: true,
+            epoch_unit: Some(EpochUnit::Auto),
+            ..Default::default()
+        };
+        let serialized = serde_json::to_string(&settings).unwrap();
+        assert_eq!(
+            serde_json::from_str::<JsonTreeSettings>(&serialized).unwrap(),
+            settings
+        );
+
+        let partial = serde_json::from_str::<JsonTreeSettings>(r#"{"density":"Spacious"}"#);
+        assert_eq!(
+            partial.unwrap(),
+            JsonTreeSettings {
+                density: JsonTreeDensity::Spacious,
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use egui::mutex::RwLock;
+
+/// A search term shared by multiple [`JsonTree`](crate::JsonTree)s, so that one search bar
+/// drives highlighting and expansion consistently in all of them, e.g. in request and response viewers.
+///
+/// This is a cheaply cloneable handle. Subscribe a tree with [`JsonTree::shared_search`](crate::JsonTree::shared_search):
+/// ```rust
+/// # use egui_json_tree::{DefaultExpand, JsonTree, JsonTreeSharedSearch};
+/// # egui::__run_test_ui(|ui| {
+/// # let mut search_input = String::new();
+/// let request = serde_json::json!({ "id": 1 });
+/// let response = serde_json::json!({ "id": 1, "ok": true });
+/// let search = JsonTreeSharedSearch::new();
+///
+/// if ui.text_edit_singleline(&mut search_input).changed() {
+///     search.set_term(search_input.clone());
+/// }
+///
+/// for (id, value) in [("request", &request), ("response", &response)] {
+///     JsonTree::new(id, value)
+///         .default_expand(DefaultExpand::SearchResults(""))
+///         .shared_search(&search)
+///         .show(ui);
+/// }
+/// # });
+/// ```
+///
+/// Whenever the search term changes, the expanded state of all subscribed trees is reset,
+/// so that they consistently reveal the matches of the new search term.
+#[derive(Clone, Default)]
+pub struct JsonTreeSharedSearch {
+    shared: Arc<RwLock<SharedSearchState>>,
+}
+
+#[derive(Default)]
+struct SharedSearchState {
+    term: String,
+    /// Incremented whenever the search term changes.
+    generation: u64,
+}
+
+impl JsonTreeSharedSearch {
+    /// Creates a new [`JsonTreeSharedSearch`] with an empty search term.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the current search term.
+    pub fn term(&self) -> String {
+        self.shared.read().term.clone()
+    }
+
+    /// Sets the search term used by all subscribed trees when they are next shown.
+    pub fn set_term(&self, term: impl Into<String>) {
+        let term = term.into();
+        let mut state = self.shared.write();
+        if state.term != term {
+            state.term = term;
+            state.generation += 1;
+        }
+    }
+
+    /// Returns the current search term and its generation.
+    pub(crate) fn load(&self) -> (String, u64) {
+        let state = self.shared.read();
+        (state.term.clone(), state.generation)
+    }
+}
@@ -0,0 +1,119 @@
+use std::{fmt, ops::Range, sync::Arc};
+
+use egui::{mutex::Mutex, text::LayoutJob, Color32, Id, Ui};
+
+use crate::pointer::{JsonPointer, JsonPointerSegment};
+
+/// A structured model of what a [`JsonTree`](crate::JsonTree) rendered in a frame,
+/// for golden-file style tests of formatting, search highlighting and filtering.
+///
+/// Enable recording with [`JsonTree::record_snapshot`](crate::JsonTree::record_snapshot),
+/// then retrieve it with [`JsonTreeResponse::snapshot`](crate::JsonTreeResponse::snapshot).
+///
+/// Only text rendered by the default render implementation is recorded,
+/// so text rendered by a custom render hook instead of calling `render_default` will be missing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonTreeSnapshot {
+    /// The rendered rows, in order from top to bottom.
+    pub rows: Vec<JsonTreeSnapshotRow>,
+}
+
+/// A single row of a [`JsonTreeSnapshot`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonTreeSnapshotRow {
+    /// The JSON Pointer string of the value shown in this row.
+    /// For the closing delimiter of an expanded array/object, this is the pointer to the array/object.
+    /// Likewise for the heading of a group of object entries configured via [`JsonTree::group_entries`](crate::JsonTree::group_entries),
+    /// whose depth is also that of the object.
+    pub pointer: String,
+    /// The nesting depth of the row, where the root value has a depth of `0`.
+    pub depth: usize,
+    /// The displayed text of the row.
+    pub text: String,
+    /// The byte ranges of `text` that are highlighted, e.g. search matches.
+    pub highlights: Vec<Range<usize>>,
+}
+
+/// Formats the snapshot as one line per row, indented by depth, with highlighted text wrapped in `«` and `»`.
+impl fmt::Display for JsonTreeSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in &self.rows {
+            write!(f, "{}", "  ".repeat(row.depth))?;
+
+            let mut start = 0;
+            for highlight in &row.highlights {
+                write!(
+                    f,
+                    "{}«{}»",
+                    &row.text[start..highlight.start],
+                    &row.text[highlight.clone()]
+                )?;
+                start = highlight.end;
+            }
+            writeln!(f, "{}", &row.text[start..])?;
+        }
+        Ok(())
+    }
+}
+
+/// Records a [`JsonTreeSnapshot`] while a tree is shown,
+/// via egui temp memory so that the default render implementations can append to it.
+#[derive(Clone)]
+pub(crate) struct SnapshotRecorder(Arc<Mutex<JsonTreeSnapshot>>);
+
+impl SnapshotRecorder {
+    fn id() -> Id {
+        Id::new("egui_json_tree_snapshot_recorder")
+    }
+
+    /// Starts recording everything rendered within the `Ui`, until [`SnapshotRecorder::finish`] is called.
+    pub(crate) fn start(ui: &Ui) -> Self {
+        let recorder = Self(Default::default());
+        ui.data_mut(|d| d.insert_temp(Self::id(), recorder.clone()));
+        recorder
+    }
+
+    pub(crate) fn finish(self, ui: &Ui) -> JsonTreeSnapshot {
+        ui.data_mut(|d| d.remove::<Self>(Self::id()));
+        std::mem::take(&mut *self.0.lock())
+    }
+
+    fn active(ui: &Ui) -> Option<Self> {
+        ui.data(|d| d.get_temp::<Self>(Self::id()))
+    }
+}
+
+/// Starts a new row in the snapshot being recorded, if any.
+pub(crate) fn record_row(ui: &Ui, path_segments: &[JsonPointerSegment]) {
+    if let Some(recorder) = SnapshotRecorder::active(ui) {
+        recorder.0.lock().rows.push(JsonTreeSnapshotRow {
+            pointer: JsonPointer(path_segments).to_json_pointer_string(),
+            depth: path_segments.len(),
+            ..Default::default()
+        });
+    }
+}
+
+/// Appends the text of a rendered [`LayoutJob`] to the current row of the snapshot being recorded, if any.
+pub(crate) fn record_job(ui: &Ui, job: &LayoutJob) {
+    let Some(recorder) = SnapshotRecorder::active(ui) else {
+        return;
+    };
+    let mut snapshot = recorder.0.lock();
+    let Some(row) = snapshot.rows.last_mut() else {
+        return;
+    };
+
+    for section in &job.sections {
+        let start = row.text.len();
+        row.text.push_str(&job.text[section.byte_range.clone()]);
+
+        if section.format.background != Color32::TRANSPARENT {
+            let end = row.text.len();
+            match row.highlights.last_mut() {
+                Some(last) if last.end == start => last.end = end,
+                _ => row.highlights.push(start..end),
+            }
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use egui::{layers::ShapeIdx, Context, Id, Label, Response, RichText, ScrollArea, Shape, Ui};
+
+use crate::{
+    pointer::{parse_json_pointer_string, JsonPointer},
+    search::find_path_in,
+    value::{to_json_string, BaseValueType, ExpandableType, JsonTreeValue, ToJsonTreeValue},
+    JsonTree, JsonTreeResponse,
+};
+
+/// A hook that adds UI to the detail panel of a [`JsonTreeSplitView`] for the selected value.
+pub(crate) type DetailHook<'a, T> = dyn FnMut(&mut Ui, JsonPointer, &T) + 'a;
+
+/// Shows a [`JsonTree`] on the left and a detail panel for the selected value on the right,
+/// with its JSON Pointer, its type and the value in full.
+///
+/// A value is selected by clicking its array index, object key or value in the tree.
+/// The tree keeps its own configuration, including any render hook.
+/// ```rust
+/// # use egui_json_tree::{JsonTree, JsonTreeSplitView};
+/// # egui::__run_test_ui(|ui| {
+/// let value = serde_json::json!({ "user": { "id": 1, "name": "Ferris" } });
+///
+/// let response = JsonTreeSplitView::new(JsonTree::new("inspector", &value))
+///     .on_detail(|ui, pointer, _| {
+///         if ui.button("Copy JSON pointer").clicked() {
+///             ui.ctx().copy_text(pointer.to_json_pointer_string());
+///         }
+///     })
+///     .show(ui);
+///
+/// if let Some(pointer) = response.selected_pointer() {
+///     // ...
+/// }
+/// # });
+/// ```
+#[must_use = "You should call .show()"]
+pub struct JsonTreeSplitView<'a, T: ToJsonTreeValue> {
+    tree: JsonTree<'a, T>,
+    tree_width: f32,
+    on_detail: Option<Box<DetailHook<'a, T>>>,
+}
+
+impl<'a, T: ToJsonTreeValue> JsonTreeSplitView<'a, T> {
+    /// Creates a new [`JsonTreeSplitView`] around the given tree.
+    pub fn new(tree: JsonTree<'a, T>) -> Self {
+        Self {
+            tree,
+            tree_width: 300.0,
+            on_detail: None,
+        }
+    }
+
+    /// Set the initial width of the tree panel, which the user can resize. Defaults to `300.0`.
+    pub fn tree_width(mut self, tree_width: f32) -> Self {
+        self.tree_width = tree_width;
+        self
+    }
+
+    /// Add UI to the detail panel below the details of the selected value, e.g. actions specific to your app.
+    /// The hook receives the JSON Pointer to the selected value and the value itself.
+    pub fn on_detail(mut self, on_detail: impl FnMut(&mut Ui, JsonPointer, &T) + 'a) -> Self {
+        self.on_detail = Some(Box::new(on_detail));
+        self
+    }
+
+    /// Show the tree and the detail panel within the `Ui`.
+    pub fn show(self, ui: &mut Ui) -> JsonTreeSplitViewResponse {
+        let Self {
+            mut tree,
+            tree_width,
+            mut on_detail,
+        } = self;
+        let id = tree.id;
+        let value = tree.value;
+        let selection = Selection::load(ui.ctx(), id);
+        let selection_id = selection.id;
+        tree.config.renderer.selection = Some(selection);
+
+        let tree_response = egui::SidePanel::left(id.with("tree_panel"))
+            .resizable(true)
+            .default_width(tree_width)
+            .show_inside(ui, |ui| {
+                ScrollArea::both()
+                    .auto_shrink(false)
+                    .show(ui, |ui| tree.show(ui))
+                    .inner
+            })
+            .inner;
+
+        let selected = ui.data(|d| d.get_temp::<String>(selection_id));
+        let found = selected
+            .as_deref()
+            .and_then(parse_json_pointer_string)
+            .and_then(|segments| find_path_in(value, &segments));
+        // A selection whose value no longer exists is treated as no selection.
+        let selected = selected.filter(|_| found.is_some());
+        egui::CentralPanel::default().show_inside(ui, |ui| {
+            let Some((path_segments, selected_value)) = found else {
+                ui.weak("Select a value to see its details.");
+                return;
+            };
+            ScrollArea::vertical().auto_shrink(false).show(ui, |ui| {
+                let pointer = JsonPointer(&path_segments);
+                show_details(ui, id, pointer, selected_value);
+                if let Some(on_detail) = on_detail.as_mut() {
+                    ui.separator();
+                    on_detail(ui, pointer, selected_value);
+                }
+            });
+        });
+
+        JsonTreeSplitViewResponse {
+            tree_response,
+            selected,
+        }
+    }
+}
+
+fn show_details<T: ToJsonTreeValue>(ui: &mut Ui, id: Id, pointer: JsonPointer, value: &T) {
+    egui::Grid::new(id.with("details"))
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.strong("Pointer");
+            let pointer_str = pointer.to_json_pointer_string();
+            if pointer_str.is_empty() {
+                ui.weak("(root)");
+            } else {
+                ui.add(Label::new(RichText::new(pointer_str).monospace()).selectable(true));
+            }
+            ui.end_row();
+
+            ui.strong("Type");
+            ui.label(type_name(value));
+            ui.end_row();
+        });
+    ui.add_space(4.0);
+    ui.add(Label::new(RichText::new(to_json_string(value)).monospace()).selectable(true));
+}
+
+fn type_name<T: ToJsonTreeValue>(value: &T) -> String {
+    match value.to_json_tree_value() {
+        JsonTreeValue::Base(_, _, BaseValueType::Null) => "null".to_owned(),
+        JsonTreeValue::Base(_, _, BaseValueType::Bool) => "boolean".to_owned(),
+        JsonTreeValue::Base(_, _, BaseValueType::Number) => "number".to_owned(),
+        JsonTreeValue::Base(_, _, BaseValueType::String) => "string".to_owned(),
+        JsonTreeValue::Expandable(entries, ExpandableType::Array) => {
+            format!("array ({} elements)", entries.len())
+        }
+        JsonTreeValue::Expandable(entries, ExpandableType::Object) => {
+            format!("object ({} keys)", entries.len())
+        }
+    }
+}
+
+/// The response from showing a [`JsonTreeSplitView`].
+pub struct JsonTreeSplitViewResponse {
+    tree_response: JsonTreeResponse,
+    selected: Option<String>,
+}
+
+impl JsonTreeSplitViewResponse {
+    /// Returns the response of the tree.
+    pub fn tree_response(&self) -> &JsonTreeResponse {
+        &self.tree_response
+    }
+
+    /// Returns the JSON Pointer string of the selected value, if any.
+    pub fn selected_pointer(&self) -> Option<&str> {
+        self.selected.as_deref()
+    }
+}
+
+/// Selects the value whose array index, object key, value or delimiter is clicked, and highlights the selected value.
+/// See [`JsonTreeSplitView`].
+pub(crate) struct Selection {
+    id: Id,
+    /// The JSON Pointer string of the value selected as of the start of this frame.
+    selected: Option<String>,
+}
+
+impl Selection {
+    fn load(ctx: &Context, tree_id: Id) -> Self {
+        let id = tree_id.with("split_view_selection");
+        let selected = ctx.data(|d| d.get_temp::<String>(id));
+        Self { id, selected }
+    }
+
+    /// Selects the value of the response when it is clicked,
+    /// and highlights the response in the background shape reserved before it was rendered if its value is selected.
+    pub(crate) fn attach(
+        &self,
+        ui: &Ui,
+        response: &Response,
+        pointer: JsonPointer,
+        background: ShapeIdx,
+    ) {
+        let pointer_str = pointer.to_json_pointer_string();
+        if response.clicked() {
+            ui.data_mut(|d| d.insert_temp(self.id, pointer_str.clone()));
+            // Highlight the new selection straight away.
+            ui.ctx().request_repaint();
+        }
+        if self.selected.as_deref() == Some(pointer_str.as_str()) {
+            ui.painter().set(
+                background,
+                Shape::rect_filled(response.rect, 2.0, ui.visuals().selection.bg_fill),
+            );
+        }
+    }
+}
@@ -1,15 +1,42 @@
-use egui::{Color32, FontId, TextStyle, Ui};
+use std::{borrow::Cow, collections::HashMap, time::Duration};
 
-use crate::{render::ParentStatus, value::BaseValueType, ToggleButtonsState};
+use egui::{Color32, FontFamily, FontId, Stroke, TextStyle, Ui};
+
+use crate::{
+    pointer::{JsonPointer, JsonPointerSegment},
+    render::ParentStatus,
+    value::{BaseValueType, JsonTreeValue, ToJsonTreeValue},
+    ToggleButtonsState,
+};
 
 /// Styling configuration to control the appearance of the [`JsonTree`](crate::JsonTree).
 #[derive(Debug, Clone, Default)]
 pub struct JsonTreeStyle {
     pub visuals: Option<JsonTreeVisuals>,
     pub font_id: Option<FontId>,
+    pub font_families: JsonTreeFontFamilies,
     pub abbreviate_root: bool,
+    pub root_label: Option<String>,
+    pub abbreviation: Option<String>,
     pub toggle_buttons_state: ToggleButtonsState,
     pub wrapping_config: JsonTreeWrappingConfig,
+    pub uuid_display: UuidDisplay,
+    pub epoch_timestamps: EpochTimestampConfig,
+    pub show_hidden_match_counts: bool,
+    pub expansion_budget: Option<usize>,
+    pub frame_budget: Option<Duration>,
+    pub search_debounce: Option<Duration>,
+    pub high_contrast: bool,
+    pub density: JsonTreeDensity,
+    pub scroll_animation: JsonTreeScrollAnimation,
+    pub layout: JsonTreeLayout,
+    pub long_string_viewer: Option<JsonTreeLongStringViewer>,
+    pub bracket_matching: JsonTreeBracketMatching,
+    pub closing_delimiters: JsonTreeClosingDelimiters,
+    pub provenance_display: JsonTreeProvenanceDisplay,
+    pub drop_indicator_stroke: Option<Stroke>,
+    pub severity_styling: Option<JsonTreeSeverityStyling>,
+    pub low_cost: bool,
 }
 
 impl JsonTreeStyle {
@@ -29,6 +56,14 @@ impl JsonTreeStyle {
         self
     }
 
+    /// Override the font family of keys, values and punctuation independently,
+    /// e.g. to show proportional keys alongside monospace values.
+    /// Defaults to the family of the font set via [`JsonTreeStyle::font_id`] for all elements.
+    pub fn font_families(mut self, font_families: JsonTreeFontFamilies) -> Self {
+        self.font_families = font_families;
+        self
+    }
+
     /// Override whether a root array/object should show direct child elements when collapsed.
     ///
     /// If `true`, a collapsed root object would render as: `{...}`.
@@ -41,6 +76,24 @@ impl JsonTreeStyle {
         self
     }
 
+    /// Show a label before the root value, e.g. the name of the file it was loaded from,
+    /// so that the root renders as: `response body: {...}`.
+    ///
+    /// Defaults to no label.
+    pub fn root_label(mut self, root_label: impl Into<String>) -> Self {
+        self.root_label = Some(root_label.into());
+        self
+    }
+
+    /// Override the text shown within the brackets/braces of a collapsed non-empty array/object,
+    /// e.g. `"…"` to render a collapsed object as: `{…}`.
+    ///
+    /// Defaults to `"..."`.
+    pub fn abbreviation(mut self, abbreviation: impl Into<String>) -> Self {
+        self.abbreviation = Some(abbreviation.into());
+        self
+    }
+
     /// Override the visibility and interactivity of the toggle buttons for expanding/collapsing objects and arrays.
     /// Defaults to [`ToggleButtonsState::VisibleEnabled`].
     pub fn toggle_buttons_state(mut self, toggle_buttons_state: ToggleButtonsState) -> Self {
@@ -55,24 +108,271 @@ impl JsonTreeStyle {
         self
     }
 
+    /// Override how string values formatted as UUIDs are displayed.
+    /// Defaults to [`UuidDisplay::Plain`], i.e. UUIDs are displayed like any other string.
+    pub fn uuid_display(mut self, uuid_display: UuidDisplay) -> Self {
+        self.uuid_display = uuid_display;
+        self
+    }
+
+    /// Override which integer values are interpreted as Unix epoch timestamps,
+    /// and displayed alongside their formatted UTC date-time, e.g. `1700000000 (2023-11-14T22:13:20Z)`.
+    /// Defaults to not interpreting any values as timestamps.
+    pub fn epoch_timestamps(mut self, epoch_timestamps: EpochTimestampConfig) -> Self {
+        self.epoch_timestamps = epoch_timestamps;
+        self
+    }
+
+    /// Override whether collapsed arrays/objects show a badge with the number of search matches they contain,
+    /// e.g. after the user collapses an array/object that was expanded to reveal matches.
+    ///
+    /// Defaults to `false`.
+    pub fn show_hidden_match_counts(mut self, show_hidden_match_counts: bool) -> Self {
+        self.show_hidden_match_counts = show_hidden_match_counts;
+        self
+    }
+
+    /// Override the maximum number of array elements and object entries that are newly revealed per frame.
+    ///
+    /// When expanding an array/object with a very large number of descendants,
+    /// the entries are revealed progressively across multiple frames with a progress indicator,
+    /// instead of freezing the UI for a single long frame.
+    ///
+    /// Defaults to `None`, i.e. all entries are revealed immediately.
+    pub fn expansion_budget(mut self, expansion_budget: Option<usize>) -> Self {
+        self.expansion_budget = expansion_budget;
+        self
+    }
+
+    /// Override the time budget for showing the [`JsonTree`](crate::JsonTree) each frame.
+    ///
+    /// When the budget is exceeded, rendering degrades gracefully:
+    /// rows far outside of the visible area are replaced by blank placeholders,
+    /// and a collapsed root array/object is abbreviated instead of previewing its entries in the next frame.
+    /// Use [`JsonTreeResponse::is_degraded`](crate::JsonTreeResponse::is_degraded) to check whether this happened.
+    ///
+    /// Defaults to `None`, i.e. no budget.
+    pub fn frame_budget(mut self, frame_budget: Option<Duration>) -> Self {
+        self.frame_budget = frame_budget;
+        self
+    }
+
+    /// Override how long the search term must stay unchanged before it is searched for,
+    /// so that matches are not recomputed on every keystroke while the user types.
+    ///
+    /// Until then, the tree keeps showing the matches of the previous search term.
+    /// When the new search term is applied, the expanded state of all arrays/objects is reset to respect
+    /// [`DefaultExpand`](crate::DefaultExpand), so there is no need to call
+    /// [`JsonTreeResponse::reset_expanded`](crate::JsonTreeResponse::reset_expanded) when the search term changes.
+    ///
+    /// Defaults to `None`, i.e. the search term is applied straight away.
+    pub fn search_debounce(mut self, search_debounce: Option<Duration>) -> Self {
+        self.search_debounce = search_debounce;
+        self
+    }
+
+    /// Override whether the default color scheme is the high-contrast variant,
+    /// i.e. [`JsonTreeVisuals::HIGH_CONTRAST_DARK`] or [`JsonTreeVisuals::HIGH_CONTRAST_LIGHT`] depending on [`egui::Visuals::dark_mode`].
+    /// Has no effect if the colors are overridden via [`JsonTreeStyle::visuals`].
+    ///
+    /// Defaults to `false`.
+    pub fn high_contrast(mut self, high_contrast: bool) -> Self {
+        self.high_contrast = high_contrast;
+        self
+    }
+
+    /// Override how densely rows are packed, adjusting the font size, row spacing, indentation and toggle button size together.
+    /// Defaults to [`JsonTreeDensity::Comfortable`], i.e. egui's own spacing.
+    pub fn density(mut self, density: JsonTreeDensity) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Override how the view moves to a value requested via [`JsonTreeResponse::scroll_to_pointer`](crate::JsonTreeResponse::scroll_to_pointer),
+    /// and whether its row is briefly pulsed afterwards.
+    ///
+    /// Defaults to jumping immediately, without pulsing.
+    pub fn scroll_animation(mut self, scroll_animation: JsonTreeScrollAnimation) -> Self {
+        self.scroll_animation = scroll_animation;
+        self
+    }
+
+    /// Override how keys and values are laid out within each row,
+    /// e.g. to align all values in a column like a property grid.
+    /// Defaults to [`JsonTreeLayout::Inline`].
+    pub fn layout(mut self, layout: JsonTreeLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    /// Override whether very long string values, such as stack traces or HTML, are truncated to a single row
+    /// with a button to open them in a scrollable viewer beneath the row, which can be searched independently of the tree.
+    ///
+    /// Defaults to `None`, i.e. long strings are shown in full according to the [`JsonTreeWrappingConfig`].
+    pub fn long_string_viewer(
+        mut self,
+        long_string_viewer: Option<JsonTreeLongStringViewer>,
+    ) -> Self {
+        self.long_string_viewer = long_string_viewer;
+        self
+    }
+
+    /// Override whether hovering an opening or closing bracket/brace of an expanded array/object highlights its counterpart,
+    /// and optionally the background of the whole array/object, to help track the extent of large arrays/objects.
+    /// Defaults to [`JsonTreeBracketMatching::Off`].
+    pub fn bracket_matching(mut self, bracket_matching: JsonTreeBracketMatching) -> Self {
+        self.bracket_matching = bracket_matching;
+        self
+    }
+
+    /// Override where the closing bracket/brace of an expanded array/object is placed on its own row,
+    /// e.g. [`JsonTreeClosingDelimiters::AlignedWithOpener`] for classic pretty-printed JSON.
+    /// Defaults to [`JsonTreeClosingDelimiters::Compact`].
+    pub fn closing_delimiters(mut self, closing_delimiters: JsonTreeClosingDelimiters) -> Self {
+        self.closing_delimiters = closing_delimiters;
+        self
+    }
+
+    /// Override how the provenance of values configured via [`JsonTree::provenance`](crate::JsonTree::provenance) is shown.
+    /// Defaults to [`JsonTreeProvenanceDisplay::Suffix`].
+    pub fn provenance_display(mut self, provenance_display: JsonTreeProvenanceDisplay) -> Self {
+        self.provenance_display = provenance_display;
+        self
+    }
+
+    /// Override the stroke of the line indicating where a dragged item would be inserted,
+    /// while it hovers a tree that accepts drops via [`JsonTree::drop_targets`](crate::JsonTree::drop_targets).
+    /// Defaults to a 2 point line in the selection color of the `Ui`.
+    pub fn drop_indicator_stroke(mut self, drop_indicator_stroke: Stroke) -> Self {
+        self.drop_indicator_stroke = Some(drop_indicator_stroke);
+        self
+    }
+
+    /// Override whether the rows of log entries are tinted by their severity, e.g. red for `{ "level": "error", ... }`,
+    /// when showing arrays of log entries.
+    ///
+    /// Defaults to `None`, i.e. no rows are tinted. See [`JsonTreeSeverityStyling::default`] for the built-in rules.
+    pub fn severity_styling(mut self, severity_styling: Option<JsonTreeSeverityStyling>) -> Self {
+        self.severity_styling = severity_styling;
+        self
+    }
+
+    /// Override whether the tree is rendered with a low-cost profile, for weak hardware such as wasm on low-power devices,
+    /// where the full-featured rendering is too heavy. This disables, in one switch:
+    /// - Previews of the entries of a collapsed root array/object, which is abbreviated instead.
+    /// - Animations, i.e. of expanding/collapsing arrays/objects, and the [`JsonTreeStyle::scroll_animation`].
+    /// - Badges, i.e. [`JsonTreeStyle::show_hidden_match_counts`], [`JsonTree::numeric_summaries`](crate::JsonTree::numeric_summaries)
+    ///   and [`JsonTree::subtree_hashes`](crate::JsonTree::subtree_hashes).
+    /// - Hover effects, i.e. [`JsonTreeStyle::bracket_matching`].
+    ///
+    /// Defaults to `false`.
+    pub fn low_cost(mut self, low_cost: bool) -> Self {
+        self.low_cost = low_cost;
+        self
+    }
+
+    /// Resolves the scroll animation to use, which jumps immediately without pulsing if rendering is [`JsonTreeStyle::low_cost`].
+    pub(crate) fn resolve_scroll_animation(&self) -> JsonTreeScrollAnimation {
+        if self.low_cost {
+            JsonTreeScrollAnimation::default()
+        } else {
+            self.scroll_animation
+        }
+    }
+
+    /// Resolves the stroke of the drop indicator.
+    pub(crate) fn resolve_drop_indicator_stroke(&self, ui: &Ui) -> Stroke {
+        self.drop_indicator_stroke
+            .unwrap_or_else(|| Stroke::new(2.0, ui.visuals().selection.bg_fill))
+    }
+
+    /// Resolves the [`JsonTreeVisuals`] color scheme to use for an element,
+    /// highlighting search matches with the current match highlight color if it is within the current match.
+    pub(crate) fn resolve_match_visuals(
+        &self,
+        ui: &Ui,
+        is_current_match: bool,
+    ) -> Cow<'_, JsonTreeVisuals> {
+        let visuals = self.resolve_visuals(ui);
+        if is_current_match {
+            Cow::Owned(JsonTreeVisuals {
+                highlight_color: visuals.current_match_highlight_color,
+                key_highlight_color: None,
+                ..visuals.clone()
+            })
+        } else {
+            Cow::Borrowed(visuals)
+        }
+    }
+
     /// Resolves the [`JsonTreeVisuals`] color scheme to use.
     pub(crate) fn resolve_visuals(&self, ui: &Ui) -> &JsonTreeVisuals {
         if let Some(visuals) = &self.visuals {
             visuals
-        } else if ui.visuals().dark_mode {
-            &JsonTreeVisuals::DARK
         } else {
-            &JsonTreeVisuals::LIGHT
+            match (ui.visuals().dark_mode, self.high_contrast) {
+                (true, false) => &JsonTreeVisuals::DARK,
+                (false, false) => &JsonTreeVisuals::LIGHT,
+                (true, true) => &JsonTreeVisuals::HIGH_CONTRAST_DARK,
+                (false, true) => &JsonTreeVisuals::HIGH_CONTRAST_LIGHT,
+            }
         }
     }
 
-    /// Resolves the [`FontId`] to use.
+    /// Resolves the [`FontId`] to use, scaled according to the [`JsonTreeDensity`].
     pub(crate) fn resolve_font_id(&self, ui: &Ui) -> FontId {
-        if let Some(font_id) = &self.font_id {
+        let mut font_id = if let Some(font_id) = &self.font_id {
             font_id.clone()
         } else {
             TextStyle::Monospace.resolve(ui.style())
+        };
+        font_id.size *= self.density.font_scale();
+        font_id
+    }
+
+    /// Resolves the [`FontId`] to use for an element, with its family overridden if configured in [`JsonTreeFontFamilies`].
+    pub(crate) fn resolve_element_font_id(&self, ui: &Ui, family: &Option<FontFamily>) -> FontId {
+        let mut font_id = self.resolve_font_id(ui);
+        if let Some(family) = family {
+            font_id.family = family.clone();
         }
+        font_id
+    }
+
+    /// Adjusts the spacing of the `Ui` according to the [`JsonTreeDensity`].
+    pub(crate) fn apply_density(&self, ui: &mut Ui) {
+        Self::scale_spacing(ui, self.density.spacing_scale());
+    }
+
+    /// Adjusts the spacing of a `Ui` that was adjusted according to the [`JsonTreeDensity`] of the parent style
+    /// to follow the density of this style instead, e.g. within a subtree configured via [`JsonTree::subtree`](crate::JsonTree::subtree).
+    pub(crate) fn reapply_density(&self, ui: &mut Ui, parent: &JsonTreeStyle) {
+        Self::scale_spacing(
+            ui,
+            self.density.spacing_scale() / parent.density.spacing_scale(),
+        );
+    }
+
+    fn scale_spacing(ui: &mut Ui, spacing_scale: f32) {
+        let spacing = ui.spacing_mut();
+        spacing.item_spacing.y *= spacing_scale;
+        spacing.indent *= spacing_scale;
+        spacing.icon_width *= spacing_scale;
+        spacing.icon_width_inner *= spacing_scale;
+        spacing.icon_spacing *= spacing_scale;
+    }
+
+    /// Returns a copy of this style that truncates values to a single row, e.g. for a string shown in a [`JsonTreeLongStringViewer`].
+    pub(crate) fn with_single_row_values(&self) -> Self {
+        let mut style = self.clone();
+        for wrap in [
+            &mut style.wrapping_config.value_when_root,
+            &mut style.wrapping_config.value_with_expanded_parent,
+        ] {
+            wrap.max_rows = 1;
+            wrap.break_anywhere = true;
+        }
+        style
     }
 
     pub(crate) fn resolve_value_text_wrapping(
@@ -101,7 +401,9 @@ impl JsonTreeStyle {
 }
 
 /// Colors for JSON syntax highlighting, and search match highlighting.
-#[derive(Debug, Clone, Hash)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct JsonTreeVisuals {
     pub object_key_color: Color32,
     pub array_idx_color: Color32,
@@ -109,7 +411,16 @@ pub struct JsonTreeVisuals {
     pub bool_color: Color32,
     pub number_color: Color32,
     pub string_color: Color32,
+    /// The color for string values recognised as UUIDs, if enabled via [`JsonTreeStyle::uuid_display`].
+    pub uuid_color: Color32,
     pub highlight_color: Color32,
+    /// The highlight color for search matches within object keys and array indices,
+    /// to tell them apart from matches within values. Defaults to `None`, i.e. `highlight_color`.
+    pub key_highlight_color: Option<Color32>,
+    /// The highlight color for the current search match, as navigated to via
+    /// [`JsonTreeResponse::scroll_to_match`](crate::JsonTreeResponse::scroll_to_match),
+    /// to distinguish it from the other matches highlighted with `highlight_color`.
+    pub current_match_highlight_color: Color32,
     /// The color for array brackets, object braces, colons and commas.
     pub punctuation_color: Color32,
 }
@@ -128,7 +439,10 @@ impl JsonTreeVisuals {
         bool_color: Color32::from_rgb(103, 154, 209),
         number_color: Color32::from_rgb(181, 199, 166),
         string_color: Color32::from_rgb(194, 146, 122),
+        uuid_color: Color32::from_rgb(197, 134, 192),
         highlight_color: Color32::from_rgba_premultiplied(72, 72, 72, 50),
+        key_highlight_color: None,
+        current_match_highlight_color: Color32::from_rgb(110, 80, 20),
         punctuation_color: Color32::from_gray(140),
     };
 
@@ -139,10 +453,52 @@ impl JsonTreeVisuals {
         bool_color: Color32::from_rgb(40, 34, 245),
         number_color: Color32::from_rgb(1, 97, 63),
         string_color: Color32::from_rgb(149, 38, 31),
+        uuid_color: Color32::from_rgb(128, 35, 140),
         highlight_color: Color32::from_rgba_premultiplied(181, 213, 251, 255),
+        key_highlight_color: None,
+        current_match_highlight_color: Color32::from_rgb(255, 200, 120),
         punctuation_color: Color32::from_gray(70),
     };
 
+    /// A high-contrast color scheme for dark backgrounds,
+    /// where every text color has a contrast ratio of at least 7:1 against the background,
+    /// and at least 4.5:1 against the search match highlights.
+    pub const HIGH_CONTRAST_DARK: Self = Self {
+        object_key_color: Color32::from_rgb(156, 220, 254),
+        array_idx_color: Color32::from_rgb(204, 187, 255),
+        null_color: Color32::from_rgb(130, 200, 255),
+        bool_color: Color32::from_rgb(130, 200, 255),
+        number_color: Color32::from_rgb(181, 240, 160),
+        string_color: Color32::from_rgb(255, 204, 160),
+        uuid_color: Color32::from_rgb(255, 180, 255),
+        highlight_color: Color32::from_rgb(0, 70, 140),
+        key_highlight_color: None,
+        current_match_highlight_color: Color32::from_rgb(100, 30, 0),
+        punctuation_color: Color32::from_gray(220),
+    };
+
+    /// A high-contrast color scheme for light backgrounds,
+    /// where every text color has a contrast ratio of at least 7:1 against the background,
+    /// and at least 4.5:1 against the search match highlights.
+    pub const HIGH_CONTRAST_LIGHT: Self = Self {
+        object_key_color: Color32::from_rgb(0, 50, 130),
+        array_idx_color: Color32::from_rgb(120, 0, 80),
+        null_color: Color32::from_rgb(0, 0, 190),
+        bool_color: Color32::from_rgb(0, 0, 190),
+        number_color: Color32::from_rgb(0, 90, 30),
+        string_color: Color32::from_rgb(140, 20, 0),
+        uuid_color: Color32::from_rgb(100, 0, 120),
+        highlight_color: Color32::from_rgb(255, 230, 0),
+        key_highlight_color: None,
+        current_match_highlight_color: Color32::from_rgb(255, 200, 140),
+        punctuation_color: Color32::from_gray(30),
+    };
+
+    /// Returns the highlight color for search matches within object keys and array indices.
+    pub(crate) fn resolve_key_highlight_color(&self) -> Color32 {
+        self.key_highlight_color.unwrap_or(self.highlight_color)
+    }
+
     pub fn get_color(&self, base_value_type: &BaseValueType) -> Color32 {
         match base_value_type {
             BaseValueType::Null => self.null_color,
@@ -184,9 +540,328 @@ impl Default for JsonTreeWrapping {
     }
 }
 
+/// Setting for what is highlighted when hovering a bracket/brace of an expanded array/object.
+/// See [`JsonTreeStyle::bracket_matching`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JsonTreeBracketMatching {
+    /// Nothing is highlighted.
+    #[default]
+    Off,
+    /// The opening and closing brackets/braces are highlighted.
+    Brackets,
+    /// The opening and closing brackets/braces are highlighted, and the background of the array/object is faintly highlighted.
+    BracketsAndSubtree,
+}
+
+/// Setting for the placement of the closing bracket/brace of an expanded array/object,
+/// which is shown on its own row after the entries. See [`JsonTreeStyle::closing_delimiters`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JsonTreeClosingDelimiters {
+    /// The closing bracket/brace is tucked beneath the toggle button of the array/object.
+    #[default]
+    Compact,
+    /// The closing bracket/brace is aligned with the start of the row of its opening bracket/brace,
+    /// i.e. with the key or index of the array/object, as in pretty-printed JSON.
+    AlignedWithOpener,
+}
+
+/// Setting for how the provenance of values is shown. See [`JsonTreeStyle::provenance_display`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum JsonTreeProvenanceDisplay {
+    /// The provenance is shown as a subtle comment after the value, e.g. `// base.toml:12`.
+    #[default]
+    Suffix,
+    /// The provenance is shown in a tooltip when the row of the value is hovered.
+    Tooltip,
+}
+
+/// Configuration for showing long string values in a viewer. See [`JsonTreeStyle::long_string_viewer`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct JsonTreeLongStringViewer {
+    /// Strings with more characters than this are truncated to a single row, and can be opened in the viewer.
+    pub min_chars: usize,
+    /// The maximum height of the viewer, beyond which its contents scroll.
+    pub max_height: f32,
+}
+
+impl Default for JsonTreeLongStringViewer {
+    fn default() -> Self {
+        Self {
+            min_chars: 500,
+            max_height: 200.0,
+        }
+    }
+}
+
+/// Rules for tinting the rows of log entries by their severity. See [`JsonTreeStyle::severity_styling`].
+///
+/// A log entry is an object that is an element of an array, whose severity is the string value of one of its `fields`.
+/// All rows of a log entry are tinted, from its opening brace to its closing brace.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct JsonTreeSeverityStyling {
+    /// The keys of the object entries holding the severity of a log entry, in order of preference.
+    pub fields: Vec<String>,
+    /// The severities, matched case-insensitively, and the tint of the rows of log entries with each severity.
+    /// Log entries with any other severity are not tinted.
+    pub tints: Vec<(String, Color32)>,
+}
+
+impl Default for JsonTreeSeverityStyling {
+    /// Recognises the severity in the `level` or `severity` field of log entries,
+    /// tinting `error`, `fatal` and `critical` entries red, and `warn` and `warning` entries yellow.
+    fn default() -> Self {
+        let error = Color32::from_rgba_unmultiplied(255, 0, 0, 40);
+        let warning = Color32::from_rgba_unmultiplied(255, 200, 0, 40);
+        Self {
+            fields: vec!["level".to_owned(), "severity".to_owned()],
+            tints: vec![
+                ("error".to_owned(), error),
+                ("fatal".to_owned(), error),
+                ("critical".to_owned(), error),
+                ("warn".to_owned(), warning),
+                ("warning".to_owned(), warning),
+            ],
+        }
+    }
+}
+
+impl JsonTreeSeverityStyling {
+    /// Returns the tint of the log entry with the given entries, if it has a severity with a tint.
+    pub(crate) fn resolve_tint<T: ToJsonTreeValue>(
+        &self,
+        entries: &[(JsonPointerSegment, &T)],
+    ) -> Option<Color32> {
+        let severity = self.fields.iter().find_map(|field| {
+            entries.iter().find_map(|(property, value)| match property {
+                JsonPointerSegment::Key(key) if key == field => match value.to_json_tree_value() {
+                    JsonTreeValue::Base(_, display_value, BaseValueType::String) => {
+                        Some(display_value.to_string())
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+        })?;
+        self.tints
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(severity.trim()))
+            .map(|(_, tint)| *tint)
+    }
+}
+
 /// Options for controlling the max width of JSON elements.
 #[derive(Debug, Clone, Copy)]
 pub enum JsonTreeMaxWidth {
     Points(f32),
     UiAvailableWidth,
 }
+
+/// Font family overrides for the elements of a [`JsonTree`](crate::JsonTree).
+/// A value of `None` uses the family of the font set via [`JsonTreeStyle::font_id`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct JsonTreeFontFamilies {
+    /// The font family of object keys and array indices.
+    pub keys: Option<FontFamily>,
+    /// The font family of non-recursive JSON values.
+    pub values: Option<FontFamily>,
+    /// The font family of brackets, braces, colons and commas.
+    pub punctuation: Option<FontFamily>,
+}
+
+/// Presets for how densely the rows of a [`JsonTree`](crate::JsonTree) are packed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum JsonTreeDensity {
+    /// Smaller text and tighter spacing, e.g. for an inspector in a sidebar.
+    Compact,
+    /// egui's own text size and spacing.
+    #[default]
+    Comfortable,
+    /// Larger text and looser spacing, e.g. for a full-screen viewer.
+    Spacious,
+}
+
+impl JsonTreeDensity {
+    fn font_scale(self) -> f32 {
+        match self {
+            JsonTreeDensity::Compact => 0.85,
+            JsonTreeDensity::Comfortable => 1.0,
+            JsonTreeDensity::Spacious => 1.15,
+        }
+    }
+
+    fn spacing_scale(self) -> f32 {
+        match self {
+            JsonTreeDensity::Compact => 0.6,
+            JsonTreeDensity::Comfortable => 1.0,
+            JsonTreeDensity::Spacious => 1.5,
+        }
+    }
+}
+
+/// Setting for how keys and values are laid out within each row of a [`JsonTree`](crate::JsonTree).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum JsonTreeLayout {
+    /// Each value directly follows its key.
+    #[default]
+    Inline,
+    /// Keys and indentation are shown in a left column, and values in a right column, like a property grid.
+    /// The columns are separated by a splitter that can be dragged to resize the key column,
+    /// which is initially `key_column_width` points wide.
+    Columns { key_column_width: f32 },
+}
+
+/// Configuration for how the view moves to a value requested via [`JsonTreeResponse::scroll_to_pointer`](crate::JsonTreeResponse::scroll_to_pointer),
+/// so that users keep their sense of place in large documents.
+#[derive(Debug, Clone, Copy)]
+pub struct JsonTreeScrollAnimation {
+    /// How long it takes to scroll the row of the value to the centre of the visible area.
+    /// [`Duration::ZERO`] jumps immediately.
+    pub duration: Duration,
+    /// Maps linear progress from `0.0` to `1.0` to eased progress, e.g. one of the functions in [`egui::emath::easing`].
+    pub easing: fn(f32) -> f32,
+    /// How long the row of the value is pulsed with the search match highlight color once scrolling has finished.
+    /// [`Duration::ZERO`] disables pulsing.
+    pub pulse_duration: Duration,
+}
+
+impl Default for JsonTreeScrollAnimation {
+    fn default() -> Self {
+        Self {
+            duration: Duration::ZERO,
+            easing: egui::emath::easing::cubic_in_out,
+            pulse_duration: Duration::ZERO,
+        }
+    }
+}
+
+/// Setting for how string values formatted as UUIDs, e.g. `"550e8400-e29b-41d4-a716-446655440000"`, are displayed.
+///
+/// A UUID can be copied without quotes via the "Copy UUID" entry of the [`JsonTree::context_menu`](crate::JsonTree::context_menu).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UuidDisplay {
+    /// Display UUIDs like any other string.
+    #[default]
+    Plain,
+    /// Display UUIDs in full, using [`JsonTreeVisuals::uuid_color`].
+    Highlighted,
+    /// Display UUIDs shortened to their first and last groups, e.g. `"550e8400…446655440000"`,
+    /// using [`JsonTreeVisuals::uuid_color`]. The full UUID is shown on hover.
+    Shortened,
+}
+
+/// Configuration for interpreting integer values as Unix epoch timestamps.
+///
+/// Interpreted values are displayed alongside their formatted UTC date-time.
+/// Searching and any render hooks still operate on the original value.
+#[derive(Debug, Clone, Default)]
+pub struct EpochTimestampConfig {
+    /// The unit used to interpret all integer values, or `None` to not interpret them as timestamps.
+    pub unit: Option<EpochUnit>,
+    /// Overrides of `unit` for values at specific JSON pointer strings, e.g. `"/created_at"`.
+    /// An override of `None` stops the value at that pointer from being interpreted as a timestamp.
+    pub pointer_overrides: HashMap<String, Option<EpochUnit>>,
+}
+
+impl EpochTimestampConfig {
+    /// Resolves the unit to use for the value at the given pointer, if it should be interpreted as a timestamp.
+    pub(crate) fn resolve_unit(&self, pointer: &JsonPointer) -> Option<EpochUnit> {
+        if self.pointer_overrides.is_empty() {
+            return self.unit;
+        }
+
+        match self
+            .pointer_overrides
+            .get(&pointer.to_json_pointer_string())
+        {
+            Some(unit) => *unit,
+            None => self.unit,
+        }
+    }
+}
+
+/// The unit of a Unix epoch timestamp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EpochUnit {
+    /// Guess the unit from the magnitude of the value:
+    /// - Integers from `10^9` up to `10^11` are interpreted as seconds.
+    /// - Integers from `10^11` up to `10^14` are interpreted as milliseconds.
+    ///
+    /// Integers outside of these ranges are not interpreted as timestamps.
+    Auto,
+    Seconds,
+    Milliseconds,
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::{Color32, Visuals};
+
+    use super::JsonTreeVisuals;
+
+    /// The WCAG 2 relative luminance of an opaque color.
+    fn relative_luminance(color: Color32) -> f32 {
+        let channel = |c: u8| {
+            let c = c as f32 / 255.0;
+            if c <= 0.03928 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * channel(color.r()) + 0.7152 * channel(color.g()) + 0.0722 * channel(color.b())
+    }
+
+    fn contrast_ratio(a: Color32, b: Color32) -> f32 {
+        let (a, b) = (relative_luminance(a), relative_luminance(b));
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    fn assert_high_contrast(visuals: &JsonTreeVisuals, background: Color32) {
+        let text_colors = [
+            visuals.object_key_color,
+            visuals.array_idx_color,
+            visuals.null_color,
+            visuals.bool_color,
+            visuals.number_color,
+            visuals.string_color,
+            visuals.uuid_color,
+            visuals.punctuation_color,
+        ];
+        for color in text_colors {
+            assert!(contrast_ratio(color, background) >= 7.0, "{color:?}");
+            assert!(
+                contrast_ratio(color, visuals.highlight_color) >= 4.5,
+                "{color:?}"
+            );
+            assert!(
+                contrast_ratio(color, visuals.current_match_highlight_color) >= 4.5,
+                "{color:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn high_contrast_dark_meets_contrast_ratios() {
+        assert_high_contrast(
+            &JsonTreeVisuals::HIGH_CONTRAST_DARK,
+            Visuals::dark().panel_fill,
+        );
+    }
+
+    #[test]
+    fn high_contrast_light_meets_contrast_ratios() {
+        assert_high_contrast(
+            &JsonTreeVisuals::HIGH_CONTRAST_LIGHT,
+            Visuals::light().panel_fill,
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use egui::Ui;
+
+use crate::{
+    identity::segment_matches,
+    pointer::{parse_json_pointer_string, JsonPointerSegment},
+    render::{RenderContext, RenderHook},
+    value::ToJsonTreeValue,
+    ExpandOverride, JsonTreeStyle,
+};
+
+/// Configuration for the subtree beneath a JSON Pointer string, overriding that of the [`JsonTree`](crate::JsonTree) within it,
+/// e.g. to render a `/raw` payload compact and dark while the rest of the document renders as normal.
+/// See [`JsonTree::subtree`](crate::JsonTree::subtree).
+///
+/// Options that are not configured are inherited from the tree, or from the subtree containing this one.
+/// The expanded state of arrays/objects within the subtree is managed by the tree as usual,
+/// e.g. it is reset by [`JsonTreeResponse::reset_expanded`](crate::JsonTreeResponse::reset_expanded).
+#[must_use]
+pub struct JsonTreeSubtreeConfig<'a, T: ToJsonTreeValue> {
+    pub(crate) style: Option<JsonTreeStyle>,
+    pub(crate) default_expand: Option<ExpandOverride>,
+    pub(crate) render_hook: Option<Box<RenderHook<'a, T>>>,
+}
+
+impl<T: ToJsonTreeValue> Default for JsonTreeSubtreeConfig<'_, T> {
+    fn default() -> Self {
+        Self {
+            style: None,
+            default_expand: None,
+            render_hook: None,
+        }
+    }
+}
+
+impl<'a, T: ToJsonTreeValue> JsonTreeSubtreeConfig<'a, T> {
+    /// Creates a new [`JsonTreeSubtreeConfig`] that inherits all options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override the style of the tree within the subtree.
+    ///
+    /// Options that apply to the tree as a whole, such as its [`JsonTreeLayout`](crate::JsonTreeLayout),
+    /// search debounce and budgets, are taken from the style of the tree.
+    pub fn style(mut self, style: JsonTreeStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Override how arrays/objects within the subtree are expanded by default, including the array/object at its pointer.
+    ///
+    /// This only takes effect once the arrays/objects containing the subtree are expanded.
+    pub fn default_expand(mut self, default_expand: ExpandOverride) -> Self {
+        self.default_expand = Some(default_expand);
+        self
+    }
+
+    /// Customise rendering within the subtree, in place of the hook registered via [`JsonTree::on_render`](crate::JsonTree::on_render).
+    pub fn on_render(
+        mut self,
+        render_hook: impl FnMut(&mut Ui, RenderContext<'a, '_, T>) + 'a,
+    ) -> Self {
+        self.render_hook = Some(Box::new(render_hook));
+        self
+    }
+}
+
+/// The render hooks of the configured subtrees, by the index of the subtree.
+pub(crate) type SubtreeRenderHooks<'a, T> = Vec<Option<Box<RenderHook<'a, T>>>>;
+
+/// The options of a [`JsonTreeSubtreeConfig`] that do not depend on the JSON type, keyed by the parsed JSON Pointer of the subtree.
+/// Its render hook is kept by the renderer, at the same index as this override.
+pub(crate) struct SubtreeOverride {
+    /// The unescaped segments of the JSON Pointer string of the subtree.
+    pub(crate) segments: Vec<String>,
+    pub(crate) style: Option<JsonTreeStyle>,
+    pub(crate) default_expand: Option<ExpandOverride>,
+}
+
+/// Splits the configured subtrees into their overrides and render hooks, in the same order, ignoring invalid JSON Pointer strings.
+pub(crate) fn split_subtrees<'a, T: ToJsonTreeValue>(
+    subtrees: Vec<(&str, JsonTreeSubtreeConfig<'a, T>)>,
+) -> (Vec<SubtreeOverride>, SubtreeRenderHooks<'a, T>) {
+    subtrees
+        .into_iter()
+        .filter_map(|(pointer, config)| {
+            let subtree = SubtreeOverride {
+                segments: parse_json_pointer_string(pointer)?,
+                style: config.style,
+                default_expand: config.default_expand,
+            };
+            Some((subtree, config.render_hook))
+        })
+        .unzip()
+}
+
+/// Returns the index of the first subtree whose pointer is that of the path, if any.
+pub(crate) fn find_subtree(
+    subtrees: &[SubtreeOverride],
+    path_segments: &[JsonPointerSegment],
+) -> Option<usize> {
+    subtrees.iter().position(|subtree| {
+        subtree.segments.len() == path_segments.len()
+            && path_segments
+                .iter()
+                .zip(&subtree.segments)
+                .all(|(segment, unescaped)| segment_matches(segment, unescaped))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_subtree_by_exact_pointer() {
+        let subtrees = vec![
+            SubtreeOverride {
+                segments: vec!["raw".to_owned()],
+                style: None,
+                default_expand: None,
+            },
+            SubtreeOverride {
+                segments: vec!["raw".to_owned(), "0".to_owned()],
+                style: None,
+                default_expand: Some(ExpandOverride::All),
+            },
+        ];
+
+        assert_eq!(
+            find_subtree(&subtrees, &[JsonPointerSegment::Key("raw")]),
+            Some(0)
+        );
+        assert_eq!(
+            find_subtree(
+                &subtrees,
+                &[JsonPointerSegment::Key("raw"), JsonPointerSegment::Index(0)]
+            ),
+            Some(1)
+        );
+        assert_eq!(find_subtree(&subtrees, &[]), None);
+        assert_eq!(
+            find_subtree(&subtrees, &[JsonPointerSegment::Key("meta")]),
+            None
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+
+use egui::{Id, Ui};
+
+use crate::value::{BaseValueType, ExpandableType, JsonTreeValue, ToJsonTreeValue};
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// A short hash of the content of an array/object, which is the same for equal arrays/objects,
+/// regardless of the order of object keys, and is stable across runs of the app.
+/// See [`JsonTree::subtree_hashes`](crate::JsonTree::subtree_hashes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct SubtreeHash(u64);
+
+impl SubtreeHash {
+    pub(crate) fn compute<T: ToJsonTreeValue>(value: &T) -> Self {
+        Self(hash_value(value))
+    }
+
+    /// Returns the hash of the array/object with this path id, computing it only if it was not already computed
+    /// for the same version of the value.
+    /// If no version is given, the hash is computed every frame.
+    pub(crate) fn load_or_compute<T: ToJsonTreeValue>(
+        ui: &Ui,
+        path_id: Id,
+        value_version: Option<u64>,
+        value: &T,
+    ) -> Self {
+        let Some(value_version) = value_version else {
+            return Self::compute(value);
+        };
+
+        let id = path_id.with("subtree_hash");
+        if let Some((version, hash)) = ui.data(|d| d.get_temp::<(u64, Self)>(id)) {
+            if version == value_version {
+                return hash;
+            }
+        }
+        let hash = Self::compute(value);
+        ui.data_mut(|d| d.insert_temp(id, (value_version, hash)));
+        hash
+    }
+}
+
+impl fmt::Display for SubtreeHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Fold the hash to 32 bits, which is plenty to tell sections apart at a glance.
+        write!(f, "#{:08x}", (self.0 >> 32) ^ (self.0 & 0xffff_ffff))
+    }
+}
+
+/// A 64-bit FNV-1a hasher, used rather than the standard library's hashers so that hashes are the same in every run.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    /// Writes a string followed by a terminator, so that adjacent strings cannot run together.
+    fn write_str(&mut self, s: &str) {
+        self.write(s.as_bytes());
+        self.write(&[0xff]);
+    }
+}
+
+fn hash_value<T: ToJsonTreeValue>(value: &T) -> u64 {
+    let mut hasher = Fnv1a(FNV_OFFSET_BASIS);
+    match value.to_json_tree_value() {
+        JsonTreeValue::Base(_, display_value, value_type) => {
+            let tag = match value_type {
+                BaseValueType::Null => b'n',
+                BaseValueType::Bool => b'b',
+                BaseValueType::Number => b'd',
+                BaseValueType::String => b's',
+            };
+            hasher.write(&[tag]);
+            hasher.write_str(&display_value.to_string());
+        }
+        JsonTreeValue::Expandable(entries, ExpandableType::Array) => {
+            hasher.write(b"a");
+            hasher.write(&entries.len().to_le_bytes());
+            for (_, elem) in entries {
+                hasher.write(&hash_value(elem).to_le_bytes());
+            }
+        }
+        JsonTreeValue::Expandable(entries, ExpandableType::Object) => {
+            hasher.write(b"o");
+            hasher.write(&entries.len().to_le_bytes());
+            let mut entries = entries
+                .into_iter()
+                .map(|(property, elem)| (property.to_string(), hash_value(elem)))
+                .collect::<Vec<_>>();
+            entries.sort();
+            for (key, elem_hash) in entries {
+                hasher.write_str(&key);
+                hasher.write(&elem_hash.to_le_bytes());
+            }
+        }
+    }
+    hasher.0
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::json;
+
+    use super::SubtreeHash;
+
+    #[test]
+    fn hashes_equal_values_equally() {
+        let hash = |value| SubtreeHash::compute(&value);
+
+        assert_eq!(
+            hash(json!({"a": 1, "b": [true, null]})),
+            hash(json!({"b": [true, null], "a": 1}))
+        );
+        assert_ne!(hash(json!({"a": 1})), hash(json!({"a": "1"})));
+        assert_ne!(hash(json!([1, 2])), hash(json!([2, 1])));
+        assert_ne!(hash(json!(["ab", "c"])), hash(json!(["a", "bc"])));
+        assert_eq!(hash(json!([])).to_string().len(), 9);
+    }
+}
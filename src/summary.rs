@@ -0,0 +1,123 @@
+use std::fmt;
+
+use egui::{Id, Ui};
+
+use crate::{
+    pointer::JsonPointerSegment,
+    value::{BaseValueType, JsonTreeValue, ToJsonTreeValue},
+};
+
+/// Statistics of an array whose elements are all numbers.
+/// See [`JsonTree::numeric_summaries`](crate::JsonTree::numeric_summaries).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct NumericSummary {
+    count: usize,
+    min: f64,
+    max: f64,
+    mean: f64,
+    sum: f64,
+}
+
+impl NumericSummary {
+    /// Returns the summary of the elements of an array, if it is non-empty and all of its elements are numbers.
+    pub(crate) fn compute<T: ToJsonTreeValue>(
+        entries: &[(JsonPointerSegment, &T)],
+    ) -> Option<Self> {
+        if entries.is_empty() {
+            return None;
+        }
+
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        let mut sum = 0.0;
+        for (_, elem) in entries {
+            let JsonTreeValue::Base(_, display_value, BaseValueType::Number) =
+                elem.to_json_tree_value()
+            else {
+                return None;
+            };
+            let number = display_value.to_string().parse::<f64>().ok()?;
+            min = min.min(number);
+            max = max.max(number);
+            sum += number;
+        }
+
+        let count = entries.len();
+        Some(Self {
+            count,
+            min,
+            max,
+            mean: sum / count as f64,
+            sum,
+        })
+    }
+
+    /// Returns the summary of the array with this path id, computing it only if it was not already computed
+    /// for the same version of the value.
+    /// If no version is given, the summary is computed every frame.
+    pub(crate) fn load_or_compute<T: ToJsonTreeValue>(
+        ui: &Ui,
+        path_id: Id,
+        value_version: Option<u64>,
+        entries: &[(JsonPointerSegment, &T)],
+    ) -> Option<Self> {
+        let Some(value_version) = value_version else {
+            return Self::compute(entries);
+        };
+
+        let id = path_id.with("numeric_summary");
+        if let Some((version, summary)) = ui.data(|d| d.get_temp::<(u64, Option<Self>)>(id)) {
+            if version == value_version {
+                return summary;
+            }
+        }
+        let summary = Self::compute(entries);
+        ui.data_mut(|d| d.insert_temp(id, (value_version, summary)));
+        summary
+    }
+}
+
+impl fmt::Display for NumericSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "count {}, min {}, max {}, mean {}, sum {}",
+            self.count,
+            self.min,
+            self.max,
+            round(self.mean),
+            round(self.sum)
+        )
+    }
+}
+
+/// Rounds to a few decimal places, to hide floating point error accumulated while summing.
+fn round(number: f64) -> f64 {
+    (number * 1e6).round() / 1e6
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::json;
+
+    use crate::value::{JsonTreeValue, ToJsonTreeValue};
+
+    use super::NumericSummary;
+
+    fn summarize(value: &serde_json::Value) -> Option<NumericSummary> {
+        let JsonTreeValue::Expandable(entries, _) = value.to_json_tree_value() else {
+            return None;
+        };
+        NumericSummary::compute(&entries)
+    }
+
+    #[test]
+    fn summarizes_arrays_of_numbers_only() {
+        assert_eq!(
+            summarize(&json!([0.1, 0.2, -3])).unwrap().to_string(),
+            "count 3, min -3, max 0.2, mean -0.9, sum -2.7"
+        );
+        assert_eq!(summarize(&json!([1, "2"])), None);
+        assert_eq!(summarize(&json!([])), None);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::{
+    pointer::JsonPointerSegment,
+    value::{
+        to_compact_json_string, BaseValueType, ExpandableType, JsonTreeValue, ToJsonTreeValue,
+    },
+};
+
+/// Infers the columns of a table from a non-empty array whose elements are all objects,
+/// as the union of their keys in the order they are first seen.
+/// Returns `None` if the value cannot be shown as a table.
+pub(crate) fn infer_columns<T: ToJsonTreeValue>(value: &T) -> Option<Vec<String>> {
+    let JsonTreeValue::Expandable(rows, ExpandableType::Array) = value.to_json_tree_value() else {
+        return None;
+    };
+    if rows.is_empty() {
+        return None;
+    }
+
+    let mut columns: Vec<String> = vec![];
+    for (_, row) in rows {
+        let JsonTreeValue::Expandable(entries, ExpandableType::Object) = row.to_json_tree_value()
+        else {
+            return None;
+        };
+        for (property, _) in entries {
+            if let JsonPointerSegment::Key(key) = property {
+                if !columns.iter().any(|column| column == key) {
+                    columns.push(key.to_string());
+                }
+            }
+        }
+    }
+    Some(columns)
+}
+
+/// The text format of a table copied to the clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Delimited {
+    /// Comma-separated values, quoted as per RFC 4180.
+    Csv,
+    /// Tab-separated values, with tabs and line breaks within cells replaced by spaces.
+    Tsv,
+}
+
+impl Delimited {
+    fn separator(self) -> char {
+        match self {
+            Delimited::Csv => ',',
+            Delimited::Tsv => '\t',
+        }
+    }
+
+    fn push_cell(self, text: &mut String, cell: &str) {
+        match self {
+            Delimited::Csv if cell.contains([',', '"', '\n', '\r']) => {
+                text.push('"');
+                text.push_str(&cell.replace('"', "\"\""));
+                text.push('"');
+            }
+            Delimited::Csv => text.push_str(cell),
+            Delimited::Tsv => text.push_str(&cell.replace(['\t', '\n', '\r'], " ")),
+        }
+    }
+}
+
+/// Flattens an array of objects into a header row of the inferred columns followed by a row per element,
+/// for pasting into spreadsheets. Strings are unquoted, nulls and missing keys are empty,
+/// and nested arrays/objects are written as compact JSON.
+/// Returns `None` if the value cannot be shown as a table, see [`infer_columns`].
+pub(crate) fn to_delimited_string<T: ToJsonTreeValue>(
+    value: &T,
+    format: Delimited,
+) -> Option<String> {
+    let columns = infer_columns(value)?;
+    let JsonTreeValue::Expandable(rows, _) = value.to_json_tree_value() else {
+        return None;
+    };
+
+    let mut text = String::new();
+    for (idx, column) in columns.iter().enumerate() {
+        if idx > 0 {
+            text.push(format.separator());
+        }
+        format.push_cell(&mut text, column);
+    }
+
+    for (_, row) in rows {
+        text.push_str("\r\n");
+        let JsonTreeValue::Expandable(entries, _) = row.to_json_tree_value() else {
+            return None;
+        };
+        for (idx, column) in columns.iter().enumerate() {
+            if idx > 0 {
+                text.push(format.separator());
+            }
+            let cell = entries
+                .iter()
+                .find(|(property, _)| matches!(property, JsonPointerSegment::Key(key) if key == column))
+                .map(|(_, elem)| cell_text(*elem))
+                .unwrap_or_default();
+            format.push_cell(&mut text, &cell);
+        }
+    }
+    Some(text)
+}
+
+fn cell_text<T: ToJsonTreeValue>(value: &T) -> String {
+    match value.to_json_tree_value() {
+        JsonTreeValue::Base(_, _, BaseValueType::Null) => String::new(),
+        JsonTreeValue::Base(_, display_value, _) => display_value.to_string(),
+        JsonTreeValue::Expandable(..) => to_compact_json_string(value),
+    }
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::json;
+
+    use super::{infer_columns, to_delimited_string, Delimited};
+
+    #[test]
+    fn infers_columns_of_arrays_of_objects() {
+        let value = json!([{"a": 1, "b": 2}, {"c": 3, "a": 4}]);
+        assert_eq!(
+            infer_columns(&value),
+            Some(vec!["a".to_string(), "b".to_string(), "c".to_string()])
+        );
+        assert_eq!(infer_columns(&json!([])), None);
+        assert_eq!(infer_columns(&json!([{"a": 1}, 2])), None);
+        assert_eq!(infer_columns(&json!({"a": {"b": 1}})), None);
+    }
+
+    #[test]
+    fn flattens_arrays_of_objects() {
+        let value = json!([
+            {"name": "Ann, Jr.", "tags": ["x"], "age": 30},
+            {"name": "Bob\t\"B\"", "age": null},
+            {"name": "Cy", "extra": true}
+        ]);
+        assert_eq!(
+            to_delimited_string(&value, Delimited::Csv).unwrap(),
+            "age,name,tags,extra\r\n30,\"Ann, Jr.\",\"[\"\"x\"\"]\",\r\n,\"Bob\t\"\"B\"\"\",,\r\n,Cy,,true"
+        );
+        assert_eq!(
+            to_delimited_string(&value, Delimited::Tsv).unwrap(),
+            "age\tname\ttags\textra\r\n30\tAnn, Jr.\t[\"x\"]\t\r\n\tBob \"B\"\t\t\r\n\tCy\t\ttrue"
+        );
+        assert_eq!(to_delimited_string(&json!([1, 2]), Delimited::Csv), None);
+    }
+}
@@ -1,5 +1,6 @@
 /// Setting for the visibility and interactivity of the toggle buttons for expanding/collapsing objects and arrays.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ToggleButtonsState {
     #[default]
     VisibleEnabled,
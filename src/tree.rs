@@ -1,16 +1,52 @@
 use crate::{
-    node::JsonTreeNode,
+    context_menu::ContextMenuEntry,
+    document::ChangeLog,
+    drop_target::AcceptsPayload,
+    empty::{EmptyPlaceholder, JsonTreeEmptyReason},
+    node::{EntryGroups, JsonTreeNode, Provenance, ToggleHook},
+    pointer::JsonPointer,
     render::{JsonTreeRenderer, RenderContext},
+    schema_fields::{JsonTreeSchemaField, SchemaFields},
+    subtree::JsonTreeSubtreeConfig,
     value::ToJsonTreeValue,
-    DefaultExpand, JsonTreeResponse, JsonTreeStyle,
+    DefaultExpand, JsonTreeBackgroundSearch, JsonTreeDocument, JsonTreeNumericDrag,
+    JsonTreeResponse, JsonTreeSharedSearch, JsonTreeStyle,
 };
-use egui::{Id, Ui};
-use std::hash::Hash;
+use egui::{Color32, DragAndDrop, Id, Modifiers, Ui, WidgetText};
+use std::{any::Any, cell::RefCell, hash::Hash};
 
 pub(crate) struct JsonTreeConfig<'a, T: ToJsonTreeValue> {
     pub(crate) style: Option<JsonTreeStyle>,
     pub(crate) default_expand: Option<DefaultExpand<'a>>,
+    pub(crate) search_term: Option<&'a str>,
+    pub(crate) search_root: Option<&'a str>,
+    pub(crate) highlight_only: bool,
+    pub(crate) shared_search: Option<JsonTreeSharedSearch>,
+    pub(crate) background_search: Option<JsonTreeBackgroundSearch>,
     pub(crate) renderer: JsonTreeRenderer<'a, T>,
+    pub(crate) filter_search_results: bool,
+    pub(crate) count_hidden_entries: bool,
+    pub(crate) focus_follows_search: bool,
+    pub(crate) scroll_to_first_match: bool,
+    pub(crate) record_snapshot: bool,
+    pub(crate) scroll_markers: Vec<(String, Color32)>,
+    pub(crate) search_match_scroll_marker_color: Option<Color32>,
+    pub(crate) entry_groups: Option<Box<EntryGroups<'a>>>,
+    pub(crate) element_identities: Vec<(String, String)>,
+    pub(crate) numeric_summaries: bool,
+    pub(crate) subtree_hashes: bool,
+    pub(crate) condense_search_paths: bool,
+    pub(crate) value_version: Option<u64>,
+    pub(crate) document_changes: Option<ChangeLog>,
+    pub(crate) empty_placeholder: Option<Box<EmptyPlaceholder<'a>>>,
+    pub(crate) schema_fields: Option<Box<SchemaFields<'a>>>,
+    pub(crate) provenance: Option<Box<Provenance<'a>>>,
+    pub(crate) on_toggle: Option<Box<ToggleHook<'a>>>,
+    pub(crate) recursive_toggle_modifiers: Option<Modifiers>,
+    pub(crate) accepts_drop: Option<AcceptsPayload>,
+    pub(crate) subtrees: Vec<(&'a str, JsonTreeSubtreeConfig<'a, T>)>,
+    #[cfg(feature = "persistence")]
+    pub(crate) expansion_state: Option<&'a crate::JsonTreeExpansionState>,
 }
 
 impl<'a, T: ToJsonTreeValue> Default for JsonTreeConfig<'a, T> {
@@ -18,7 +54,35 @@ impl<'a, T: ToJsonTreeValue> Default for JsonTreeConfig<'a, T> {
         Self {
             style: Default::default(),
             default_expand: Default::default(),
+            search_term: None,
+            search_root: None,
+            highlight_only: false,
+            shared_search: None,
+            background_search: None,
             renderer: Default::default(),
+            filter_search_results: false,
+            count_hidden_entries: false,
+            focus_follows_search: false,
+            scroll_to_first_match: false,
+            record_snapshot: false,
+            scroll_markers: vec![],
+            search_match_scroll_marker_color: None,
+            entry_groups: None,
+            element_identities: vec![],
+            numeric_summaries: false,
+            subtree_hashes: false,
+            condense_search_paths: false,
+            value_version: None,
+            document_changes: None,
+            empty_placeholder: None,
+            schema_fields: None,
+            provenance: None,
+            on_toggle: None,
+            recursive_toggle_modifiers: None,
+            accepts_drop: None,
+            subtrees: vec![],
+            #[cfg(feature = "persistence")]
+            expansion_state: None,
         }
     }
 }
@@ -54,6 +118,451 @@ impl<'a, T: ToJsonTreeValue> JsonTree<'a, T> {
         self
     }
 
+    /// Highlight matches of a search term, independently of how arrays/objects are expanded by default,
+    /// e.g. to highlight matches while expanding via [`DefaultExpand::ToLevel`].
+    /// Letter case is ignored, unless matched via the options of [`DefaultExpand::SearchResultsWith`].
+    ///
+    /// If [`DefaultExpand::SearchResults`] is also used, this search term takes precedence over its search term,
+    /// both for highlighting and for expanding arrays/objects to reveal matches.
+    pub fn search_term(mut self, search_term: &'a str) -> Self {
+        self.config.search_term = Some(search_term);
+        self
+    }
+
+    /// Use the search term of a [`JsonTreeSharedSearch`], so that multiple trees can be searched at once.
+    /// This takes precedence over [`JsonTree::search_term`] and the search term of [`DefaultExpand::SearchResults`].
+    ///
+    /// Whenever the shared search term changes, the expanded state of this tree is reset to respect the `default_expand` setting.
+    pub fn shared_search(mut self, shared_search: &JsonTreeSharedSearch) -> Self {
+        self.config.shared_search = Some(shared_search.clone());
+        self
+    }
+
+    /// Show the results of a search performed on a background thread, instead of searching the value while it is shown,
+    /// e.g. for a document so large that searching it would block the UI. See [`JsonTreeBackgroundSearch`].
+    ///
+    /// The search term and options of the latest completed search override any given via [`JsonTree::search_term`],
+    /// [`DefaultExpand::SearchResults`] or [`DefaultExpand::SearchResultsWith`], but as with [`JsonTree::shared_search`],
+    /// [`JsonTree::default_expand`] must be one of these search variants for the matches to be expanded.
+    /// The whole value is searched, regardless of [`JsonTree::search_within`].
+    pub fn background_search(mut self, background_search: &JsonTreeBackgroundSearch) -> Self {
+        self.config.background_search = Some(background_search.clone());
+        self
+    }
+
+    /// Only match the search term against the descendants of the array/object at the given JSON Pointer string,
+    /// e.g. `"/summary"` to keep matches in a huge `"/raw_payload"` from polluting the results.
+    ///
+    /// Matches are neither expanded nor highlighted elsewhere. If the pointer is not valid, or there is no array/object there,
+    /// nothing matches. This does not apply to [`DefaultExpand::JsonPath`].
+    pub fn search_within(mut self, pointer: &'a str) -> Self {
+        self.config.search_root = Some(pointer);
+        self
+    }
+
+    /// If `true`, the search term only highlights matches within rows that are already visible,
+    /// leaving the expanded state of the tree completely untouched so that it can be scanned manually.
+    ///
+    /// The document is not searched up front, so arrays/objects are not expanded to reveal matches
+    /// (even with [`DefaultExpand::SearchResults`], which then expands nothing by default),
+    /// no entries are hidden by [`JsonTree::filter_search_results`], and no hidden match counts are shown.
+    ///
+    /// Defaults to `false`.
+    pub fn highlight_only(mut self, highlight_only: bool) -> Self {
+        self.config.highlight_only = highlight_only;
+        self
+    }
+
+    /// If `true`, searching via [`DefaultExpand::SearchResults`] or [`JsonTree::search_term`] hides all object entries and array elements
+    /// that neither match the search term nor contain a match, leaving only the matches and their ancestors visible.
+    /// The contents of a matched array/object are shown in full.
+    ///
+    /// If `false`, matches are expanded in place. Defaults to `false`.
+    pub fn filter_search_results(mut self, filter_search_results: bool) -> Self {
+        self.config.filter_search_results = filter_search_results;
+        self
+    }
+
+    /// If `true` while search results are filtered via [`JsonTree::filter_search_results`],
+    /// each expanded array/object ends with a row counting how many of its entries are hidden, e.g. `3 entries hidden`,
+    /// so that it is clear how much of the document was left out. Defaults to `false`.
+    pub fn count_hidden_entries(mut self, count_hidden_entries: bool) -> Self {
+        self.config.count_hidden_entries = count_hidden_entries;
+        self
+    }
+
+    /// If `true`, navigating to a search match via [`JsonTreeResponse::scroll_to_match`] or its neighbours
+    /// also moves keyboard focus to the key or array index of the match once its row is shown,
+    /// so that its context menu can then be opened with [`OPEN_SHORTCUT`](crate::context_menu::OPEN_SHORTCUT), e.g. to copy it.
+    ///
+    /// Defaults to `false`.
+    pub fn focus_follows_search(mut self, focus_follows_search: bool) -> Self {
+        self.config.focus_follows_search = focus_follows_search;
+        self
+    }
+
+    /// If `true`, whenever a search term is applied, e.g. as it is typed, the first match is scrolled into view
+    /// as if via [`JsonTreeResponse::scroll_to_pointer`], without expanding any arrays/objects to reveal it.
+    /// The tree should be shown within an [`egui::ScrollArea`].
+    ///
+    /// Leave this disabled if your app manages scrolling itself, e.g. via [`JsonTreeResponse::scroll_to_match`].
+    /// Defaults to `false`.
+    pub fn scroll_to_first_match(mut self, scroll_to_first_match: bool) -> Self {
+        self.config.scroll_to_first_match = scroll_to_first_match;
+        self
+    }
+
+    /// If `true`, a chain of expanded arrays/objects that each show nothing but a single nested array/object,
+    /// e.g. when searching forces deeply nested matches open, is condensed into a single row of their keys and indices,
+    /// such as `…/data/items/41/`, followed by the array/object at the end of the chain.
+    /// Only chains of at least two levels that are not search matches themselves are condensed, and only while searching.
+    ///
+    /// Clicking the row restores the full chain. Defaults to `false`.
+    pub fn condense_search_paths(mut self, condense_search_paths: bool) -> Self {
+        self.config.condense_search_paths = condense_search_paths;
+        self
+    }
+
+    /// If `true`, records a structured model of what is rendered this frame,
+    /// available via [`JsonTreeResponse::snapshot`]. Defaults to `false`.
+    ///
+    /// This is intended for tests, e.g. to compare against a golden file.
+    pub fn record_snapshot(mut self, record_snapshot: bool) -> Self {
+        self.config.record_snapshot = record_snapshot;
+        self
+    }
+
+    /// Mark the values at the given JSON Pointer strings on the scroll bar track, each in the given color,
+    /// e.g. to indicate where validation errors or diff changes are located in the document.
+    /// A value hidden within a collapsed array/object is marked at the row of that array/object.
+    ///
+    /// The markers are painted by [`JsonTreeResponse::show_scroll_markers`], which requires the tree to be shown within an [`egui::ScrollArea`].
+    pub fn scroll_markers(
+        mut self,
+        markers: impl IntoIterator<Item = (impl Into<String>, Color32)>,
+    ) -> Self {
+        self.config.scroll_markers = markers
+            .into_iter()
+            .map(|(pointer, color)| (pointer.into(), color))
+            .collect();
+        self
+    }
+
+    /// Accept drops of items dragged with a payload of type `P` via [`egui::DragAndDrop`], e.g. from a palette of values.
+    ///
+    /// While such a drag hovers a row, an insertion line is painted before or after the row,
+    /// or before the first entry of an expanded array/object if hovering the lower half of its row,
+    /// and the targeted gap is returned by [`JsonTreeResponse::drop_target`].
+    /// Its stroke can be configured via [`JsonTreeStyle::drop_indicator_stroke`].
+    /// ```rust
+    /// # use egui::DragAndDrop;
+    /// # use egui_json_tree::JsonTree;
+    /// # egui::__run_test_ui(|ui| {
+    /// # let value = serde_json::json!({ "plugins": ["a", "b"] });
+    /// struct PaletteItem(serde_json::Value);
+    ///
+    /// let response = JsonTree::new("drop-tree", &value)
+    ///     .drop_targets::<PaletteItem>()
+    ///     .show(ui);
+    ///
+    /// if let Some(target) = response.drop_target() {
+    ///     if ui.input(|i| i.pointer.any_released()) {
+    ///         if let Some(item) = DragAndDrop::take_payload::<PaletteItem>(ui.ctx()) {
+    ///             // Insert `item.0` into the array/object at `target.parent_pointer`, at `target.index`.
+    ///         }
+    ///     }
+    /// }
+    /// # });
+    /// ```
+    pub fn drop_targets<P: Any + Send + Sync>(mut self) -> Self {
+        self.config.accepts_drop = Some(DragAndDrop::has_payload_of_type::<P>);
+        self
+    }
+
+    /// Mark search matches on the scroll bar track in the given color,
+    /// including collapsed arrays/objects that contain matches. See [`JsonTree::scroll_markers`].
+    pub fn search_match_scroll_markers(mut self, color: Color32) -> Self {
+        self.config.search_match_scroll_marker_color = Some(color);
+        self
+    }
+
+    /// If `true`, the rows of non-empty arrays whose elements are all numbers show the count, minimum, maximum, mean and sum of the numbers,
+    /// so that large arrays of metrics can be interpreted without expanding them.
+    ///
+    /// Summaries are computed when their rows are shown, and only once per version of the value if [`JsonTree::value_version`] is set.
+    /// Defaults to `false`.
+    pub fn numeric_summaries(mut self, numeric_summaries: bool) -> Self {
+        self.config.numeric_summaries = numeric_summaries;
+        self
+    }
+
+    /// If `true`, the rows of arrays/objects show a short hash of their content, e.g. `// #1f2e3d4c`,
+    /// so that users can confirm at a glance that two sections of the same or different documents are identical.
+    /// Equal arrays/objects have equal hashes regardless of the order of object keys.
+    ///
+    /// Hashes are computed when their rows are shown, and only once per version of the value if [`JsonTree::value_version`] is set.
+    /// Defaults to `false`.
+    pub fn subtree_hashes(mut self, subtree_hashes: bool) -> Self {
+        self.config.subtree_hashes = subtree_hashes;
+        self
+    }
+
+    /// The version of the value being shown, which must change whenever the value changes,
+    /// e.g. [`JsonTreeDocument::version`](crate::JsonTreeDocument::version).
+    ///
+    /// Results derived from the value, such as [`JsonTree::numeric_summaries`] and [`JsonTree::subtree_hashes`], are then cached until the version changes,
+    /// rather than being recomputed every frame. The matches of a search term are likewise cached until the version or the search changes,
+    /// so that a large value is not searched every frame.
+    pub fn value_version(mut self, value_version: u64) -> Self {
+        self.config.value_version = Some(value_version);
+        self
+    }
+
+    /// Update the cached matches of the search term incrementally when the value is a snapshot of the document
+    /// that was changed via [`JsonTreeDocument::update_at`] since the matches were found,
+    /// by searching only the subtrees that changed rather than the whole value.
+    ///
+    /// This requires [`JsonTree::value_version`] to be the version of the snapshot being shown:
+    /// ```rust
+    /// # use egui_json_tree::{DefaultExpand, JsonTree, JsonTreeDocument};
+    /// # egui::__run_test_ui(|ui| {
+    /// let document = JsonTreeDocument::new(serde_json::json!({ "logs": [] }));
+    ///
+    /// let (snapshot, version) = document.load_with_version();
+    /// JsonTree::new("log-tree", &*snapshot)
+    ///     .default_expand(DefaultExpand::SearchResults("error"))
+    ///     .value_version(version)
+    ///     .document_changes(&document)
+    ///     .show(ui);
+    /// # });
+    /// ```
+    pub fn document_changes(mut self, document: &JsonTreeDocument<T>) -> Self {
+        self.config.document_changes = Some(document.changes());
+        self
+    }
+
+    /// Identify the elements of the array at `array_pointer` by the value at `identity_pointer` within each element, e.g. `/id`,
+    /// rather than by their index. Call this once for each array to configure.
+    ///
+    /// The expanded state of an element and of the arrays/objects within it then follows the element
+    /// when elements are inserted or removed, instead of shifting to whichever element is now at its index.
+    /// Elements whose identity is missing, or the same as that of an earlier element, are identified by their index as normal.
+    /// ```rust
+    /// # use egui_json_tree::JsonTree;
+    /// # egui::__run_test_ui(|ui| {
+    /// # let value = serde_json::json!({ "users": [{ "id": 7, "name": "Alice" }] });
+    /// JsonTree::new("identity-tree", &value)
+    ///     .element_identity("/users", "/id")
+    ///     .show(ui);
+    /// # });
+    /// ```
+    pub fn element_identity(
+        mut self,
+        array_pointer: impl Into<String>,
+        identity_pointer: impl Into<String>,
+    ) -> Self {
+        self.config
+            .element_identities
+            .push((array_pointer.into(), identity_pointer.into()));
+        self
+    }
+
+    /// Group the entries of objects under collapsible headings, e.g. to show all `x-*` headers under "Extensions".
+    ///
+    /// The closure receives the JSON pointer to the object and the key of each of its entries,
+    /// and returns the heading of the group that the entry belongs to, or `None` to leave it ungrouped.
+    /// Ungrouped entries are shown first, followed by each group in the order that its first entry appears in the object.
+    ///
+    /// Group headings are for display only - they do not affect JSON pointers, search or the values passed to render hooks.
+    /// ```rust
+    /// # use egui_json_tree::JsonTree;
+    /// # egui::__run_test_ui(|ui| {
+    /// # let headers = serde_json::json!({ "content-type": "text/plain", "x-request-id": "abc" });
+    /// JsonTree::new("grouped-tree", &headers)
+    ///     .group_entries(|_, key| key.starts_with("x-").then(|| "Extensions".to_string()))
+    ///     .show(ui);
+    /// # });
+    /// ```
+    pub fn group_entries(
+        mut self,
+        entry_groups: impl Fn(JsonPointer, &str) -> Option<String> + 'a,
+    ) -> Self {
+        self.config.entry_groups = Some(Box::new(entry_groups));
+        self
+    }
+
+    /// Show the fields that your schema defines for an object but that are missing from it, as dimmed placeholder rows
+    /// after its entries, e.g. `"port": <unset, default 8080>`.
+    ///
+    /// The closure receives the JSON pointer to each expanded object, and returns the fields defined for it, if any.
+    /// Placeholder rows are not shown while search results are filtered via [`JsonTree::filter_search_results`].
+    /// ```rust
+    /// # use egui_json_tree::{JsonTree, JsonTreeSchemaField};
+    /// # egui::__run_test_ui(|ui| {
+    /// # let config = serde_json::json!({ "server": { "host": "localhost" } });
+    /// JsonTree::new("schema-tree", &config)
+    ///     .schema_fields(|pointer| match pointer.to_json_pointer_string().as_str() {
+    ///         "/server" => vec![
+    ///             JsonTreeSchemaField::new("host"),
+    ///             JsonTreeSchemaField::new("port").with_default("8080"),
+    ///         ],
+    ///         _ => vec![],
+    ///     })
+    ///     .show(ui);
+    /// # });
+    /// ```
+    pub fn schema_fields(
+        mut self,
+        schema_fields: impl Fn(JsonPointer) -> Vec<JsonTreeSchemaField> + 'a,
+    ) -> Self {
+        self.config.schema_fields = Some(Box::new(schema_fields));
+        self
+    }
+
+    /// Annotate values with their provenance, e.g. the file and line or the config layer that provided them,
+    /// shown as a subtle comment after each value or in a tooltip, according to [`JsonTreeStyle::provenance_display`].
+    ///
+    /// The closure receives the JSON pointer to each value shown, including arrays/objects, and returns its provenance, if known.
+    /// ```rust
+    /// # use std::collections::HashMap;
+    /// # use egui_json_tree::JsonTree;
+    /// # egui::__run_test_ui(|ui| {
+    /// # let config = serde_json::json!({ "port": 8080 });
+    /// let sources = HashMap::from([("/port", "override.toml:3")]);
+    /// JsonTree::new("provenance-tree", &config)
+    ///     .provenance(|pointer| {
+    ///         sources
+    ///             .get(pointer.to_json_pointer_string().as_str())
+    ///             .map(|source| source.to_string())
+    ///     })
+    ///     .show(ui);
+    /// # });
+    /// ```
+    pub fn provenance(mut self, provenance: impl Fn(JsonPointer) -> Option<String> + 'a) -> Self {
+        self.config.provenance = Some(Box::new(provenance));
+        self
+    }
+
+    /// Register a hook that is called whenever an array/object is expanded or collapsed, e.g. by the user clicking its toggle button,
+    /// with the JSON pointer to it and whether it is now expanded.
+    ///
+    /// This can be used to log usage, to fetch lazily loaded data for a branch that was just opened,
+    /// or to sync the expanded state to other views. Arrays/objects that are expanded by default are not reported.
+    /// ```rust
+    /// # use egui_json_tree::JsonTree;
+    /// # egui::__run_test_ui(|ui| {
+    /// # let value = serde_json::json!({ "users": [] });
+    /// JsonTree::new("toggle-tree", &value)
+    ///     .on_toggle(|pointer, is_open| {
+    ///         if is_open && pointer.to_json_pointer_string() == "/users" {
+    ///             // Fetch the users...
+    ///         }
+    ///     })
+    ///     .show(ui);
+    /// # });
+    /// ```
+    pub fn on_toggle(mut self, on_toggle: impl FnMut(JsonPointer, bool) + 'a) -> Self {
+        self.config.on_toggle = Some(Box::new(on_toggle));
+        self
+    }
+
+    /// The modifier keys that, when held while clicking the toggle button of an array/object,
+    /// expand or collapse it along with every array/object nested within it, as in most IDE tree views.
+    /// Pass `None` to always toggle a single array/object.
+    ///
+    /// Defaults to `None`. To expand or collapse recursively when shift-clicking a toggle button:
+    /// ```rust
+    /// # use egui::Modifiers;
+    /// # use egui_json_tree::JsonTree;
+    /// # egui::__run_test_ui(|ui| {
+    /// # let value = serde_json::json!({ "a": { "b": [1] } });
+    /// JsonTree::new("recursive-tree", &value)
+    ///     .recursive_toggle_modifiers(Some(Modifiers::SHIFT))
+    ///     .show(ui);
+    /// # });
+    /// ```
+    pub fn recursive_toggle_modifiers(mut self, modifiers: Option<Modifiers>) -> Self {
+        self.config.recursive_toggle_modifiers = modifiers;
+        self
+    }
+
+    /// Override the style, default expansion and/or rendering of the tree within the subtree beneath a JSON Pointer string,
+    /// including the row of the array/object or value at the pointer itself. Subtrees may be nested, e.g. within another subtree.
+    /// ```rust
+    /// # use egui_json_tree::{ExpandOverride, JsonTree, JsonTreeDensity, JsonTreeStyle, JsonTreeSubtreeConfig, JsonTreeVisuals};
+    /// # egui::__run_test_ui(|ui| {
+    /// # let value = serde_json::json!({ "id": 1, "raw": { "bytes": [0, 1] } });
+    /// JsonTree::new("subtree-tree", &value)
+    ///     .subtree(
+    ///         "/raw",
+    ///         JsonTreeSubtreeConfig::new()
+    ///             .style(
+    ///                 JsonTreeStyle::new()
+    ///                     .density(JsonTreeDensity::Compact)
+    ///                     .visuals(JsonTreeVisuals::DARK),
+    ///             )
+    ///             .default_expand(ExpandOverride::None),
+    ///     )
+    ///     .show(ui);
+    /// # });
+    /// ```
+    ///
+    /// Overrides for an invalid JSON Pointer string are ignored. If the same pointer is configured more than once, the first configuration applies.
+    pub fn subtree(mut self, pointer: &'a str, config: JsonTreeSubtreeConfig<'a, T>) -> Self {
+        self.config.subtrees.push((pointer, config));
+        self
+    }
+
+    /// Restore the expanded state of the arrays/objects of the tree, e.g. as saved with your app state when it last ran.
+    /// The state is restored the first time the tree is shown, after which the arrays/objects can be expanded and collapsed as usual.
+    /// See [`JsonTreeExpansionState`](crate::JsonTreeExpansionState).
+    ///
+    /// Requires the `persistence` feature.
+    #[cfg(feature = "persistence")]
+    pub fn restore_expansion_state(
+        mut self,
+        expansion_state: &'a crate::JsonTreeExpansionState,
+    ) -> Self {
+        self.config.expansion_state = Some(expansion_state);
+        self
+    }
+
+    /// Render a placeholder instead of the tree when it has nothing to show, i.e. when the value is `null`,
+    /// an empty array or object, or when [`JsonTree::filter_search_results`] hides every entry.
+    /// The hook receives the [`JsonTreeEmptyReason`], e.g. to show "No results" rather than "Empty document".
+    ///
+    /// Without a placeholder, such values are rendered as normal, e.g. `null` or `{}`.
+    /// ```rust
+    /// # use egui_json_tree::{JsonTree, JsonTreeEmptyReason};
+    /// # egui::__run_test_ui(|ui| {
+    /// # let value = serde_json::json!({});
+    /// JsonTree::new("empty-tree", &value)
+    ///     .on_empty(|ui, reason| {
+    ///         ui.weak(match reason {
+    ///             JsonTreeEmptyReason::NoSearchResults => "No matches",
+    ///             _ => "Empty document",
+    ///         });
+    ///     })
+    ///     .show(ui);
+    /// # });
+    /// ```
+    pub fn on_empty(
+        mut self,
+        empty_placeholder: impl FnMut(&mut Ui, JsonTreeEmptyReason) + 'a,
+    ) -> Self {
+        self.config.empty_placeholder = Some(Box::new(empty_placeholder));
+        self
+    }
+
+    /// Show a message instead of the tree when it has nothing to show, whatever the reason.
+    /// See [`JsonTree::on_empty`].
+    pub fn empty_placeholder(self, message: impl Into<WidgetText>) -> Self {
+        let message = message.into();
+        self.on_empty(move |ui, _| {
+            ui.label(message.clone());
+        })
+    }
+
     /// A convenience method for conditionally registering a custom rendering hook.
     /// See [`JsonTree::on_render`].
     pub fn on_render_if(
@@ -94,6 +603,125 @@ impl<'a, T: ToJsonTreeValue> JsonTree<'a, T> {
         self
     }
 
+    /// Format number values for display, e.g. to apply locale-aware digit grouping or currency symbols
+    /// using a crate such as `icu` or `num-format`.
+    ///
+    /// The formatter receives the number as it appears in the JSON document, and the JSON pointer to it.
+    /// Return `None` to display the number as normal.
+    ///
+    /// Only the displayed text is affected - search matching is performed against the original number,
+    /// which is also shown on hover and remains available to render hooks via [`RenderBaseValueContext::display_value`](crate::render::RenderBaseValueContext::display_value).
+    pub fn format_numbers(
+        mut self,
+        formatter: impl Fn(&str, JsonPointer) -> Option<String> + 'a,
+    ) -> Self {
+        self.config.renderer.number_formatter = Some(Box::new(formatter));
+        self
+    }
+
+    /// If `true`, shows a context menu with the built-in [`ContextMenuEntry`]s
+    /// when right clicking a key, value or bracket, e.g. to copy the JSON pointer or value,
+    /// or when pressing [`OPEN_SHORTCUT`](crate::context_menu::OPEN_SHORTCUT) while one has keyboard focus.
+    /// See the [`context_menu`](mod@crate::context_menu) module.
+    ///
+    /// Defaults to `false`.
+    pub fn context_menu(mut self, context_menu: bool) -> Self {
+        self.config.renderer.context_menu.builtin_entries = context_menu;
+        self
+    }
+
+    /// Decide whether each built-in [`ContextMenuEntry`] is shown for the value at the JSON pointer,
+    /// e.g. to prevent copying sensitive values. Return `false` to suppress the entry.
+    pub fn context_menu_entries(
+        mut self,
+        entry_filter: impl Fn(ContextMenuEntry, JsonPointer, &T) -> bool + 'a,
+    ) -> Self {
+        self.config.renderer.context_menu.entry_filter = Some(Box::new(entry_filter));
+        self
+    }
+
+    /// Append your own items to the context menu, after any built-in entries.
+    /// The hook receives the JSON pointer to the value that was right clicked, and the value itself.
+    ///
+    /// The context menu is shown even if the built-in entries are not enabled via [`JsonTree::context_menu`].
+    pub fn on_context_menu(
+        mut self,
+        context_menu_hook: impl FnMut(&mut Ui, JsonPointer<'a, '_>, &'a T) + 'a,
+    ) -> Self {
+        self.config.renderer.context_menu.hook = Some(RefCell::new(Box::new(context_menu_hook)));
+        self
+    }
+
+    /// If `true`, non-recursive values can be edited inline, without a full editor UI:
+    /// double clicking a value replaces it with a text edit, and pressing Enter commits the text via [`JsonTree::on_value_edit`].
+    /// Pressing Escape or clicking elsewhere cancels the edit.
+    ///
+    /// Defaults to `false`.
+    pub fn editable_values(mut self, editable_values: bool) -> Self {
+        self.config.renderer.value_editor.enabled = editable_values;
+        self
+    }
+
+    /// If `true`, double clicking a non-recursive value copies it to the clipboard as displayed,
+    /// i.e. without quotes for strings, and briefly shows "Copied" next to it.
+    ///
+    /// Values that are editable via [`JsonTree::editable_values`] are edited by double clicking them instead.
+    /// Defaults to `false`.
+    pub fn copy_on_double_click(mut self, copy_on_double_click: bool) -> Self {
+        self.config.renderer.copy_on_double_click = copy_on_double_click;
+        self
+    }
+
+    /// Commit an edit made via [`JsonTree::editable_values`].
+    ///
+    /// The callback receives the JSON pointer to the edited value and the new text, which is the content of a string value without quotes,
+    /// or the text of any other value as typed, e.g. `42`, `true` or `null`. Parse it as appropriate for your JSON type to update your document.
+    pub fn on_value_edit(mut self, on_edit: impl FnMut(JsonPointer<'a, '_>, &str) + 'a) -> Self {
+        self.config.renderer.value_editor.on_edit = Some(RefCell::new(Box::new(on_edit)));
+        self
+    }
+
+    /// When values are editable via [`JsonTree::editable_values`], clicking the placeholder row of a missing field
+    /// with a default value, shown via [`JsonTree::schema_fields`], inserts the field.
+    ///
+    /// The callback receives the JSON pointer to the object and the field to insert with its default value,
+    /// which is in the same format as the text passed to [`JsonTree::on_value_edit`].
+    pub fn on_insert_field(
+        mut self,
+        on_insert_field: impl FnMut(JsonPointer<'a, '_>, &JsonTreeSchemaField) + 'a,
+    ) -> Self {
+        self.config.renderer.value_editor.on_insert_field =
+            Some(RefCell::new(Box::new(on_insert_field)));
+        self
+    }
+
+    /// When values are editable via [`JsonTree::editable_values`], allow adjusting numbers by dragging horizontally on them,
+    /// like an [`egui::DragValue`], e.g. for live-tuning config values.
+    /// Each change is committed via [`JsonTree::on_value_edit`] while dragging.
+    ///
+    /// The closure decides the speed and range of the number at each JSON pointer. Return `None` to disable dragging for that number.
+    pub fn drag_numbers(
+        mut self,
+        numeric_drag: impl Fn(JsonPointer) -> Option<JsonTreeNumericDrag> + 'a,
+    ) -> Self {
+        self.config.renderer.value_editor.numeric_drag = Some(Box::new(numeric_drag));
+        self
+    }
+
+    /// When values are editable via [`JsonTree::editable_values`], constrain the value at each JSON pointer to a list of allowed values,
+    /// e.g. the variants of an enum in your schema. Return `None` if the value is unconstrained.
+    ///
+    /// A constrained value is edited by selecting one of its allowed values from a combo box instead of typing free text,
+    /// and it cannot be dragged via [`JsonTree::drag_numbers`].
+    /// The allowed values are given in the same format as the text passed to [`JsonTree::on_value_edit`].
+    pub fn allowed_values(
+        mut self,
+        allowed_values: impl Fn(JsonPointer) -> Option<Vec<String>> + 'a,
+    ) -> Self {
+        self.config.renderer.value_editor.allowed_values = Some(Box::new(allowed_values));
+        self
+    }
+
     /// Show the JSON tree visualisation within the `Ui`.
     pub fn show(self, ui: &mut Ui) -> JsonTreeResponse {
         JsonTreeNode::show(self, ui)
@@ -0,0 +1,53 @@
+/// The lengths of the hyphen-separated groups of hex digits in a UUID, e.g. `550e8400-e29b-41d4-a716-446655440000`.
+const UUID_GROUP_LENGTHS: [usize; 5] = [8, 4, 4, 4, 12];
+
+/// Returns whether the string is formatted as a UUID, ignoring letter case.
+pub(crate) fn is_uuid(s: &str) -> bool {
+    let mut groups = s.split('-');
+
+    let groups_valid = UUID_GROUP_LENGTHS.iter().all(|len| {
+        groups.next().is_some_and(|group| {
+            group.len() == *len && group.bytes().all(|b| b.is_ascii_hexdigit())
+        })
+    });
+
+    groups_valid && groups.next().is_none()
+}
+
+/// Shortens a UUID to its first and last groups, e.g. `550e8400…446655440000`.
+pub(crate) fn shorten_uuid(uuid: &str) -> String {
+    match (uuid.split('-').next(), uuid.rsplit('-').next()) {
+        (Some(first), Some(last)) => format!("{first}…{last}"),
+        _ => uuid.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognises_uuids() {
+        assert!(is_uuid("550e8400-e29b-41d4-a716-446655440000"));
+        assert!(is_uuid("550E8400-E29B-41D4-A716-446655440000"));
+        assert!(is_uuid("00000000-0000-0000-0000-000000000000"));
+    }
+
+    #[test]
+    fn rejects_non_uuids() {
+        assert!(!is_uuid(""));
+        assert!(!is_uuid("550e8400e29b41d4a716446655440000"));
+        assert!(!is_uuid("550e8400-e29b-41d4-a716-44665544000"));
+        assert!(!is_uuid("550e8400-e29b-41d4-a716-4466554400000"));
+        assert!(!is_uuid("550e8400-e29b-41d4-a716-446655440000-0"));
+        assert!(!is_uuid("g50e8400-e29b-41d4-a716-446655440000"));
+    }
+
+    #[test]
+    fn shortens_uuid_to_first_and_last_groups() {
+        assert_eq!(
+            shorten_uuid("550e8400-e29b-41d4-a716-446655440000"),
+            "550e8400…446655440000"
+        );
+    }
+}
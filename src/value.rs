@@ -7,7 +7,10 @@
 //! - `serde_json::Value`
 //! - `simd_json::owned::Value`
 
-use std::fmt::Display;
+use std::{
+    collections::HashMap,
+    fmt::{Display, Write},
+};
 
 use crate::pointer::JsonPointerSegment;
 /// Representation of JSON values for presentation purposes.
@@ -47,6 +50,36 @@ pub trait ToJsonTreeValue {
     fn to_json_tree_value(&self) -> JsonTreeValue<Self>;
     /// Returns whether this JSON value is expandable, i.e. whether it is an object or an array.
     fn is_expandable(&self) -> bool;
+    /// Returns whether this JSON value may be an object that contains the same key more than once,
+    /// e.g. if it is backed by a `Vec<(String, Value)>`.
+    ///
+    /// If so, repeated occurrences of a key are identified by [`JsonPointerSegment::DuplicateKey`] instead of [`JsonPointerSegment::Key`],
+    /// so that each occurrence has a distinct JSON pointer and expanded state.
+    /// Defaults to `false`, which avoids the cost of checking each object for duplicate keys.
+    fn allows_duplicate_keys(&self) -> bool {
+        false
+    }
+}
+
+/// Replaces repeated occurrences of object keys in the entries of the value with [`JsonPointerSegment::DuplicateKey`],
+/// if the value allows duplicate keys.
+pub(crate) fn disambiguate_duplicate_keys<'a, T: ToJsonTreeValue + ?Sized>(
+    value: &T,
+    entries: &mut [(JsonPointerSegment<'a>, &'a T)],
+) {
+    if !value.allows_duplicate_keys() {
+        return;
+    }
+    let mut occurrences = HashMap::<&str, usize>::new();
+    for (property, _) in entries.iter_mut() {
+        if let JsonPointerSegment::Key(key) = *property {
+            let occurrence = occurrences.entry(key).or_default();
+            *occurrence += 1;
+            if *occurrence > 1 {
+                *property = JsonPointerSegment::DuplicateKey(key, *occurrence);
+            }
+        }
+    }
 }
 
 #[cfg(feature = "serde_json")]
@@ -122,3 +155,159 @@ impl ToJsonTreeValue for simd_json::owned::Value {
         )
     }
 }
+
+/// The language in which a value is written out as a literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Syntax {
+    Json,
+    /// The contents of a `serde_json::json!` macro invocation.
+    Rust,
+    Python,
+}
+
+/// Serializes the value as pretty-printed JSON, indented by two spaces.
+pub(crate) fn to_json_string<T: ToJsonTreeValue + ?Sized>(value: &T) -> String {
+    let mut json = String::new();
+    write_literal(&mut json, value, Some(0), Syntax::Json);
+    json
+}
+
+/// Serializes the value as JSON without any whitespace.
+pub(crate) fn to_compact_json_string<T: ToJsonTreeValue + ?Sized>(value: &T) -> String {
+    let mut json = String::new();
+    write_literal(&mut json, value, None, Syntax::Json);
+    json
+}
+
+/// Writes the value as a pretty-printed `serde_json::json!` macro invocation, for use as a test fixture in Rust code.
+pub(crate) fn to_rust_literal<T: ToJsonTreeValue + ?Sized>(value: &T) -> String {
+    let mut literal = String::from("serde_json::json!(");
+    write_literal(&mut literal, value, Some(0), Syntax::Rust);
+    literal.push(')');
+    literal
+}
+
+/// Writes the value as a pretty-printed Python literal of dicts, lists, strings, numbers, booleans and `None`.
+pub(crate) fn to_python_literal<T: ToJsonTreeValue + ?Sized>(value: &T) -> String {
+    let mut literal = String::new();
+    write_literal(&mut literal, value, Some(0), Syntax::Python);
+    literal
+}
+
+/// Writes the value in the given syntax, pretty-printed at the given depth of indentation if any.
+fn write_literal<T: ToJsonTreeValue + ?Sized>(
+    out: &mut String,
+    value: &T,
+    depth: Option<usize>,
+    syntax: Syntax,
+) {
+    match value.to_json_tree_value() {
+        JsonTreeValue::Base(_, display_value, BaseValueType::String) => {
+            write_string_literal(out, &display_value.to_string(), syntax);
+        }
+        JsonTreeValue::Base(_, _, BaseValueType::Null) if syntax == Syntax::Python => {
+            out.push_str("None");
+        }
+        JsonTreeValue::Base(_, display_value, BaseValueType::Bool) if syntax == Syntax::Python => {
+            out.push_str(if display_value.to_string() == "true" {
+                "True"
+            } else {
+                "False"
+            });
+        }
+        JsonTreeValue::Base(_, display_value, _) => {
+            let _ = write!(out, "{display_value}");
+        }
+        JsonTreeValue::Expandable(entries, expandable_type) => {
+            let (opening, closing) = match expandable_type {
+                ExpandableType::Array => ('[', ']'),
+                ExpandableType::Object => ('{', '}'),
+            };
+            let nested_depth = depth.map(|depth| depth + 1);
+            out.push(opening);
+            for (idx, (property, elem)) in entries.iter().enumerate() {
+                if idx > 0 {
+                    out.push(',');
+                }
+                if let Some(nested_depth) = nested_depth {
+                    out.push('\n');
+                    out.push_str(&"  ".repeat(nested_depth));
+                }
+                if let JsonPointerSegment::Key(key) | JsonPointerSegment::DuplicateKey(key, _) =
+                    property
+                {
+                    write_string_literal(out, key, syntax);
+                    out.push_str(if depth.is_some() { ": " } else { ":" });
+                }
+                write_literal(out, *elem, nested_depth, syntax);
+            }
+            if let (Some(depth), false) = (depth, entries.is_empty()) {
+                out.push('\n');
+                out.push_str(&"  ".repeat(depth));
+            }
+            out.push(closing);
+        }
+    }
+}
+
+fn write_string_literal(out: &mut String, s: &str, syntax: Syntax) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            // Rust string literals have no `\b` or `\f` escapes.
+            c if c < ' ' && syntax == Syntax::Rust => {
+                let _ = write!(out, "\\u{{{:x}}}", c as u32);
+            }
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            c if c < ' ' => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(all(test, feature = "serde_json"))]
+mod tests {
+    use serde_json::json;
+
+    use super::{to_compact_json_string, to_json_string, to_python_literal, to_rust_literal};
+
+    #[test]
+    fn to_json_string_matches_serde_json_pretty_output() {
+        let value = json!({
+            "array": [1, -2.5, true, null, [], {}],
+            "nested": {"escaped": "quote \" backslash \\ newline \n tab \t bell \u{7} unicode \u{e9}"},
+            "empty": ""
+        });
+        assert_eq!(
+            to_json_string(&value),
+            serde_json::to_string_pretty(&value).unwrap()
+        );
+        assert_eq!(to_json_string(&json!("\u{8}\u{c}")), r#""\b\f""#);
+        assert_eq!(
+            to_compact_json_string(&value),
+            serde_json::to_string(&value).unwrap()
+        );
+    }
+
+    #[test]
+    fn writes_rust_and_python_literals() {
+        let value = json!({"a": [true, null, 1.5], "b": "line\nbell \u{7} \u{8}"});
+        assert_eq!(
+            to_rust_literal(&value),
+            "serde_json::json!({\n  \"a\": [\n    true,\n    null,\n    1.5\n  ],\n  \"b\": \"line\\nbell \\u{7} \\u{8}\"\n})"
+        );
+        assert_eq!(
+            to_python_literal(&value),
+            "{\n  \"a\": [\n    True,\n    None,\n    1.5\n  ],\n  \"b\": \"line\\nbell \\u0007 \\b\"\n}"
+        );
+    }
+}
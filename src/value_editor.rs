@@ -0,0 +1,327 @@
+use std::{cell::RefCell, ops::RangeInclusive};
+
+use egui::{ComboBox, Context, CursorIcon, Id, Key, Response, TextEdit, Ui};
+
+use crate::{
+    identity::PathIds,
+    pointer::JsonPointer,
+    render::{ParentStatus, RenderBaseValueContext},
+    schema_fields::JsonTreeSchemaField,
+    value::{BaseValueType, ToJsonTreeValue},
+};
+
+/// A closure that commits the edited text of the value at the JSON pointer.
+pub(crate) type ValueEditHook<'a> = dyn FnMut(JsonPointer<'a, '_>, &str) + 'a;
+
+/// A closure that inserts a missing field into the object at the JSON pointer, with its default value.
+pub(crate) type InsertFieldHook<'a> = dyn FnMut(JsonPointer<'a, '_>, &JsonTreeSchemaField) + 'a;
+
+/// A closure that decides how the number at the JSON pointer is adjusted by dragging, if at all.
+pub(crate) type NumericDragConfig<'a> = dyn Fn(JsonPointer) -> Option<JsonTreeNumericDrag> + 'a;
+
+/// A closure that returns the only values allowed for the value at the JSON pointer, if it is constrained.
+pub(crate) type AllowedValues<'a> = dyn Fn(JsonPointer) -> Option<Vec<String>> + 'a;
+
+/// Configuration for adjusting a number by dragging horizontally on it, like an [`egui::DragValue`].
+/// See [`JsonTree::drag_numbers`](crate::JsonTree::drag_numbers).
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonTreeNumericDrag {
+    /// How much the number changes for each point dragged.
+    pub speed: f64,
+    /// The number is clamped to this range while dragging.
+    pub range: RangeInclusive<f64>,
+}
+
+impl Default for JsonTreeNumericDrag {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            range: f64::NEG_INFINITY..=f64::INFINITY,
+        }
+    }
+}
+
+impl JsonTreeNumericDrag {
+    /// Returns the number after dragging `total_delta` points from `start`, formatted as text.
+    /// Integers remain integers, and other numbers are shown with as many decimals as the speed requires.
+    fn adjust(&self, start: &str, total_delta: f32) -> Option<String> {
+        let start_value = start.parse::<f64>().ok()?;
+        let value = (start_value + total_delta as f64 * self.speed)
+            .clamp(*self.range.start(), *self.range.end());
+
+        if start.contains(['.', 'e', 'E']) {
+            let decimals = (-self.speed.abs().log10()).ceil().clamp(0.0, 15.0) as usize;
+            Some(format!("{value:.decimals$}"))
+        } else {
+            Some(format!("{}", value.round() as i64))
+        }
+    }
+}
+
+/// The state of a number being dragged, stored in egui's temporary memory between frames.
+#[derive(Debug, Clone)]
+struct NumericDragState {
+    start: String,
+    total_delta: f32,
+}
+
+/// Lets non-recursive values be edited inline, after being double clicked.
+/// See [`JsonTree::editable_values`](crate::JsonTree::editable_values).
+pub(crate) struct ValueEditor<'a> {
+    pub(crate) enabled: bool,
+    pub(crate) on_edit: Option<RefCell<Box<ValueEditHook<'a>>>>,
+    pub(crate) on_insert_field: Option<RefCell<Box<InsertFieldHook<'a>>>>,
+    pub(crate) numeric_drag: Option<Box<NumericDragConfig<'a>>>,
+    pub(crate) allowed_values: Option<Box<AllowedValues<'a>>>,
+    /// The ids of the tree being shown, for identifying the value being edited.
+    path_ids: Option<PathIds>,
+    editing: RefCell<Option<ValueEdit>>,
+}
+
+impl<'a> Default for ValueEditor<'a> {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            on_edit: None,
+            on_insert_field: None,
+            numeric_drag: None,
+            allowed_values: None,
+            path_ids: None,
+            editing: RefCell::new(None),
+        }
+    }
+}
+
+/// The state of a value being edited, stored in egui's temporary memory between frames.
+#[derive(Debug, Clone)]
+struct ValueEdit {
+    target_id: Id,
+    text: String,
+    request_focus: bool,
+}
+
+impl<'a> ValueEditor<'a> {
+    fn id(tree_state_id: Id) -> Id {
+        tree_state_id.with("value_edit")
+    }
+
+    /// Prepares to show the tree with the given ids, loading the value being edited, if any.
+    pub(crate) fn load(&mut self, ctx: &Context, path_ids: PathIds) {
+        if self.enabled {
+            *self.editing.get_mut() =
+                ctx.data(|d| d.get_temp::<ValueEdit>(Self::id(path_ids.tree_state_id())));
+        }
+        self.path_ids = Some(path_ids);
+    }
+
+    fn target_id(&self, pointer: JsonPointer) -> Option<Id> {
+        self.path_ids
+            .as_ref()
+            .map(|path_ids| path_ids.id(pointer.0))
+    }
+
+    fn tree_state_id(&self) -> Option<Id> {
+        self.path_ids.as_ref().map(PathIds::tree_state_id)
+    }
+
+    fn allowed_values(&self, pointer: JsonPointer) -> Option<Vec<String>> {
+        self.allowed_values
+            .as_ref()
+            .and_then(|allowed_values| allowed_values(pointer))
+    }
+
+    fn commit(&self, pointer: JsonPointer<'a, '_>, text: &str) {
+        if let Some(on_edit) = &self.on_edit {
+            (on_edit.borrow_mut())(pointer, text);
+        }
+    }
+
+    /// Returns whether a missing field can be inserted by clicking its placeholder row, which requires a default value.
+    pub(crate) fn can_insert_field(&self, field: &JsonTreeSchemaField) -> bool {
+        self.enabled && self.on_insert_field.is_some() && field.default.is_some()
+    }
+
+    /// Inserts a missing field into the object at the JSON pointer.
+    pub(crate) fn insert_field(&self, pointer: JsonPointer<'a, '_>, field: &JsonTreeSchemaField) {
+        if let Some(on_insert_field) = &self.on_insert_field {
+            (on_insert_field.borrow_mut())(pointer, field);
+        }
+    }
+
+    /// Shows an editor instead of the value if it is being edited.
+    ///
+    /// Values constrained via [`JsonTree::allowed_values`](crate::JsonTree::allowed_values) are edited with a combo box,
+    /// committing the selected value. Other values are edited with a text edit, committing the text when Enter is pressed.
+    /// Editing is cancelled if the editor loses focus otherwise, e.g. when Escape is pressed.
+    pub(crate) fn show_if_editing<T: ToJsonTreeValue>(
+        &self,
+        ui: &mut Ui,
+        context: &RenderBaseValueContext<'a, '_, T>,
+    ) -> Option<Response> {
+        let (Some(edit), Some(tree_state_id)) =
+            (&mut *self.editing.borrow_mut(), self.tree_state_id())
+        else {
+            return None;
+        };
+        if self.target_id(context.pointer) != Some(edit.target_id) {
+            return None;
+        }
+        let id = Self::id(tree_state_id);
+
+        if let Some(allowed_values) = self.allowed_values(context.pointer) {
+            let mut selected = None;
+            let combo_box = ComboBox::from_id_salt(edit.target_id)
+                .selected_text(&edit.text)
+                .show_ui(ui, |ui| {
+                    for allowed_value in allowed_values {
+                        if ui
+                            .selectable_label(allowed_value == edit.text, &allowed_value)
+                            .clicked()
+                        {
+                            selected = Some(allowed_value);
+                        }
+                    }
+                });
+            let response = combo_box.response;
+
+            if let Some(selected) = selected {
+                self.commit(context.pointer, &selected);
+                ui.data_mut(|d| d.remove::<ValueEdit>(id));
+            } else if ui.input(|i| i.key_pressed(Key::Escape))
+                || (!ComboBox::is_open(ui.ctx(), response.id) && response.clicked_elsewhere())
+            {
+                ui.data_mut(|d| d.remove::<ValueEdit>(id));
+            } else {
+                ui.data_mut(|d| d.insert_temp(id, edit.clone()));
+            }
+            return Some(response);
+        }
+
+        let font_id = context
+            .style
+            .resolve_element_font_id(ui, &context.style.font_families.values);
+        let response = ui.add(TextEdit::singleline(&mut edit.text).font(font_id));
+        if edit.request_focus {
+            response.request_focus();
+            edit.request_focus = false;
+        }
+
+        if response.lost_focus() {
+            if ui.input(|i| i.key_pressed(Key::Enter)) {
+                self.commit(context.pointer, &edit.text);
+            }
+            ui.data_mut(|d| d.remove::<ValueEdit>(id));
+        } else {
+            ui.data_mut(|d| d.insert_temp(id, edit.clone()));
+        }
+        Some(response)
+    }
+
+    /// Starts editing the value when its response is double clicked,
+    /// and adjusts numbers that are dragged horizontally if configured via [`JsonTree::drag_numbers`](crate::JsonTree::drag_numbers).
+    pub(crate) fn attach<T: ToJsonTreeValue>(
+        &self,
+        response: &Response,
+        context: &RenderBaseValueContext<'a, '_, T>,
+    ) {
+        if !self.enabled || context.parent_status == ParentStatus::CollapsedRoot {
+            return;
+        }
+        let (Some(target_id), Some(tree_state_id)) =
+            (self.target_id(context.pointer), self.tree_state_id())
+        else {
+            return;
+        };
+
+        if response.double_clicked() {
+            let edit = ValueEdit {
+                target_id,
+                text: context.display_value.to_string(),
+                request_focus: true,
+            };
+            response
+                .ctx
+                .data_mut(|d| d.insert_temp(Self::id(tree_state_id), edit));
+            response.ctx.request_repaint();
+            return;
+        }
+
+        if context.value_type == BaseValueType::Number {
+            if let Some(numeric_drag) = self
+                .numeric_drag
+                .as_ref()
+                .and_then(|numeric_drag| numeric_drag(context.pointer))
+            {
+                // Dragging could produce values that are not allowed.
+                if self.allowed_values(context.pointer).is_none() {
+                    self.drag_number(response, context, target_id, &numeric_drag);
+                }
+            }
+        }
+    }
+
+    fn drag_number<T: ToJsonTreeValue>(
+        &self,
+        response: &Response,
+        context: &RenderBaseValueContext<'a, '_, T>,
+        target_id: Id,
+        numeric_drag: &JsonTreeNumericDrag,
+    ) {
+        if response.hovered() || response.dragged() {
+            response.ctx.set_cursor_icon(CursorIcon::ResizeHorizontal);
+        }
+
+        let id = target_id.with("numeric_drag");
+        if response.drag_started() {
+            let state = NumericDragState {
+                start: context.display_value.to_string(),
+                total_delta: 0.0,
+            };
+            response.ctx.data_mut(|d| d.insert_temp(id, state));
+        }
+        if response.drag_stopped() {
+            response.ctx.data_mut(|d| d.remove::<NumericDragState>(id));
+            return;
+        }
+
+        let delta = response.drag_delta().x;
+        if !response.dragged() || delta == 0.0 {
+            return;
+        }
+        let Some(mut state) = response.ctx.data(|d| d.get_temp::<NumericDragState>(id)) else {
+            return;
+        };
+        state.total_delta += delta;
+
+        if let Some(text) = numeric_drag.adjust(&state.start, state.total_delta) {
+            if text != context.display_value.to_string() {
+                self.commit(context.pointer, &text);
+            }
+        }
+        response.ctx.data_mut(|d| d.insert_temp(id, state));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JsonTreeNumericDrag;
+
+    #[test]
+    fn adjusts_numbers_by_speed_within_range() {
+        let numeric_drag = JsonTreeNumericDrag {
+            speed: 0.5,
+            range: 0.0..=10.0,
+        };
+        assert_eq!(numeric_drag.adjust("4", 3.0).as_deref(), Some("6"));
+        assert_eq!(numeric_drag.adjust("4", 100.0).as_deref(), Some("10"));
+        assert_eq!(numeric_drag.adjust("4", -100.0).as_deref(), Some("0"));
+        assert_eq!(numeric_drag.adjust("not a number", 1.0), None);
+
+        let numeric_drag = JsonTreeNumericDrag {
+            speed: 0.01,
+            ..Default::default()
+        };
+        assert_eq!(numeric_drag.adjust("1.5", 7.0).as_deref(), Some("1.57"));
+        assert_eq!(numeric_drag.adjust("1e2", -1.0).as_deref(), Some("99.99"));
+    }
+}
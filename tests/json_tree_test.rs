@@ -1,7 +1,16 @@
-use std::sync::Arc;
+use std::{cell::Cell, ops::Range, sync::Arc, time::Duration};
 
-use egui::{mutex::Mutex, CentralPanel, Context, FontDefinitions, Style};
-use egui_json_tree::{render::RenderContext, DefaultExpand, JsonTree, JsonTreeStyle};
+use egui::{mutex::Mutex, CentralPanel, Context, FontDefinitions, FontFamily, ScrollArea, Style};
+use egui_json_tree::{
+    pointer::JsonPointerSegment,
+    recording::{JsonTreeInteraction, JsonTreeRecorder, JsonTreeReplay},
+    render::{DefaultRender, RenderContext},
+    value::{BaseValueType, ExpandableType, JsonTreeValue, ToJsonTreeValue},
+    DefaultExpand, ExpandOverride, JsonForest, JsonTree, JsonTreeDensity, JsonTreeEmptyReason,
+    JsonTreeFontFamilies, JsonTreeScrollAnimation, JsonTreeSharedSearch, JsonTreeSnapshot,
+    JsonTreeSnapshotRow, JsonTreeStyle, JsonTreeSubtreeConfig, SearchCandidate, SearchOptions,
+    SearchScope, SearchWords,
+};
 #[cfg(feature = "serde_json")]
 use serde_json::{json, Value};
 
@@ -597,3 +606,3950 @@ fn json_tree_reset_expanded() {
         assert_eq!(actual, expected_nothing_expanded);
     });
 }
+
+#[test]
+fn json_tree_format_numbers() {
+    let value = json!({"price": 1234.5, "count": 3, "name": "foo"});
+
+    egui::__run_test_ui(|ui| {
+        let mut actual = vec![];
+
+        JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::All)
+            .format_numbers(|number, pointer| {
+                (pointer.to_json_pointer_string() == "/price").then(|| format!("${number}"))
+            })
+            .on_render(|_, render_ctx| {
+                if let RenderContext::BaseValue(ctx) = render_ctx {
+                    actual.push((
+                        ctx.display_value.to_string(),
+                        ctx.formatted_number().map(str::to_string),
+                    ));
+                }
+            })
+            .show(ui);
+
+        actual.sort();
+
+        let expected = vec![
+            ("1234.5".to_string(), Some("$1234.5".to_string())),
+            ("3".to_string(), None),
+            ("foo".to_string(), None),
+        ];
+
+        assert_eq!(actual, expected);
+    });
+}
+
+#[test]
+fn json_tree_filter_search_results() {
+    let value = json!({
+      "bar": { "qux": false },
+      "baz": "grep",
+      "foo": [1, 2, { "grep": [3] }]
+    });
+
+    egui::__run_test_ui(|ui| {
+        let mut actual = vec![];
+
+        JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::SearchResults("grep"))
+            .filter_search_results(true)
+            .on_render(|_, render_ctx| {
+                actual.push(render_ctx.pointer().to_json_pointer_string());
+            })
+            .show(ui);
+
+        let expected = vec![
+            "",
+            "/baz",
+            "/baz",
+            "/foo",
+            "/foo",
+            "/foo/2",
+            "/foo/2",
+            "/foo/2/grep",
+            "/foo/2/grep",
+            "/foo/2",
+            "/foo",
+            "",
+        ];
+
+        assert_eq!(actual, expected);
+    });
+}
+
+#[test]
+fn json_tree_count_hidden_entries() {
+    let value = json!({
+      "bar": { "qux": false },
+      "baz": "grep",
+      "foo": [1, 2, { "grep": [3] }]
+    });
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::SearchResults("grep"))
+            .filter_search_results(true)
+            .count_hidden_entries(true)
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"baz\": \"«grep»\"",
+                "  \"foo\": [",
+                "    2: {",
+                "      \"«grep»\": [...]",
+                "    }",
+                // Counts are recorded at the depth of their array/object, like group headings.
+                "  2 entries hidden",
+                "  ]",
+                "1 entry hidden",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+    });
+}
+
+#[test]
+fn json_tree_expansion_budget_reveals_entries_across_frames() {
+    let value = json!([1, 2, 3, 4, 5]);
+
+    // Reusing the same Context so the memory persists between multiple frames.
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+
+    for expected_num_revealed in [2, 4, 5, 5] {
+        let _ = ctx.run(Default::default(), |ctx| {
+            let mut num_revealed = 0;
+
+            CentralPanel::default().show(ctx, |ui| {
+                JsonTree::new("id", &value)
+                    .default_expand(DefaultExpand::All)
+                    .style(JsonTreeStyle::new().expansion_budget(Some(2)))
+                    .on_render(|_, render_ctx| {
+                        if matches!(render_ctx, RenderContext::BaseValue(_)) {
+                            num_revealed += 1;
+                        }
+                    })
+                    .show(ui);
+            });
+
+            assert_eq!(num_revealed, expected_num_revealed);
+        });
+    }
+}
+
+#[test]
+fn json_tree_frame_budget_degrades_after_being_exceeded() {
+    let value = json!({"foo": [1, 2, 3], "bar": "baz"});
+
+    // Reusing the same Context so the memory persists between multiple frames.
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+
+    for expected_degraded in [false, true] {
+        let _ = ctx.run(Default::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let response = JsonTree::new("id", &value)
+                    .style(JsonTreeStyle::new().frame_budget(Some(Duration::ZERO)))
+                    .show(ui);
+
+                assert_eq!(response.is_degraded(), expected_degraded);
+            });
+        });
+    }
+}
+
+#[test]
+fn json_tree_memory_usage_and_trim_caches() {
+    let value = json!({"foo": [1, 2, {"bar": null}], "baz": "qux"});
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::All)
+            .show(ui);
+
+        let memory_usage = response.memory_usage(ui.ctx());
+        assert_eq!(memory_usage.num_expanded_states, 3);
+        assert!(memory_usage.num_cached_layouts > 0);
+        assert!(memory_usage.total_bytes() > 0);
+
+        response.trim_caches(ui.ctx());
+        assert_eq!(response.memory_usage(ui.ctx()).num_cached_layouts, 0);
+    });
+}
+
+#[test]
+fn json_tree_record_and_replay_interactions() {
+    let value = json!({"foo": [{"bar": 1}, 2], "qux": "baz"});
+
+    let rendered_pointers = |ctx: &Context, search_term: &str, mut toggle: Option<&str>| {
+        let mut pointers = vec![];
+        let mut response = None;
+        let _ = ctx.run(Default::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                response = Some(
+                    JsonTree::new("id", &value)
+                        .default_expand(DefaultExpand::SearchResults(search_term))
+                        .on_render(|ui, mut render_ctx| {
+                            if let RenderContext::ExpandableDelimiter(ctx) = &mut render_ctx {
+                                if toggle == Some(&ctx.pointer.to_json_pointer_string()) {
+                                    ctx.collapsing_state.toggle(ui);
+                                    toggle = None;
+                                }
+                            }
+                            pointers.push(ExpectedRender::from(render_ctx).pointer_str);
+                        })
+                        .show(ui),
+                );
+            });
+        });
+        (pointers, response.unwrap())
+    };
+
+    // Record a sequence of interactions.
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+    let mut recorder = JsonTreeRecorder::new();
+    let mut recorded_renders = vec![];
+
+    for (search_term, toggle) in [
+        ("", Some("")),
+        ("", Some("/foo")),
+        ("", Some("/foo/0")),
+        ("baz", None),
+    ] {
+        recorder.record_search(search_term);
+        let (_, response) = rendered_pointers(&ctx, search_term, toggle);
+        recorder.record_response(&response);
+        recorded_renders.push(rendered_pointers(&ctx, search_term, None).0);
+    }
+
+    let interactions = recorder.into_interactions();
+    assert_eq!(
+        interactions,
+        vec![
+            JsonTreeInteraction::Expand {
+                pointer: "".to_string()
+            },
+            JsonTreeInteraction::Expand {
+                pointer: "/foo".to_string()
+            },
+            JsonTreeInteraction::Expand {
+                pointer: "/foo/0".to_string()
+            },
+            JsonTreeInteraction::Search {
+                term: "baz".to_string()
+            },
+        ]
+    );
+
+    // Replay the interactions onto a tree in a fresh context.
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+    let mut replay = JsonTreeReplay::new(interactions);
+    let (_, mut response) = rendered_pointers(&ctx, "", None);
+    let mut replayed_renders = vec![];
+
+    while replay.step(&ctx, &response).is_some() {
+        let (pointers, next_response) = rendered_pointers(&ctx, replay.search_term(), None);
+        replayed_renders.push(pointers);
+        response = next_response;
+    }
+
+    assert_eq!(replayed_renders, recorded_renders);
+}
+
+#[cfg(feature = "kittest")]
+#[test]
+fn json_tree_kittest_helpers() {
+    use egui_json_tree::kittest::JsonTreeHarnessExt;
+    use egui_kittest::Harness;
+
+    let value = json!({"foo": [1, {"bar": "baz"}], "qux": null});
+
+    let mut harness = Harness::new_ui_state(
+        |ui, search_term: &mut String| {
+            let search_changed = ui.text_edit_singleline(search_term).changed();
+            let response = JsonTree::new("id", &value)
+                .default_expand(DefaultExpand::SearchResults(search_term))
+                .show(ui);
+            if search_changed {
+                response.reset_expanded(ui);
+            }
+        },
+        String::new(),
+    );
+
+    assert_eq!(harness.json_tree_rows(), vec![""]);
+
+    harness.type_json_tree_search("baz");
+    assert_eq!(harness.state(), "baz");
+    assert_eq!(
+        harness.json_tree_rows(),
+        vec!["", "/foo", "/foo/0", "/foo/1", "/foo/1/bar", "/qux"]
+    );
+    assert_eq!(harness.json_tree_row_text("/foo/1/bar"), "\"bar\": \"baz\"");
+
+    harness.click_json_tree_expander("/foo");
+    assert_eq!(harness.json_tree_rows(), vec!["", "/foo", "/qux"]);
+    assert_eq!(harness.json_tree_row_text("/foo"), "\"foo\": [...]");
+}
+
+#[cfg(feature = "kittest")]
+#[test]
+fn json_tree_recursive_toggle() {
+    use egui::Modifiers;
+    use egui_json_tree::kittest::JsonTreeHarnessExt;
+    use egui_kittest::Harness;
+
+    let value = json!({"a": {"b": {"c": [1]}}, "d": [2]});
+
+    let mut harness = Harness::new_ui(|ui| {
+        JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::ToLevel(0))
+            .recursive_toggle_modifiers(Some(Modifiers::SHIFT))
+            .show(ui);
+    });
+    assert_eq!(harness.json_tree_rows(), vec!["", "/a", "/d"]);
+
+    harness.input_mut().modifiers = Modifiers::SHIFT;
+    harness.click_json_tree_expander("/a");
+    assert_eq!(
+        harness.json_tree_rows(),
+        vec!["", "/a", "/a/b", "/a/b/c", "/a/b/c/0", "/d"]
+    );
+
+    harness.click_json_tree_expander("/a");
+    harness.input_mut().modifiers = Modifiers::NONE;
+    harness.click_json_tree_expander("/a");
+    assert_eq!(harness.json_tree_rows(), vec!["", "/a", "/a/b", "/d"]);
+}
+
+#[cfg(feature = "kittest")]
+#[test]
+fn json_tree_bracket_matching() {
+    use egui::{Color32, Event, Shape};
+    use egui_json_tree::JsonTreeBracketMatching;
+    use egui_kittest::{kittest::Queryable, Harness};
+
+    let value = json!({"items": [1, 2]});
+
+    let mut harness = Harness::new_ui(|ui| {
+        JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::All)
+            .style(
+                JsonTreeStyle::new().bracket_matching(JsonTreeBracketMatching::BracketsAndSubtree),
+            )
+            .show(ui);
+    });
+
+    let count_filled = |harness: &Harness, fill: Color32| {
+        harness
+            .output()
+            .shapes
+            .iter()
+            .filter(|clipped| matches!(&clipped.shape, Shape::Rect(rect) if rect.fill == fill))
+            .count()
+    };
+    let selection_fill = egui::Visuals::dark().selection.bg_fill;
+    let faint_fill = egui::Visuals::dark().faint_bg_color;
+    assert_eq!(count_filled(&harness, selection_fill), 0);
+    let faint_fills_before_hover = count_filled(&harness, faint_fill);
+
+    let bounds = harness.get_by_label("]").raw_bounds().unwrap();
+    let pos = egui::pos2(
+        (bounds.x0 + bounds.x1) as f32 / 2.0,
+        (bounds.y0 + bounds.y1) as f32 / 2.0,
+    );
+    harness.input_mut().events.push(Event::PointerMoved(pos));
+    harness.run();
+
+    // Both the opening and closing brackets of the hovered array are highlighted, along with the array itself.
+    assert_eq!(count_filled(&harness, selection_fill), 2);
+    assert_eq!(
+        count_filled(&harness, faint_fill),
+        faint_fills_before_hover + 1
+    );
+}
+
+#[cfg(feature = "kittest")]
+#[test]
+fn json_tree_closing_delimiters() {
+    use egui_json_tree::JsonTreeClosingDelimiters;
+    use egui_kittest::{kittest::Queryable, Harness};
+
+    let value = json!({"items": [1]});
+
+    for (closing_delimiters, is_aligned) in [
+        (JsonTreeClosingDelimiters::Compact, false),
+        (JsonTreeClosingDelimiters::AlignedWithOpener, true),
+    ] {
+        let harness = Harness::new_ui(|ui| {
+            JsonTree::new("id", &value)
+                .default_expand(DefaultExpand::All)
+                .style(JsonTreeStyle::new().closing_delimiters(closing_delimiters))
+                .show(ui);
+        });
+
+        let key_left = harness.get_by_label("\"items\"").raw_bounds().unwrap().x0;
+        let closing_left = harness.get_by_label("]").raw_bounds().unwrap().x0;
+        assert_eq!(closing_left == key_left, is_aligned);
+        assert!(closing_left <= key_left);
+    }
+}
+
+#[cfg(feature = "kittest")]
+#[test]
+fn json_tree_long_string_viewer() {
+    use egui::accesskit::Role;
+    use egui_json_tree::JsonTreeLongStringViewer;
+    use egui_kittest::{kittest::Queryable, Harness};
+
+    let value = json!({"short": "abc", "trace": format!("{}needle", "frame\n".repeat(50))});
+
+    let mut harness = Harness::new_ui(|ui| {
+        JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::All)
+            .style(
+                JsonTreeStyle::new().long_string_viewer(Some(JsonTreeLongStringViewer {
+                    min_chars: 20,
+                    ..Default::default()
+                })),
+            )
+            .show(ui);
+    });
+
+    // Only the long string can be viewed.
+    assert_eq!(harness.query_all_by_label("view").count(), 1);
+    assert!(harness.query_by_label("Find:").is_none());
+
+    harness.get_by_label("view").click();
+    harness.run();
+    assert!(harness.query_by_label("Find:").is_some());
+
+    harness.get_by_role(Role::TextInput).focus();
+    harness.run();
+    harness.get_by_role(Role::TextInput).type_text("needle");
+    harness.run();
+    assert!(harness.query_by_label("1 match").is_some());
+
+    harness.get_by_label("hide").click();
+    harness.run();
+    assert!(harness.query_by_label("Find:").is_none());
+    assert!(harness.query_by_label("view").is_some());
+}
+
+#[cfg(feature = "kittest")]
+#[test]
+fn json_tree_context_menu_shortcut() {
+    use egui::{accesskit::Role, Event};
+    use egui_json_tree::{context_menu::OPEN_SHORTCUT, kittest::JsonTreeHarnessExt};
+    use egui_kittest::{kittest::Queryable, Harness};
+
+    let value = json!({"a": 1, "b": 2});
+
+    let mut harness = Harness::new_ui_state(
+        |ui, hooked_pointers: &mut Vec<String>| {
+            JsonTree::new("id", &value)
+                .default_expand(DefaultExpand::All)
+                .context_menu(true)
+                .on_context_menu(|_, pointer, _| {
+                    hooked_pointers.push(pointer.to_json_pointer_string());
+                })
+                .show(ui);
+        },
+        Vec::new(),
+    );
+
+    harness
+        .json_tree_row("/b")
+        .query_all_by_role(Role::Label)
+        .next()
+        .unwrap()
+        .focus();
+    harness.run();
+    assert!(harness.query_by_label("Copy JSON pointer").is_none());
+
+    harness.input_mut().events.push(Event::Key {
+        key: OPEN_SHORTCUT.logical_key,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: OPEN_SHORTCUT.modifiers,
+    });
+    harness.run();
+    assert!(harness.query_by_label("Copy JSON pointer").is_some());
+    assert_eq!(harness.state().last().unwrap(), "/b");
+}
+
+#[cfg(feature = "kittest")]
+#[test]
+fn json_tree_context_menu() {
+    use egui::{accesskit::Role, Event, PointerButton};
+    use egui_json_tree::{context_menu::ContextMenuEntry, kittest::JsonTreeHarnessExt};
+    use egui_kittest::{kittest::Queryable, Harness};
+
+    let value =
+        json!({"items": [1, 2], "rows": [{"a": 1, "b": "x,y"}, {"a": 2}], "secret": "hunter2"});
+
+    let mut harness = Harness::new_ui_state(
+        |ui, hooked_pointers: &mut Vec<String>| {
+            JsonTree::new("id", &value)
+                .default_expand(DefaultExpand::All)
+                .context_menu(true)
+                .context_menu_entries(|entry, pointer, _| {
+                    entry != ContextMenuEntry::CopyValue
+                        || pointer.to_json_pointer_string() != "/secret"
+                })
+                .on_context_menu(|ui, pointer, _| {
+                    hooked_pointers.push(pointer.to_json_pointer_string());
+                    ui.label("Custom item");
+                })
+                .show(ui);
+        },
+        Vec::new(),
+    );
+
+    let right_click_row = |harness: &mut Harness<'_, Vec<String>>, pointer: &str| {
+        let bounds = harness
+            .json_tree_row(pointer)
+            .query_all_by_role(Role::Label)
+            .next()
+            .unwrap()
+            .raw_bounds()
+            .unwrap();
+        let pos = egui::pos2(
+            (bounds.x0 + bounds.x1) as f32 / 2.0,
+            (bounds.y0 + bounds.y1) as f32 / 2.0,
+        );
+        let input = harness.input_mut();
+        input.events.push(Event::PointerMoved(pos));
+        for pressed in [true, false] {
+            input.events.push(Event::PointerButton {
+                pos,
+                button: PointerButton::Secondary,
+                pressed,
+                modifiers: Default::default(),
+            });
+        }
+        harness.run();
+    };
+
+    right_click_row(&mut harness, "/secret");
+    assert!(harness.query_by_label("Copy JSON pointer").is_some());
+    assert!(harness.query_by_label("Copy value").is_none());
+    assert!(harness.query_by_label("Expand all").is_none());
+    assert!(harness.query_by_label("Copy as CSV").is_none());
+    assert!(harness.query_by_label("Custom item").is_some());
+    assert_eq!(harness.state().last().unwrap(), "/secret");
+
+    harness.get_by_label("Copy JSON pointer").click();
+    harness.step();
+    assert_eq!(harness.output().platform_output.copied_text, "/secret");
+    harness.run();
+    assert!(harness.query_by_label("Custom item").is_none());
+
+    right_click_row(&mut harness, "/items");
+    assert!(harness.query_by_label("Copy value").is_some());
+    assert!(harness.query_by_label("Collapse all").is_some());
+    assert!(harness.query_by_label("Copy as CSV").is_none());
+    assert_eq!(harness.state().last().unwrap(), "/items");
+
+    harness.get_by_label("Copy value").click();
+    harness.step();
+    assert_eq!(
+        harness.output().platform_output.copied_text,
+        "[\n  1,\n  2\n]"
+    );
+    harness.run();
+
+    right_click_row(&mut harness, "/items");
+    harness.get_by_label("Copy as Rust literal").click();
+    harness.step();
+    assert_eq!(
+        harness.output().platform_output.copied_text,
+        "serde_json::json!([\n  1,\n  2\n])"
+    );
+    harness.run();
+
+    right_click_row(&mut harness, "/secret");
+    harness.get_by_label("Copy as Python literal").click();
+    harness.step();
+    assert_eq!(harness.output().platform_output.copied_text, "\"hunter2\"");
+    harness.run();
+
+    right_click_row(&mut harness, "/rows");
+    harness.get_by_label("Copy as CSV").click();
+    harness.step();
+    assert_eq!(
+        harness.output().platform_output.copied_text,
+        "a,b\r\n1,\"x,y\"\r\n2,"
+    );
+    harness.run();
+
+    right_click_row(&mut harness, "/rows");
+    harness.get_by_label("Copy as TSV").click();
+    harness.step();
+    assert_eq!(
+        harness.output().platform_output.copied_text,
+        "a\tb\r\n1\tx,y\r\n2\t"
+    );
+    harness.run();
+
+    right_click_row(&mut harness, "/items");
+    harness.get_by_label("Collapse all").click();
+    harness.run();
+    // Wait for the collapse animations to finish.
+    while harness.ctx.has_requested_repaint() {
+        harness.step();
+    }
+    assert_eq!(
+        harness.json_tree_rows(),
+        vec![
+            "",
+            "/items",
+            "/rows",
+            "/rows/0",
+            "/rows/0/a",
+            "/rows/0/b",
+            "/rows/1",
+            "/rows/1/a",
+            "/secret"
+        ]
+    );
+}
+
+#[cfg(feature = "kittest")]
+#[test]
+fn json_tree_group_entries() {
+    use egui_json_tree::kittest::JsonTreeHarnessExt;
+    use egui_kittest::{kittest::Queryable, Harness};
+
+    let value = json!({"accept": "*/*", "content-type": "text/plain", "x-a": 1, "x-b": {"c": 2}});
+
+    let mut harness = Harness::new_ui_state(
+        |ui, snapshot: &mut String| {
+            let response = JsonTree::new("id", &value)
+                .default_expand(DefaultExpand::All)
+                .group_entries(|pointer, key| {
+                    (pointer.to_json_pointer_string().is_empty() && key.starts_with("x-"))
+                        .then(|| "Extensions".to_string())
+                })
+                .record_snapshot(true)
+                .show(ui);
+            *snapshot = response.snapshot().unwrap().to_string();
+        },
+        String::new(),
+    );
+    harness.run();
+
+    assert_eq!(
+        harness.state(),
+        &[
+            "{",
+            "  \"accept\": \"*/*\"",
+            "  \"content-type\": \"text/plain\"",
+            "Extensions (2)",
+            "  \"x-a\": 1",
+            "  \"x-b\": {",
+            "    \"c\": 2",
+            "  }",
+            "}",
+            "",
+        ]
+        .join("\n")
+    );
+    // Group headings don't affect pointers.
+    assert_eq!(
+        harness.json_tree_rows(),
+        vec!["", "/accept", "/content-type", "/x-a", "/x-b", "/x-b/c"]
+    );
+
+    harness.get_by_label("Extensions (2)").click();
+    harness.run();
+    // Wait for the collapse animation to finish.
+    while harness.ctx.has_requested_repaint() {
+        harness.step();
+    }
+    assert_eq!(
+        harness.json_tree_rows(),
+        vec!["", "/accept", "/content-type"]
+    );
+}
+
+#[cfg(feature = "kittest")]
+#[test]
+fn json_tree_schema_fields() {
+    use egui_json_tree::JsonTreeSchemaField;
+    use egui_kittest::{kittest::Queryable, Harness};
+
+    let value = json!({"server": {"host": "localhost"}});
+
+    let mut harness = Harness::new_ui_state(
+        |ui, (snapshot, inserted): &mut (String, Vec<String>)| {
+            let response = JsonTree::new("id", &value)
+                .default_expand(DefaultExpand::All)
+                .schema_fields(|pointer| match pointer.to_json_pointer_string().as_str() {
+                    "/server" => vec![
+                        JsonTreeSchemaField::new("host").with_default("0.0.0.0"),
+                        JsonTreeSchemaField::new("port").with_default("8080"),
+                        JsonTreeSchemaField::new("timeout"),
+                    ],
+                    _ => vec![],
+                })
+                .editable_values(true)
+                .on_insert_field(|pointer, field| {
+                    inserted.push(format!(
+                        "{}/{}: {}",
+                        pointer.to_json_pointer_string(),
+                        field.key,
+                        field.default.as_deref().unwrap_or_default()
+                    ));
+                })
+                .record_snapshot(true)
+                .show(ui);
+            *snapshot = response.snapshot().unwrap().to_string();
+        },
+        (String::new(), vec![]),
+    );
+    harness.run();
+
+    // Fields that are present are not shown as placeholders.
+    assert_eq!(
+        harness.state().0,
+        [
+            "{",
+            "  \"server\": {",
+            "    \"host\": \"localhost\"",
+            "    \"port\": <unset, default 8080>",
+            "    \"timeout\": <unset>",
+            "  }",
+            "}",
+            "",
+        ]
+        .join("\n")
+    );
+
+    harness.get_by_label("\"timeout\": <unset>").click();
+    harness.run();
+    assert!(harness.state().1.is_empty());
+
+    harness
+        .get_by_label("\"port\": <unset, default 8080>")
+        .click();
+    harness.run();
+    assert_eq!(harness.state().1, vec!["/server/port: 8080"]);
+}
+
+#[cfg(feature = "kittest")]
+#[test]
+fn json_tree_provenance_tooltip() {
+    use egui::Event;
+    use egui_json_tree::{kittest::JsonTreeHarnessExt, JsonTreeProvenanceDisplay};
+    use egui_kittest::{kittest::Queryable, Harness};
+
+    let value = json!({"port": 8080});
+
+    let mut harness = Harness::new_ui(|ui| {
+        JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::All)
+            .style(JsonTreeStyle::new().provenance_display(JsonTreeProvenanceDisplay::Tooltip))
+            .provenance(|pointer| {
+                (pointer.to_json_pointer_string() == "/port").then(|| "override.toml:3".to_string())
+            })
+            .show(ui);
+    });
+    harness.run();
+    assert!(harness.query_by_label("override.toml:3").is_none());
+
+    let bounds = harness.json_tree_row("/port").raw_bounds().unwrap();
+    let pos = egui::pos2(
+        (bounds.x0 + bounds.x1) as f32 / 2.0,
+        (bounds.y0 + bounds.y1) as f32 / 2.0,
+    );
+    harness.input_mut().events.push(Event::PointerMoved(pos));
+    harness.run();
+    assert!(harness.query_by_label("override.toml:3").is_some());
+}
+
+#[cfg(feature = "kittest")]
+#[test]
+fn json_tree_split_view() {
+    use egui_json_tree::JsonTreeSplitView;
+    use egui_kittest::{kittest::Queryable, Harness};
+
+    let value = json!({"user": {"id": 1, "name": "Ferris"}});
+    let selected = Cell::new(None::<String>);
+
+    let mut harness = Harness::new_ui(|ui| {
+        let response =
+            JsonTreeSplitView::new(JsonTree::new("id", &value).default_expand(DefaultExpand::All))
+                .on_detail(|ui, pointer, _| {
+                    ui.label(format!(
+                        "Actions for {pointer}",
+                        pointer = pointer.to_json_pointer_string()
+                    ));
+                })
+                .show(ui);
+        selected.set(response.selected_pointer().map(str::to_owned));
+    });
+    harness.run();
+    assert_eq!(selected.take(), None);
+    assert!(harness
+        .query_by_label("Select a value to see its details.")
+        .is_some());
+
+    harness.get_by_label("\"name\"").click();
+    harness.run();
+    assert_eq!(selected.take().as_deref(), Some("/user/name"));
+    assert!(harness.query_by_label("/user/name").is_some());
+    assert!(harness.query_by_label("string").is_some());
+    assert!(harness.query_by_label("Actions for /user/name").is_some());
+
+    harness.get_by_label("\"user\"").click();
+    harness.run();
+    assert_eq!(selected.take().as_deref(), Some("/user"));
+    assert!(harness.query_by_label("object (2 keys)").is_some());
+}
+
+#[cfg(feature = "kittest")]
+#[test]
+fn json_tree_on_toggle() {
+    use egui_json_tree::kittest::JsonTreeHarnessExt;
+    use egui_kittest::Harness;
+
+    let value = json!({"foo": [1, {"bar": "baz"}], "qux": null});
+    let toggled = Mutex::new(vec![]);
+
+    let mut harness = Harness::new_ui(|ui| {
+        JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::ToLevel(0))
+            .on_toggle(|pointer, is_open| {
+                toggled
+                    .lock()
+                    .push((pointer.to_json_pointer_string(), is_open));
+            })
+            .show(ui);
+    });
+    harness.run();
+    // Arrays/objects expanded by default are not reported.
+    assert!(toggled.lock().is_empty());
+
+    harness.click_json_tree_expander("/foo");
+    assert_eq!(*toggled.lock(), vec![("/foo".to_string(), true)]);
+
+    harness.click_json_tree_expander("");
+    assert_eq!(
+        *toggled.lock(),
+        vec![("/foo".to_string(), true), ("".to_string(), false)]
+    );
+}
+
+#[cfg(feature = "kittest")]
+#[test]
+fn json_tree_context_menu_copy_uuid() {
+    use egui::{accesskit::Role, Event, PointerButton};
+    use egui_json_tree::{kittest::JsonTreeHarnessExt, UuidDisplay};
+    use egui_kittest::{kittest::Queryable, Harness};
+
+    let value = json!({"id": "550e8400-e29b-41d4-a716-446655440000", "name": "x"});
+
+    let mut harness = Harness::new_ui(|ui| {
+        JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::All)
+            .style(JsonTreeStyle::new().uuid_display(UuidDisplay::Shortened))
+            .context_menu(true)
+            .show(ui);
+    });
+
+    let right_click_value = |harness: &mut Harness<'_>, pointer: &str| {
+        let bounds = harness
+            .json_tree_row(pointer)
+            .query_all_by_role(Role::Label)
+            .last()
+            .unwrap()
+            .raw_bounds()
+            .unwrap();
+        let pos = egui::pos2(
+            (bounds.x0 + bounds.x1) as f32 / 2.0,
+            (bounds.y0 + bounds.y1) as f32 / 2.0,
+        );
+        let input = harness.input_mut();
+        input.events.push(Event::PointerMoved(pos));
+        for pressed in [true, false] {
+            input.events.push(Event::PointerButton {
+                pos,
+                button: PointerButton::Secondary,
+                pressed,
+                modifiers: Default::default(),
+            });
+        }
+        harness.run();
+    };
+
+    right_click_value(&mut harness, "/name");
+    assert!(harness.query_by_label("Copy value").is_some());
+    assert!(harness.query_by_label("Copy UUID").is_none());
+
+    right_click_value(&mut harness, "/id");
+    harness.get_by_label("Copy UUID").click();
+    harness.step();
+    // The full UUID is copied, without quotes, even though it is displayed shortened.
+    assert_eq!(
+        harness.output().platform_output.copied_text,
+        "550e8400-e29b-41d4-a716-446655440000"
+    );
+}
+
+#[cfg(feature = "kittest")]
+#[test]
+fn json_tree_copy_on_double_click() {
+    use egui::{accesskit::Role, Event, PointerButton};
+    use egui_json_tree::kittest::JsonTreeHarnessExt;
+    use egui_kittest::{kittest::Queryable, Harness};
+
+    let value = json!({"a": "text", "b": 1.5});
+
+    let mut harness = Harness::new_ui(|ui| {
+        JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::All)
+            .copy_on_double_click(true)
+            .show(ui);
+    });
+
+    let bounds = harness
+        .json_tree_row("/a")
+        .query_all_by_role(Role::Label)
+        .last()
+        .unwrap()
+        .raw_bounds()
+        .unwrap();
+    let pos = egui::pos2(
+        (bounds.x0 + bounds.x1) as f32 / 2.0,
+        (bounds.y0 + bounds.y1) as f32 / 2.0,
+    );
+    harness.input_mut().events.push(Event::PointerMoved(pos));
+    harness.run();
+    assert!(harness.query_by_label("Copied").is_none());
+    for _ in 0..2 {
+        for pressed in [true, false] {
+            harness.input_mut().events.push(Event::PointerButton {
+                pos,
+                button: PointerButton::Primary,
+                pressed,
+                modifiers: Default::default(),
+            });
+        }
+    }
+    harness.step();
+    assert_eq!(harness.output().platform_output.copied_text, "text");
+    harness.step();
+    assert!(harness.query_by_label("Copied").is_some());
+
+    for _ in 0..90 {
+        harness.step();
+    }
+    assert!(harness.query_by_label("Copied").is_none());
+}
+
+#[cfg(feature = "kittest")]
+#[test]
+fn json_tree_allowed_values() {
+    use egui::{accesskit::Role, Event, PointerButton};
+    use egui_json_tree::kittest::JsonTreeHarnessExt;
+    use egui_kittest::{kittest::Queryable, Harness};
+
+    let value = json!({"level": "info", "retries": 3});
+
+    let mut harness = Harness::new_ui_state(
+        |ui, edits: &mut Vec<(String, String)>| {
+            JsonTree::new("id", &value)
+                .default_expand(DefaultExpand::All)
+                .editable_values(true)
+                .allowed_values(|pointer| {
+                    (pointer.to_json_pointer_string() == "/level")
+                        .then(|| vec!["debug".to_string(), "info".to_string(), "warn".to_string()])
+                })
+                .on_value_edit(|pointer, text| {
+                    edits.push((pointer.to_json_pointer_string(), text.to_string()));
+                })
+                .show(ui);
+        },
+        Vec::new(),
+    );
+
+    let double_click_value = |harness: &mut Harness<'_, Vec<(String, String)>>, pointer: &str| {
+        let bounds = harness
+            .json_tree_row(pointer)
+            .query_all_by_role(Role::Label)
+            .last()
+            .unwrap()
+            .raw_bounds()
+            .unwrap();
+        let pos = egui::pos2(
+            (bounds.x0 + bounds.x1) as f32 / 2.0,
+            (bounds.y0 + bounds.y1) as f32 / 2.0,
+        );
+        harness.input_mut().events.push(Event::PointerMoved(pos));
+        harness.run();
+        for _ in 0..2 {
+            for pressed in [true, false] {
+                harness.input_mut().events.push(Event::PointerButton {
+                    pos,
+                    button: PointerButton::Primary,
+                    pressed,
+                    modifiers: Default::default(),
+                });
+            }
+        }
+        harness.run();
+    };
+
+    // Unconstrained values are edited as free text.
+    double_click_value(&mut harness, "/retries");
+    assert!(harness.query_by_role(Role::TextInput).is_some());
+    harness.input_mut().events.push(Event::Key {
+        key: egui::Key::Escape,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: Default::default(),
+    });
+    harness.run();
+    assert!(harness.query_by_role(Role::TextInput).is_none());
+
+    // Wait long enough for the next clicks not to count as a triple click.
+    for _ in 0..30 {
+        harness.step();
+    }
+    double_click_value(&mut harness, "/level");
+    assert!(harness.query_by_role(Role::TextInput).is_none());
+    harness.get_by_role(Role::ComboBox).click();
+    harness.run();
+    harness.get_by_label("warn").click();
+    harness.run();
+
+    assert_eq!(
+        harness.state(),
+        &vec![("/level".to_string(), "warn".to_string())]
+    );
+    assert!(harness.query_by_role(Role::ComboBox).is_none());
+}
+
+#[test]
+fn json_tree_record_snapshot() {
+    let value = json!({"bar": [1, "baz"], "foo": {}});
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::SearchResults("ba"))
+            .record_snapshot(true)
+            .show(ui);
+
+        let snapshot = response.snapshot().unwrap();
+        assert_eq!(
+            snapshot.rows[1],
+            JsonTreeSnapshotRow {
+                pointer: "/bar".to_string(),
+                depth: 1,
+                text: "\"bar\": [".to_string(),
+                highlights: vec![Range { start: 1, end: 3 }],
+            }
+        );
+        assert_eq!(
+            snapshot.to_string(),
+            [
+                "{",
+                "  \"«ba»r\": [",
+                "    0: 1",
+                "    1: \"«ba»z\"",
+                "  ]",
+                "  \"foo\": {}",
+                "}",
+                "",
+            ]
+            .join("\n")
+        );
+
+        let response = JsonTree::new("id", &value).show(ui);
+        assert!(response.snapshot().is_none());
+    });
+}
+
+#[test]
+fn json_tree_density_presets() {
+    let value = json!({"foo": [1, 2, {"bar": null}], "baz": "qux"});
+
+    egui::__run_test_ui(|ui| {
+        let mut tree_height = |density| {
+            ui.scope(|ui| {
+                JsonTree::new(("id", density), &value)
+                    .style(JsonTreeStyle::new().density(density))
+                    .default_expand(DefaultExpand::All)
+                    .show(ui);
+            })
+            .response
+            .rect
+            .height()
+        };
+
+        let compact = tree_height(JsonTreeDensity::Compact);
+        let comfortable = tree_height(JsonTreeDensity::Comfortable);
+        let spacious = tree_height(JsonTreeDensity::Spacious);
+
+        assert!(compact < comfortable);
+        assert!(comfortable < spacious);
+    });
+}
+
+#[test]
+fn json_tree_scroll_to_pointer() {
+    let value = json!({"items": (0..200).collect::<Vec<_>>()});
+
+    let scroll_offsets = |scroll_animation: JsonTreeScrollAnimation| {
+        let ctx = Context::default();
+        ctx.set_fonts(FontDefinitions::empty());
+
+        let mut offsets = vec![];
+        for frame in 0..20 {
+            let input = egui::RawInput {
+                time: Some(frame as f64 * 0.05),
+                ..Default::default()
+            };
+            let _ = ctx.run(input, |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    let output = ScrollArea::vertical().max_height(100.0).show(ui, |ui| {
+                        JsonTree::new("id", &value)
+                            .style(JsonTreeStyle::new().scroll_animation(scroll_animation))
+                            .show(ui)
+                    });
+                    if frame == 0 {
+                        output.inner.scroll_to_pointer(ui.ctx(), "/items/150");
+                    }
+                    offsets.push(output.state.offset.y);
+                });
+            });
+        }
+        assert!(!ctx.has_requested_repaint());
+        offsets
+    };
+
+    let instant = scroll_offsets(JsonTreeScrollAnimation::default());
+    let target_offset = *instant.last().unwrap();
+    assert!(target_offset > 0.0);
+    assert!(instant[3..].iter().all(|offset| *offset == target_offset));
+
+    let animated = scroll_offsets(JsonTreeScrollAnimation {
+        duration: Duration::from_millis(400),
+        easing: egui::emath::easing::linear,
+        pulse_duration: Duration::from_millis(200),
+    });
+    assert_eq!(*animated.last().unwrap(), target_offset);
+    assert!(animated.windows(2).all(|pair| pair[0] <= pair[1]));
+    // Linear easing scrolls the same distance each frame,
+    // except at first while the scroll range is limited by the expanding root object.
+    let steps = animated
+        .windows(2)
+        .map(|pair| pair[1] - pair[0])
+        .filter(|step| *step > 0.0)
+        .collect::<Vec<_>>();
+    assert_eq!(steps.len(), 8);
+    assert!(steps[1..].iter().all(|step| (step - steps[1]).abs() < 1.0));
+}
+
+#[test]
+fn json_tree_match_navigation() {
+    use egui_json_tree::JsonTreeResponse;
+
+    let value = json!({"items": (0..200).collect::<Vec<_>>()});
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+
+    let run_frame = |frame: usize, navigate: &dyn Fn(&Context, &JsonTreeResponse)| {
+        let mut result = (None, 0, 0.0);
+        let input = egui::RawInput {
+            time: Some(frame as f64 * 0.05),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let output = ScrollArea::vertical().max_height(100.0).show(ui, |ui| {
+                    JsonTree::new("id", &value)
+                        .default_expand(DefaultExpand::SearchResults("99"))
+                        .show(ui)
+                });
+                navigate(ui.ctx(), &output.inner);
+                result = (
+                    output.inner.current_match(),
+                    output.inner.search_match_count(),
+                    output.state.offset.y,
+                );
+            });
+        });
+        result
+    };
+
+    let mut frame = 0;
+    let mut step = |navigate: &dyn Fn(&Context, &JsonTreeResponse)| {
+        run_frame(frame, navigate);
+        frame += 1;
+        let mut result = run_frame(frame, &|_, _| {});
+        for _ in 0..5 {
+            frame += 1;
+            result = run_frame(frame, &|_, _| {});
+        }
+        result
+    };
+
+    // "99", "199"
+    assert_eq!(step(&|_, _| {}), (None, 2, 0.0));
+    assert_eq!(
+        step(&|ctx, response| response.scroll_to_next_match(ctx)).0,
+        Some(0)
+    );
+    assert_eq!(
+        step(&|ctx, response| response.scroll_to_next_match(ctx)).0,
+        Some(1)
+    );
+    // Wraps around to the first match.
+    let (current, _, first_match_offset) =
+        step(&|ctx, response| response.scroll_to_next_match(ctx));
+    assert_eq!(current, Some(0));
+    assert!(first_match_offset > 0.0);
+    assert_eq!(
+        step(&|ctx, response| response.scroll_to_previous_match(ctx)).0,
+        Some(1)
+    );
+    let (current, _, last_match_offset) = step(&|ctx, response| response.scroll_to_match(ctx, 1));
+    assert_eq!(current, Some(1));
+    assert!(last_match_offset > first_match_offset);
+}
+
+#[test]
+fn json_tree_scroll_to_first_match() {
+    let value = json!({"items": (0..200).collect::<Vec<_>>()});
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+
+    let run_frame = |frame: usize, search_term: &str| {
+        let mut offset = 0.0;
+        let input = egui::RawInput {
+            time: Some(frame as f64 * 0.05),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let output = ScrollArea::vertical().max_height(100.0).show(ui, |ui| {
+                    JsonTree::new("id", &value)
+                        .default_expand(DefaultExpand::SearchResults(search_term))
+                        .scroll_to_first_match(true)
+                        .show(ui)
+                });
+                offset = output.state.offset.y;
+            });
+        });
+        offset
+    };
+    let mut frame = 0;
+    let mut settle = |search_term: &str| {
+        let mut offset = 0.0;
+        for _ in 0..10 {
+            offset = run_frame(frame, search_term);
+            frame += 1;
+        }
+        offset
+    };
+
+    let first_match_offset = settle("150");
+    assert!(first_match_offset > 0.0);
+    // Only a newly applied search term is scrolled to.
+    assert_eq!(settle("150"), first_match_offset);
+    assert!(settle("50") < first_match_offset);
+}
+
+#[test]
+fn json_tree_focus_follows_search() {
+    use egui::{Event, RawInput};
+    use egui_json_tree::context_menu::OPEN_SHORTCUT;
+
+    let value = json!({"a": {"id": 1}, "b": {"id": 2}});
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+
+    let run_frame = |events: Vec<Event>, navigate: bool| {
+        let mut hooked_pointer = None;
+        let _ = ctx.run(
+            RawInput {
+                events,
+                ..Default::default()
+            },
+            |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    let response = JsonTree::new("id", &value)
+                        .default_expand(DefaultExpand::SearchResults("id"))
+                        .focus_follows_search(true)
+                        .on_context_menu(|_, pointer, _| {
+                            hooked_pointer = Some(pointer.to_json_pointer_string());
+                        })
+                        .show(ui);
+                    if navigate {
+                        response.scroll_to_match(ui.ctx(), 1);
+                    }
+                });
+            },
+        );
+        hooked_pointer
+    };
+
+    run_frame(vec![], false);
+    assert_eq!(ctx.memory(|m| m.focused()), None);
+    run_frame(vec![], true);
+    for _ in 0..10 {
+        run_frame(vec![], false);
+    }
+    assert!(ctx.memory(|m| m.focused()).is_some());
+
+    let open_context_menu = Event::Key {
+        key: OPEN_SHORTCUT.logical_key,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: OPEN_SHORTCUT.modifiers,
+    };
+    assert_eq!(
+        run_frame(vec![open_context_menu], false).as_deref(),
+        Some("/b/id")
+    );
+}
+
+#[test]
+fn json_tree_key_highlight_color() {
+    use egui::{Color32, Shape};
+    use egui_json_tree::JsonTreeVisuals;
+
+    let value = json!({"a": {"baz": "baz 1"}});
+    let visuals = JsonTreeVisuals {
+        highlight_color: Color32::BLUE,
+        key_highlight_color: Some(Color32::GREEN),
+        ..Default::default()
+    };
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+    let output = ctx.run(Default::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            JsonTree::new("id", &value)
+                .default_expand(DefaultExpand::SearchResults("baz"))
+                .style(JsonTreeStyle::new().visuals(visuals.clone()))
+                .show(ui);
+        });
+    });
+
+    // The texts of the galleys with a match highlighted in each color.
+    let mut highlighted = (vec![], vec![]);
+    for clipped in &output.shapes {
+        if let Shape::Text(text) = &clipped.shape {
+            let job = &text.galley.job;
+            if job
+                .sections
+                .iter()
+                .any(|s| s.format.background == Color32::GREEN)
+            {
+                highlighted.0.push(job.text.clone());
+            }
+            if job
+                .sections
+                .iter()
+                .any(|s| s.format.background == Color32::BLUE)
+            {
+                highlighted.1.push(job.text.clone());
+            }
+        }
+    }
+    assert_eq!(highlighted.0, vec!["\"baz\""]);
+    assert_eq!(highlighted.1, vec!["\"baz 1\""]);
+}
+
+#[test]
+fn json_tree_drop_targets() {
+    use egui::{DragAndDrop, Event, Pos2, Rect};
+    use egui_json_tree::JsonTreeDropTarget;
+    use std::collections::HashMap;
+
+    struct PaletteItem;
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+    let value = json!({"plugins": ["a", "b"]});
+
+    let run_frame = |hover_pos: Option<Pos2>, dragging: bool| {
+        let mut result = (None, HashMap::new());
+        let mut input = egui::RawInput::default();
+        input.events.extend(hover_pos.map(Event::PointerMoved));
+        let _ = ctx.run(input, |ctx| {
+            if dragging {
+                DragAndDrop::set_payload(ctx, PaletteItem);
+            }
+            CentralPanel::default().show(ctx, |ui| {
+                let response = JsonTree::new("id", &value)
+                    .default_expand(DefaultExpand::All)
+                    .drop_targets::<PaletteItem>()
+                    .show(ui);
+                result = (
+                    response.drop_target().cloned(),
+                    ["/plugins", "/plugins/1"]
+                        .into_iter()
+                        .map(|pointer| (pointer, response.rect_of(pointer).unwrap()))
+                        .collect::<HashMap<_, Rect>>(),
+                );
+            });
+        });
+        result
+    };
+    let target = |hovered_pointer: &str, parent_pointer: &str, index| JsonTreeDropTarget {
+        hovered_pointer: hovered_pointer.to_owned(),
+        parent_pointer: parent_pointer.to_owned(),
+        index,
+    };
+
+    let (drop_target, rects) = run_frame(None, false);
+    assert_eq!(drop_target, None);
+    let upper = |pointer: &str| rects[pointer].center_top() + egui::vec2(0.0, 1.0);
+    let lower = |pointer: &str| rects[pointer].center_bottom() - egui::vec2(0.0, 1.0);
+
+    // Nothing is targeted unless an accepted payload is dragged.
+    assert_eq!(run_frame(Some(upper("/plugins/1")), false).0, None);
+
+    assert_eq!(
+        run_frame(Some(upper("/plugins/1")), true).0,
+        Some(target("/plugins/1", "/plugins", 1))
+    );
+    assert_eq!(
+        run_frame(Some(lower("/plugins/1")), true).0,
+        Some(target("/plugins/1", "/plugins", 2))
+    );
+    // The lower half of an expanded array/object targets its first entry.
+    assert_eq!(
+        run_frame(Some(lower("/plugins")), true).0,
+        Some(target("/plugins", "/plugins", 0))
+    );
+    assert_eq!(
+        run_frame(Some(upper("/plugins")), true).0,
+        Some(target("/plugins", "", 0))
+    );
+}
+
+#[test]
+fn json_tree_current_match_highlight() {
+    use egui::{Color32, Shape};
+    use egui_json_tree::JsonTreeVisuals;
+
+    let value = json!({"a": "baz 1", "b": "baz 2"});
+    let visuals = JsonTreeVisuals {
+        highlight_color: Color32::BLUE,
+        current_match_highlight_color: Color32::RED,
+        ..Default::default()
+    };
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+
+    let run_frame = |scroll_to_match: Option<usize>| {
+        let output = ctx.run(Default::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let response = JsonTree::new("id", &value)
+                    .default_expand(DefaultExpand::SearchResults("baz"))
+                    .style(JsonTreeStyle::new().visuals(visuals.clone()))
+                    .show(ui);
+                if let Some(index) = scroll_to_match {
+                    response.scroll_to_match(ui.ctx(), index);
+                }
+            });
+        });
+        // The texts of the galleys with matches highlighted in each color.
+        let mut highlighted = (vec![], vec![]);
+        for clipped in &output.shapes {
+            if let Shape::Text(text) = &clipped.shape {
+                eprintln!(
+                    "{:?} {:?}",
+                    text.galley.job.text,
+                    text.galley
+                        .job
+                        .sections
+                        .iter()
+                        .map(|s| s.format.background)
+                        .collect::<Vec<_>>()
+                );
+                let backgrounds = text
+                    .galley
+                    .job
+                    .sections
+                    .iter()
+                    .map(|section| section.format.background)
+                    .collect::<Vec<_>>();
+                if backgrounds.contains(&Color32::BLUE) {
+                    highlighted.0.push(text.galley.job.text.clone());
+                }
+                if backgrounds.contains(&Color32::RED) {
+                    highlighted.1.push(text.galley.job.text.clone());
+                }
+            }
+        }
+        highlighted
+    };
+
+    assert_eq!(
+        run_frame(Some(1)),
+        (
+            vec!["\"baz 1\"".to_string(), "\"baz 2\"".to_string()],
+            vec![]
+        )
+    );
+    // Wait for the root object to finish expanding to reveal the current match.
+    for _ in 0..10 {
+        run_frame(None);
+    }
+    assert_eq!(
+        run_frame(None),
+        (vec!["\"baz 1\"".to_string()], vec!["\"baz 2\"".to_string()])
+    );
+}
+
+#[test]
+fn json_tree_scroll_markers() {
+    use egui::{Color32, Event, PointerButton};
+
+    let value = json!({"items": (0..200).collect::<Vec<_>>(), "name": "items"});
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+
+    let mut events = vec![];
+    let mut markers_per_frame = vec![];
+    let mut clicked_pointers = vec![];
+    let mut offset = 0.0;
+    for frame in 0..20 {
+        let input = egui::RawInput {
+            time: Some(frame as f64 * 0.05),
+            events: std::mem::take(&mut events),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let output = ScrollArea::vertical().max_height(100.0).show(ui, |ui| {
+                    JsonTree::new("id", &value)
+                        .default_expand(DefaultExpand::None)
+                        .search_term("199")
+                        .scroll_markers([("/items/150", Color32::RED)])
+                        .search_match_scroll_markers(Color32::GREEN)
+                        .show(ui)
+                });
+                if let Some(pointer) = output.inner.show_scroll_markers(ui, &output) {
+                    clicked_pointers.push(pointer.to_string());
+                }
+                markers_per_frame.push(
+                    output
+                        .inner
+                        .scroll_markers()
+                        .iter()
+                        .map(|marker| (marker.pointer.clone(), marker.color))
+                        .collect::<Vec<_>>(),
+                );
+                offset = output.state.offset.y;
+
+                if frame == 0 {
+                    // Click the track at the top of the scroll area, where the markers of the collapsed root are.
+                    let scroll_style = ui.spacing().scroll;
+                    let pos = egui::pos2(
+                        output.inner_rect.right()
+                            - scroll_style.bar_outer_margin
+                            - scroll_style.bar_width / 2.0,
+                        output.inner_rect.top() + 1.0,
+                    );
+                    events.push(Event::PointerMoved(pos));
+                    for pressed in [true, false] {
+                        events.push(Event::PointerButton {
+                            pos,
+                            button: PointerButton::Primary,
+                            pressed,
+                            modifiers: Default::default(),
+                        });
+                    }
+                }
+            });
+        });
+    }
+
+    // Both markers are shown at the row of the collapsed root object.
+    assert_eq!(
+        markers_per_frame[0],
+        vec![
+            ("".to_string(), Color32::GREEN),
+            ("/items/150".to_string(), Color32::RED)
+        ]
+    );
+    // The marker on top was clicked, so its value was revealed and scrolled into view.
+    assert_eq!(clicked_pointers, vec!["/items/150"]);
+    assert!(offset > 0.0);
+    assert_eq!(
+        markers_per_frame.last().unwrap(),
+        &vec![
+            ("/items/150".to_string(), Color32::RED),
+            ("/items/199".to_string(), Color32::GREEN)
+        ]
+    );
+}
+
+#[test]
+fn json_tree_rect_of() {
+    let value = json!({"items": (0..100).collect::<Vec<_>>(), "nested": {"hidden": 1}});
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+
+    let mut rects = vec![];
+    let _ = ctx.run(Default::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let output = ScrollArea::vertical().max_height(100.0).show(ui, |ui| {
+                JsonTree::new("id", &value)
+                    .default_expand(DefaultExpand::ToLevel(1))
+                    .show(ui)
+            });
+            let response = output.inner;
+            rects = [
+                "",
+                "/items",
+                "/items/0",
+                "/items/1",
+                "/items/99",
+                "/nested/hidden",
+                "invalid",
+            ]
+            .map(|pointer| response.rect_of(pointer))
+            .to_vec();
+            assert!(output.inner_rect.contains_rect(rects[2].unwrap()));
+        });
+    });
+
+    let [root, items, first, second, last, hidden, invalid] = rects[..] else {
+        panic!();
+    };
+    assert!(root.unwrap().top() < items.unwrap().top());
+    assert!(first.unwrap().bottom() <= second.unwrap().top());
+    // Scrolled out of view, collapsed and invalid pointers have no rect.
+    assert_eq!(last, None);
+    assert_eq!(hidden, None);
+    assert_eq!(invalid, None);
+}
+
+#[test]
+fn json_tree_popup_at_pointer() {
+    let value = json!({"items": (0..100).collect::<Vec<_>>()});
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+
+    let mut popups = vec![];
+    for frame in 0..30 {
+        let input = egui::RawInput {
+            time: Some(frame as f64 * 0.05),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let output = ScrollArea::vertical().max_height(100.0).show(ui, |ui| {
+                    JsonTree::new("id", &value)
+                        .default_expand(DefaultExpand::None)
+                        .show(ui)
+                });
+                let popup = output
+                    .inner
+                    .show_popup_at_pointer(ui, "/items/90", |ui| ui.label("Explanation"));
+                popups.push(popup.map(|popup| {
+                    let row_rect = output.inner.rect_of("/items/90").unwrap();
+                    (popup.response.rect.top(), row_rect.bottom())
+                }));
+            });
+        });
+    }
+
+    // The row is hidden within the collapsed root until it has been revealed and scrolled into view.
+    assert_eq!(popups[0], None);
+    let (popup_top, row_bottom) = popups.last().unwrap().unwrap();
+    assert_eq!(popup_top, row_bottom);
+}
+
+#[test]
+fn json_tree_search_match_rects_follow_wrapped_rows() {
+    use egui::{text::LayoutJob, Color32, FontId, Pos2, Rect, TextFormat};
+
+    let value = json!({"key": "aaaaaaaaaaaaMATCHbbbbbbbbbbbb"});
+
+    let ctx = Context::default();
+    let mut rects: Vec<Rect> = vec![];
+    let mut key_rects: Vec<Rect> = vec![];
+    let mut match_width = 0.0;
+    let _ = ctx.run(Default::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            JsonTree::new("id", &value)
+                .default_expand(DefaultExpand::All)
+                .search_term("match")
+                .on_render(|ui, render_ctx| match render_ctx {
+                    RenderContext::BaseValue(render_ctx) => {
+                        let font_id = FontId::monospace(12.0);
+                        let text = render_ctx.display_value.to_string();
+                        let char_width = ui.fonts(|f| f.glyph_width(&font_id, 'a'));
+                        match_width = ui
+                            .fonts(|f| {
+                                f.layout_no_wrap("MATCH".into(), font_id.clone(), Color32::WHITE)
+                            })
+                            .size()
+                            .x;
+
+                        // Wrap the value in the middle of the match.
+                        let mut job = LayoutJob::single_section(
+                            text,
+                            TextFormat::simple(font_id, Color32::WHITE),
+                        );
+                        job.wrap.max_width = 14.5 * char_width;
+                        job.wrap.break_anywhere = true;
+                        let galley = ui.fonts(|f| f.layout_job(job));
+                        assert!(galley.rows.len() > 1);
+                        rects = render_ctx.search_match_rects(&galley, Pos2::new(10.0, 20.0));
+                    }
+                    RenderContext::Property(render_ctx) => {
+                        let galley = ui.fonts(|f| {
+                            f.layout_no_wrap(
+                                render_ctx.property.to_string(),
+                                FontId::monospace(12.0),
+                                Color32::WHITE,
+                            )
+                        });
+                        key_rects = render_ctx.search_match_rects(&galley, Pos2::ZERO);
+                        render_ctx.render_default(ui);
+                    }
+                    render_ctx => {
+                        render_ctx.render_default(ui);
+                    }
+                })
+                .show(ui);
+        });
+    });
+
+    assert_eq!(rects.len(), 2);
+    assert!(rects[0].top() < rects[1].top());
+    assert_eq!(rects[0].top(), 20.0);
+    assert_eq!(rects[1].left(), 10.0);
+    assert!((rects[0].width() + rects[1].width() - match_width).abs() < 1.0);
+    assert!(key_rects.is_empty());
+}
+
+#[test]
+fn json_tree_search_match_ranges() {
+    let value = json!({"errno_err": 2, "level": "Error: errno", "retries": 3});
+
+    let ctx = Context::default();
+    let mut ranges = vec![];
+    let _ = ctx.run(Default::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            JsonTree::new("id", &value)
+                .default_expand(DefaultExpand::All)
+                .search_term("err")
+                .on_render(|ui, render_ctx| {
+                    match &render_ctx {
+                        RenderContext::Property(render_ctx) => ranges.push((
+                            render_ctx.property.to_string(),
+                            render_ctx.search_match_ranges(),
+                        )),
+                        RenderContext::BaseValue(render_ctx) => ranges.push((
+                            render_ctx.display_value.to_string(),
+                            render_ctx.search_match_ranges(),
+                        )),
+                        RenderContext::ExpandableDelimiter(_) => {}
+                    }
+                    render_ctx.render_default(ui);
+                })
+                .show(ui);
+        });
+    });
+
+    assert_eq!(
+        ranges,
+        vec![
+            ("errno_err".to_owned(), vec![0..3, 6..9]),
+            ("2".to_owned(), vec![]),
+            ("level".to_owned(), vec![]),
+            ("Error: errno".to_owned(), vec![0..3, 7..10]),
+            ("retries".to_owned(), vec![]),
+            ("3".to_owned(), vec![]),
+        ]
+    );
+}
+
+/// A JSON-like value whose objects may contain duplicate keys.
+enum DuplicateKeysValue {
+    Number(u32),
+    Object(Vec<(String, DuplicateKeysValue)>),
+}
+
+impl ToJsonTreeValue for DuplicateKeysValue {
+    fn to_json_tree_value(&self) -> JsonTreeValue<Self> {
+        match self {
+            DuplicateKeysValue::Number(n) => JsonTreeValue::Base(self, n, BaseValueType::Number),
+            DuplicateKeysValue::Object(entries) => JsonTreeValue::Expandable(
+                entries
+                    .iter()
+                    .map(|(key, val)| (JsonPointerSegment::Key(key), val))
+                    .collect(),
+                ExpandableType::Object,
+            ),
+        }
+    }
+
+    fn is_expandable(&self) -> bool {
+        matches!(self, DuplicateKeysValue::Object(_))
+    }
+
+    fn allows_duplicate_keys(&self) -> bool {
+        true
+    }
+}
+
+#[test]
+fn json_tree_duplicate_keys() {
+    use DuplicateKeysValue::{Number, Object};
+
+    let value = Object(vec![
+        ("a".to_string(), Object(vec![("b".to_string(), Number(1))])),
+        ("a".to_string(), Object(vec![("b".to_string(), Number(2))])),
+        ("a".to_string(), Number(3)),
+    ]);
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+
+    let mut snapshots = vec![];
+    let mut collapsed = false;
+    for _ in 0..10 {
+        let _ = ctx.run(Default::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let response = JsonTree::new("id", &value)
+                    .default_expand(DefaultExpand::All)
+                    .record_snapshot(true)
+                    .on_render(|ui, render_ctx| match render_ctx {
+                        RenderContext::ExpandableDelimiter(delimiter_ctx) => {
+                            // Collapse only the second occurrence of "a".
+                            if !collapsed
+                                && delimiter_ctx.pointer.to_json_pointer_string() == "/a~2"
+                            {
+                                delimiter_ctx.collapsing_state.set_open(false);
+                                collapsed = true;
+                            }
+                            delimiter_ctx.render_default(ui);
+                        }
+                        render_ctx => {
+                            render_ctx.render_default(ui);
+                        }
+                    })
+                    .show(ui);
+                snapshots.push(response.snapshot().unwrap().clone());
+            });
+        });
+    }
+
+    let pointers = |snapshot: &JsonTreeSnapshot| {
+        snapshot
+            .rows
+            .iter()
+            .map(|row| row.pointer.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        pointers(&snapshots[0]),
+        vec!["", "/a", "/a/b", "/a", "/a~2", "/a~2/b", "/a~2", "/a~3", ""]
+    );
+    assert_eq!(
+        snapshots[0].to_string(),
+        [
+            "{",
+            "  \"a\": {",
+            "    \"b\": 1",
+            "  }",
+            "  \"a\": {",
+            "    \"b\": 2",
+            "  }",
+            "  \"a\": 3",
+            "}",
+            "",
+        ]
+        .join("\n")
+    );
+    assert_eq!(
+        pointers(snapshots.last().unwrap()),
+        vec!["", "/a", "/a/b", "/a", "/a~2", "/a~3", ""]
+    );
+}
+
+#[test]
+fn json_tree_copy_and_mirror_expanded() {
+    let left_value = json!({"baz": [2], "foo": {"bar": [1]}});
+    let right_value = json!({"baz": [2, 3], "foo": {"bar": [1], "qux": {}}});
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+
+    let mut frame = 0;
+    let mut run_frame = |collapse_left_foo: bool| {
+        let mut rows = (vec![], vec![]);
+        let mut collapsed = false;
+        let _ = ctx.run(
+            egui::RawInput {
+                time: Some(frame as f64 * 0.05),
+                ..Default::default()
+            },
+            |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    let left = JsonTree::new("left", &left_value)
+                        .default_expand(DefaultExpand::All)
+                        .record_snapshot(true)
+                        .on_render(|ui, render_ctx| match render_ctx {
+                            RenderContext::ExpandableDelimiter(delimiter_ctx) => {
+                                if collapse_left_foo
+                                    && !collapsed
+                                    && delimiter_ctx.pointer.to_json_pointer_string() == "/foo"
+                                {
+                                    delimiter_ctx.collapsing_state.set_open(false);
+                                    collapsed = true;
+                                }
+                                delimiter_ctx.render_default(ui);
+                            }
+                            render_ctx => {
+                                render_ctx.render_default(ui);
+                            }
+                        })
+                        .show(ui);
+                    let right = JsonTree::new("right", &right_value)
+                        .default_expand(DefaultExpand::None)
+                        .record_snapshot(true)
+                        .show(ui);
+
+                    if frame == 0 {
+                        left.copy_expanded_to(ui.ctx(), &left_value, &right);
+                    }
+                    left.mirror_toggled_to(ui.ctx(), &right);
+                    right.mirror_toggled_to(ui.ctx(), &left);
+
+                    let pointers = |response: &egui_json_tree::JsonTreeResponse| {
+                        response
+                            .snapshot()
+                            .unwrap()
+                            .rows
+                            .iter()
+                            // Skip the closing delimiters of expanded arrays/objects.
+                            .filter(|row| !row.text.starts_with([']', '}']))
+                            .map(|row| row.pointer.clone())
+                            .collect::<Vec<_>>()
+                    };
+                    rows = (pointers(&left), pointers(&right));
+                });
+            },
+        );
+        frame += 1;
+        rows
+    };
+
+    let mut settle = |collapse_left_foo: bool| {
+        let mut rows = run_frame(collapse_left_foo);
+        while ctx.has_requested_repaint() {
+            rows = run_frame(false);
+        }
+        rows
+    };
+
+    let (left, right) = settle(false);
+    assert_eq!(
+        left,
+        vec!["", "/baz", "/baz/0", "/foo", "/foo/bar", "/foo/bar/0"]
+    );
+    // "/foo/qux" is not in the left tree, so it respects the default of the right tree.
+    assert_eq!(
+        right,
+        vec![
+            "",
+            "/baz",
+            "/baz/0",
+            "/baz/1",
+            "/foo",
+            "/foo/bar",
+            "/foo/bar/0",
+            "/foo/qux"
+        ]
+    );
+
+    let (left, right) = settle(true);
+    assert_eq!(left, vec!["", "/baz", "/baz/0", "/foo"]);
+    assert_eq!(right, vec!["", "/baz", "/baz/0", "/baz/1", "/foo"]);
+}
+
+#[test]
+fn json_tree_columns_layout() {
+    use egui::{Event, PointerButton};
+    use egui_json_tree::JsonTreeLayout;
+
+    let value = json!({"a": 1, "nested": {"b": true}});
+
+    let ctx = Context::default();
+    let mut events = vec![];
+    let mut value_lefts_per_frame = vec![];
+    for frame in 0..6 {
+        let input = egui::RawInput {
+            time: Some(frame as f64 * 0.05),
+            events: std::mem::take(&mut events),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let mut value_lefts = vec![];
+                let tree_left = ui.cursor().left();
+                JsonTree::new("id", &value)
+                    .style(JsonTreeStyle::new().layout(JsonTreeLayout::Columns {
+                        key_column_width: 150.0,
+                    }))
+                    .default_expand(DefaultExpand::All)
+                    .on_render(|ui, ctx| {
+                        let is_value = matches!(ctx, RenderContext::BaseValue(_));
+                        let rect = ctx.render_default(ui).rect;
+                        if is_value {
+                            value_lefts.push(rect.left() - tree_left);
+                        }
+                    })
+                    .show(ui);
+
+                // Drag the splitter 50 points to the left.
+                let splitter_pos = egui::pos2(tree_left + 150.0, ui.min_rect().top() + 5.0);
+                match frame {
+                    1 => events.push(Event::PointerMoved(splitter_pos)),
+                    2 | 4 => events.push(Event::PointerButton {
+                        pos: splitter_pos,
+                        button: PointerButton::Primary,
+                        pressed: frame == 2,
+                        modifiers: Default::default(),
+                    }),
+                    3 => events.push(Event::PointerMoved(splitter_pos - egui::vec2(50.0, 0.0))),
+                    _ => {}
+                }
+                value_lefts_per_frame.push(value_lefts);
+            });
+        });
+    }
+
+    let item_spacing = Style::default().spacing.item_spacing.x;
+    // Values at different depths are aligned in the value column.
+    assert_eq!(value_lefts_per_frame[0], vec![150.0 + item_spacing; 2]);
+    assert_eq!(
+        value_lefts_per_frame.last().unwrap(),
+        &vec![100.0 + item_spacing; 2]
+    );
+}
+
+#[test]
+fn json_tree_editable_values() {
+    use egui::{Event, Key, PointerButton, Pos2};
+
+    let value = json!({"a": 1, "b": "text"});
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+
+    let click = |pos| {
+        [true, false].map(|pressed| Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        })
+    };
+    let key = |key| Event::Key {
+        key,
+        physical_key: None,
+        pressed: true,
+        repeat: false,
+        modifiers: Default::default(),
+    };
+    // Double clicks the value, types after the existing text, then presses the key.
+    let edit = |pos: Pos2, text: &str, commit_key| {
+        vec![
+            vec![Event::PointerMoved(pos)],
+            click(pos).to_vec(),
+            click(pos).to_vec(),
+            vec![],
+            vec![Event::Text(text.to_string())],
+            vec![key(commit_key)],
+            vec![],
+        ]
+    };
+
+    let mut edits = vec![];
+    let mut value_pos = Pos2::ZERO;
+    let mut value_has_focus = vec![];
+    let mut frame_events = vec![vec![]];
+    let mut frame = 0;
+    while frame < frame_events.len() {
+        let input = egui::RawInput {
+            time: Some(frame as f64 * 0.05),
+            events: frame_events[frame].clone(),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                JsonTree::new("id", &value)
+                    .default_expand(DefaultExpand::All)
+                    .editable_values(true)
+                    .on_value_edit(|pointer, text| {
+                        edits.push((pointer.to_json_pointer_string(), text.to_string()));
+                    })
+                    .on_render(|ui, ctx| {
+                        let is_a = matches!(&ctx, RenderContext::BaseValue(ctx) if ctx.pointer.to_json_pointer_string() == "/a");
+                        let response = ctx.render_default(ui);
+                        if is_a {
+                            value_pos = response.rect.center();
+                            value_has_focus.push(response.has_focus());
+                        }
+                    })
+                    .show(ui);
+            });
+        });
+        if frame == 0 {
+            frame_events.extend(edit(value_pos, "2", Key::Enter));
+            // Escape cancels the edit.
+            frame_events.extend(edit(value_pos, "3", Key::Escape));
+        }
+        frame += 1;
+    }
+
+    assert_eq!(edits, vec![("/a".to_string(), "12".to_string())]);
+    // The text edit was focused while editing, and the value was shown again afterwards.
+    assert!(value_has_focus.iter().any(|has_focus| *has_focus));
+    assert!(!value_has_focus.last().unwrap());
+}
+
+#[test]
+fn json_tree_drag_numbers() {
+    use egui::{Event, PointerButton, Pos2};
+    use egui_json_tree::JsonTreeNumericDrag;
+
+    let value = json!({"a": 1, "b": 1});
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+
+    let mut edits = vec![];
+    let mut value_positions = vec![];
+    let mut frame_events = vec![vec![]];
+    let mut frame = 0;
+    while frame < frame_events.len() {
+        let input = egui::RawInput {
+            time: Some(frame as f64 * 0.05),
+            events: frame_events[frame].clone(),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                JsonTree::new("id", &value)
+                    .default_expand(DefaultExpand::All)
+                    .editable_values(true)
+                    .drag_numbers(|pointer| {
+                        (pointer.to_json_pointer_string() == "/a").then_some(JsonTreeNumericDrag {
+                            speed: 0.5,
+                            range: 0.0..=15.0,
+                        })
+                    })
+                    .on_value_edit(|pointer, text| {
+                        edits.push((pointer.to_json_pointer_string(), text.to_string()));
+                    })
+                    .on_render(|ui, ctx| {
+                        let is_value = matches!(ctx, RenderContext::BaseValue(_));
+                        let response = ctx.render_default(ui);
+                        if is_value && frame == 0 {
+                            value_positions.push(response.rect.center());
+                        }
+                    })
+                    .show(ui);
+            });
+        });
+        if frame == 0 {
+            for pos in value_positions.clone() {
+                let button = |pos: Pos2, pressed| Event::PointerButton {
+                    pos,
+                    button: PointerButton::Primary,
+                    pressed,
+                    modifiers: Default::default(),
+                };
+                frame_events.extend([
+                    vec![Event::PointerMoved(pos)],
+                    vec![button(pos, true)],
+                    vec![Event::PointerMoved(pos + egui::vec2(20.0, 0.0))],
+                    vec![Event::PointerMoved(pos + egui::vec2(40.0, 0.0))],
+                    vec![button(pos + egui::vec2(40.0, 0.0), false)],
+                    vec![],
+                ]);
+            }
+        }
+        frame += 1;
+    }
+
+    // Only "/a" can be dragged, and it is clamped to its range.
+    assert_eq!(
+        edits,
+        vec![
+            ("/a".to_string(), "11".to_string()),
+            ("/a".to_string(), "15".to_string())
+        ]
+    );
+}
+
+#[test]
+fn json_tree_font_families() {
+    let value = json!({"iiii": "iiii"});
+
+    // Uses the default fonts, which have different glyph widths for monospace and proportional text.
+    let ctx = Context::default();
+    let _ = ctx.run(Default::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let mut widths = |font_families: JsonTreeFontFamilies| {
+                let mut key_width = 0.0;
+                let mut value_width = 0.0;
+                JsonTree::new(("id", &font_families), &value)
+                    .style(JsonTreeStyle::new().font_families(font_families.clone()))
+                    .default_expand(DefaultExpand::All)
+                    .on_render(|ui, ctx| match ctx {
+                        RenderContext::Property(ctx) => {
+                            key_width = ctx.render_default(ui).rect.width();
+                        }
+                        RenderContext::BaseValue(ctx) => {
+                            value_width = ctx.render_default(ui).rect.width();
+                        }
+                        RenderContext::ExpandableDelimiter(ctx) => {
+                            ctx.render_default(ui);
+                        }
+                    })
+                    .show(ui);
+                (key_width, value_width)
+            };
+
+            let (monospace_key_width, monospace_value_width) = widths(Default::default());
+            let (proportional_key_width, value_width) = widths(JsonTreeFontFamilies {
+                keys: Some(FontFamily::Proportional),
+                ..Default::default()
+            });
+
+            assert!(proportional_key_width < monospace_key_width);
+            assert_eq!(value_width, monospace_value_width);
+        });
+    });
+}
+
+#[test]
+fn json_tree_root_label_and_abbreviation() {
+    let value = json!({"bar": [1], "foo": {}});
+
+    egui::__run_test_ui(|ui| {
+        let root_text = |ui: &mut egui::Ui, value: &Value, style: JsonTreeStyle, id: &str| {
+            let response = JsonTree::new(id, value)
+                .style(style)
+                .record_snapshot(true)
+                .show(ui);
+            response.snapshot().unwrap().rows[0].text.clone()
+        };
+
+        let style = JsonTreeStyle::new().root_label("body").abbreviation("…");
+        assert_eq!(
+            root_text(ui, &value, style.clone(), "preview"),
+            "body: { \"bar\": […], \"foo\": {} }"
+        );
+        assert_eq!(
+            root_text(
+                ui,
+                &value,
+                style.clone().abbreviate_root(true),
+                "abbreviated"
+            ),
+            "body: {…}"
+        );
+        assert_eq!(root_text(ui, &json!(1), style, "base"), "body: 1");
+        assert_eq!(
+            root_text(
+                ui,
+                &value,
+                JsonTreeStyle::new().abbreviate_root(true),
+                "default"
+            ),
+            "{...}"
+        );
+    });
+}
+
+#[test]
+fn json_forest_shows_labeled_documents() {
+    let request = json!({"id": 1});
+    let response = json!({"id": 2, "ok": true});
+
+    egui::__run_test_ui(|ui| {
+        let forest_response =
+            JsonForest::new("forest", [("request", &request), ("response", &response)])
+                .default_expand(DefaultExpand::SearchResults("ok"))
+                .record_snapshot(true)
+                .show(ui);
+
+        let snapshots = forest_response
+            .responses()
+            .iter()
+            .map(|response| response.snapshot().unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            snapshots,
+            vec![
+                "request: { \"id\": 1 }\n",
+                "response: { \"id\": 2, \"«ok»\": true }\n"
+            ]
+        );
+    });
+}
+
+#[test]
+fn json_tree_search_term_with_own_default_expand() {
+    let value = json!({"bar": {"baz": 1}, "foo": "baz"});
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::ToLevel(0))
+            .search_term("baz")
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            ["{", "  \"bar\": {...}", "  \"foo\": \"«baz»\"", "}", ""].join("\n")
+        );
+    });
+}
+
+#[test]
+fn json_tree_case_sensitive_search() {
+    let value = json!({"a": {"ID": 1}, "b": {"id": 2}, "c": "Identity"});
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::SearchResultsWith(
+                "ID",
+                SearchOptions {
+                    case_sensitive: true,
+                    ..Default::default()
+                },
+            ))
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"a\": {",
+                "    \"«ID»\": 1",
+                "  }",
+                "  \"b\": {...}",
+                "  \"c\": \"Identity\"",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+    });
+}
+
+#[test]
+fn json_tree_search_words_in_subtree() {
+    let value = json!({"a": {"level": "error", "reason": "timeout"}, "b": {"level": "error"}});
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::SearchResultsWith(
+                "error timeout",
+                SearchOptions {
+                    words: SearchWords::AllInSubtree,
+                    ..Default::default()
+                },
+            ))
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"a\": {",
+                "    \"level\": \"«error»\"",
+                "    \"reason\": \"«timeout»\"",
+                "  }",
+                "  \"b\": {...}",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+    });
+}
+
+#[test]
+fn json_tree_search_results_expand_match_children() {
+    let value = json!({"config": {"a": {"b": 1}}, "other": {"config_path": "x"}, "z": [1]});
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::SearchResultsWith(
+                "config",
+                SearchOptions {
+                    expand_match_children: true,
+                    ..Default::default()
+                },
+            ))
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"«config»\": {",
+                "    \"a\": {...}",
+                "  }",
+                "  \"other\": {",
+                "    \"«config»_path\": \"x\"",
+                "  }",
+                "  \"z\": [...]",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+    });
+}
+
+#[test]
+fn json_tree_fuzzy_search() {
+    let value = json!({"a": {"user_name": "x"}, "b": {"age": 1}});
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::SearchResultsWith(
+                "usrnm",
+                SearchOptions {
+                    fuzzy: true,
+                    ..Default::default()
+                },
+            ))
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"a\": {",
+                "    \"«us»e«r»_«n»a«m»e\": \"x\"",
+                "  }",
+                "  \"b\": {...}",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+    });
+}
+
+#[test]
+fn json_tree_whole_word_search() {
+    let value = json!({"a": {"id": 1}, "b": {"valid": true}, "c": "uuid or id"});
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::SearchResultsWith(
+                "id",
+                SearchOptions {
+                    whole_word: true,
+                    ..Default::default()
+                },
+            ))
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"a\": {",
+                "    \"«id»\": 1",
+                "  }",
+                "  \"b\": {...}",
+                "  \"c\": \"uuid or «id»\"",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+    });
+}
+
+#[test]
+fn json_tree_search_by_type() {
+    let value = json!({"a": {"b": null, "c": "null"}, "d": [1, null], "null": true});
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::SearchResultsWith(
+                "type:null",
+                SearchOptions {
+                    query_syntax: true,
+                    ..Default::default()
+                },
+            ))
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"a\": {",
+                "    \"b\": «null»",
+                "    \"c\": \"null\"",
+                "  }",
+                "  \"d\": [",
+                "    0: 1",
+                "    1: «null»",
+                "  ]",
+                "  \"null\": true",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+        assert_eq!(response.search_match_count(), 2);
+    });
+}
+
+#[test]
+fn json_tree_search_results_all_and_any() {
+    let value = json!({"a": "error: timeout", "b": "error: refused", "c": {"timeout": 30}});
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("all", &value)
+            .default_expand(DefaultExpand::SearchResultsAll(
+                &["error", "timeout", ""],
+                SearchOptions::default(),
+            ))
+            .record_snapshot(true)
+            .show(ui);
+        // Only a value matching both terms is highlighted.
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            "{ \"a\": \"«error»: «timeout»\", \"b\": \"error: refused\", \"c\": {...} }\n"
+        );
+        assert_eq!(response.search_match_count(), 1);
+
+        let response = JsonTree::new("any", &value)
+            .default_expand(DefaultExpand::SearchResultsAny(
+                &["error", "timeout"],
+                SearchOptions::default(),
+            ))
+            .record_snapshot(true)
+            .show(ui);
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"a\": \"«error»: «timeout»\"",
+                "  \"b\": \"«error»: refused\"",
+                "  \"c\": {",
+                "    \"«timeout»\": 30",
+                "  }",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+        assert_eq!(response.search_match_count(), 3);
+
+        let response = JsonTree::new("any-case-sensitive", &value)
+            .default_expand(DefaultExpand::SearchResultsAny(
+                &["Error", "timeout"],
+                SearchOptions {
+                    case_sensitive: true,
+                    scope: SearchScope::Keys,
+                    ..Default::default()
+                },
+            ))
+            .record_snapshot(true)
+            .show(ui);
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"a\": \"error: timeout\"",
+                "  \"b\": \"error: refused\"",
+                "  \"c\": {",
+                "    \"«timeout»\": 30",
+                "  }",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+        assert_eq!(response.search_match_count(), 1);
+    });
+}
+
+#[test]
+fn json_tree_search_exclusions() {
+    let value = json!({
+        "a": "error: timeout",
+        "b": "error: deprecation warning",
+        "c": {"deprecation_error": 1}
+    });
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::SearchResultsWith(
+                "error -deprecation",
+                SearchOptions {
+                    query_syntax: true,
+                    ..Default::default()
+                },
+            ))
+            .style(JsonTreeStyle::new().abbreviate_root(true))
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"a\": \"«error»: timeout\"",
+                "  \"b\": \"error: deprecation warning\"",
+                "  \"c\": {...}",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+        assert_eq!(response.search_match_count(), 1);
+    });
+}
+
+#[test]
+fn json_tree_search_exclusions_beneath_keys() {
+    let value = json!({
+        "debug": {"last_error": "timeout", "errors": ["a", "b"]},
+        "status": "error"
+    });
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::SearchResultsWith(
+                "error -debug",
+                SearchOptions {
+                    query_syntax: true,
+                    ..Default::default()
+                },
+            ))
+            .filter_search_results(true)
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            "{ \"status\": \"«error»\" }\n"
+        );
+        assert_eq!(response.search_match_pointers(), ["/status"]);
+    });
+}
+
+#[test]
+fn json_tree_search_with() {
+    let value = json!({"a": "short", "b": ["a long string", 12345678], "c": {"long_key": null}});
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::SearchWith(&|_, candidate| match candidate {
+                SearchCandidate::Key(key) => key.len() > 6,
+                SearchCandidate::Value(text, value_type) => {
+                    value_type == BaseValueType::String && text.len() > 6
+                }
+            }))
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"a\": \"short\"",
+                "  \"b\": [",
+                "    «0»: \"a long string\"",
+                "    1: 12345678",
+                "  ]",
+                "  \"c\": {",
+                "    «\"long_key\"»: null",
+                "  }",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+        assert_eq!(response.search_match_count(), 2);
+    });
+}
+
+#[test]
+fn json_tree_search_within() {
+    let value = json!({
+        "error": {"status": "error"},
+        "raw_payload": {"status": "error"},
+        "summary": {"status": "error"}
+    });
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("expanded", &value)
+            .default_expand(DefaultExpand::All)
+            .search_term("error")
+            .search_within("/summary")
+            .record_snapshot(true)
+            .show(ui);
+        // Only matches beneath /summary are highlighted, not its ancestors or siblings.
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"error\": {",
+                "    \"status\": \"error\"",
+                "  }",
+                "  \"raw_payload\": {",
+                "    \"status\": \"error\"",
+                "  }",
+                "  \"summary\": {",
+                "    \"status\": \"«error»\"",
+                "  }",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+        assert_eq!(response.search_match_count(), 1);
+
+        let response = JsonTree::new("invalid", &value)
+            .default_expand(DefaultExpand::SearchResults("error"))
+            .search_within("summary")
+            .show(ui);
+        assert_eq!(response.search_match_count(), 0);
+    });
+}
+
+#[test]
+fn json_tree_provenance() {
+    let value = json!({"port": 8080, "tls": {"enabled": true}});
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::All)
+            .provenance(|pointer| match pointer.to_json_pointer_string().as_str() {
+                "/port" => Some("override.toml:3".to_string()),
+                "/tls" => Some("base.toml".to_string()),
+                _ => None,
+            })
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"port\": 8080  // override.toml:3",
+                "  \"tls\": {  // base.toml",
+                "    \"enabled\": true",
+                "  }",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+    });
+}
+
+#[test]
+fn json_tree_condense_search_paths() {
+    use egui::{Event, PointerButton, RawInput, Rect};
+
+    let value = json!({
+        "data": {"items": [{"attrs": {"a": 1, "b": "needle"}}]},
+        "other": {"c": {"d": "needle"}}
+    });
+
+    let ctx = Context::default();
+
+    let run_frame = |events: Vec<Event>| {
+        let mut result = (String::new(), None::<Rect>);
+        let _ = ctx.run(
+            RawInput {
+                events,
+                ..Default::default()
+            },
+            |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    let response = JsonTree::new("id", &value)
+                        .default_expand(DefaultExpand::SearchResults("needle"))
+                        .filter_search_results(true)
+                        .condense_search_paths(true)
+                        .record_snapshot(true)
+                        .show(ui);
+                    result = (
+                        response.snapshot().unwrap().to_string(),
+                        response.rect_of("/data"),
+                    );
+                });
+            },
+        );
+        result
+    };
+
+    let (text, data_rect) = run_frame(vec![]);
+    // The chain beneath "other" is a single level, so is not condensed.
+    assert_eq!(
+        text,
+        [
+            "{",
+            "  …/data/items/0/",
+            "        \"attrs\": {",
+            "          \"b\": \"«needle»\"",
+            "        }",
+            "  \"other\": {",
+            "    \"c\": {",
+            "      \"d\": \"«needle»\"",
+            "    }",
+            "  }",
+            "}",
+            "",
+        ]
+        .join("\n")
+    );
+
+    // Clicking the condensed row restores the full chain.
+    let pos = data_rect.unwrap().left_center() + egui::vec2(40.0, 0.0);
+    let mut events = vec![Event::PointerMoved(pos)];
+    for pressed in [true, false] {
+        events.push(Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        });
+    }
+    run_frame(events);
+    let (text, _) = run_frame(vec![]);
+    assert!(text.starts_with(
+        &[
+            "{",
+            "  \"data\": {",
+            "    \"items\": [",
+            "      0: {",
+            "        \"attrs\": {",
+        ]
+        .join("\n")
+    ));
+}
+
+#[test]
+fn json_tree_search_keys_only() {
+    let value = json!({"a": {"level": 1}, "b": {"message": "level"}});
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::SearchResultsWith(
+                "level",
+                SearchOptions {
+                    scope: SearchScope::Keys,
+                    ..Default::default()
+                },
+            ))
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"a\": {",
+                "    \"«level»\": 1",
+                "  }",
+                "  \"b\": {...}",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+    });
+}
+
+#[test]
+fn json_tree_default_expand_important() {
+    let value = json!({
+        "data": [1, 2],
+        "report": {"errors": ["oops"], "summary": {"ok": false}},
+        "unknown": 1
+    });
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::Important(&[
+                "/report/errors",
+                "/unknown",
+                "/missing",
+            ]))
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"data\": [...]",
+                "  \"report\": {",
+                "    \"errors\": [",
+                "      0: \"oops\"",
+                "    ]",
+                "    \"summary\": {...}",
+                "  }",
+                "  \"unknown\": 1",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+    });
+}
+
+#[test]
+fn json_tree_default_expand_to_level_with() {
+    let value = json!({
+        "meta": {"tags": ["a"]},
+        "raw_data": {"rows": [[1]], "schema": {"nested": {"deep": 1}}}
+    });
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::ToLevelWith(
+                2,
+                &[
+                    ("/meta", ExpandOverride::ToLevel(0)),
+                    ("/raw_data", ExpandOverride::ToLevel(1)),
+                    ("/raw_data/schema", ExpandOverride::ToLevel(2)),
+                    ("invalid", ExpandOverride::ToLevel(5)),
+                ],
+            ))
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"meta\": {...}",
+                "  \"raw_data\": {",
+                "    \"rows\": [...]",
+                "    \"schema\": {",
+                "      \"nested\": {",
+                "        \"deep\": 1",
+                "      }",
+                "    }",
+                "  }",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+    });
+}
+
+#[test]
+fn json_tree_default_expand_to_level_with_all_or_none() {
+    let value = json!({
+        "errors": [{"cause": {"code": 1}}],
+        "meta": {"tags": ["a"]},
+        "raw": {"rows": [1]}
+    });
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::ToLevelWith(
+                1,
+                &[
+                    ("/errors", ExpandOverride::All),
+                    ("/raw", ExpandOverride::None),
+                    ("/meta/tags", ExpandOverride::ToLevel(1)),
+                ],
+            ))
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"errors\": [",
+                "    0: {",
+                "      \"cause\": {",
+                "        \"code\": 1",
+                "      }",
+                "    }",
+                "  ]",
+                "  \"meta\": {",
+                "    \"tags\": [",
+                "      0: \"a\"",
+                "    ]",
+                "  }",
+                "  \"raw\": {...}",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+    });
+}
+
+#[test]
+fn json_tree_subtree() {
+    let value = json!({
+        "id": 1,
+        "meta": {"tags": ["a"]},
+        "raw": {"rows": [[1]]}
+    });
+
+    egui::__run_test_ui(|ui| {
+        let mut tree_pointers = vec![];
+        let mut raw_pointers = vec![];
+
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::All)
+            .on_render(|ui, render_ctx| {
+                tree_pointers.push(render_ctx.pointer().to_json_pointer_string());
+                render_ctx.render_default(ui);
+            })
+            .subtree(
+                "/raw",
+                JsonTreeSubtreeConfig::new()
+                    .style(JsonTreeStyle::new().density(JsonTreeDensity::Compact))
+                    .default_expand(ExpandOverride::ToLevel(2))
+                    .on_render(|ui, render_ctx| {
+                        raw_pointers.push(render_ctx.pointer().to_json_pointer_string());
+                        render_ctx.render_default(ui);
+                    }),
+            )
+            .subtree(
+                "/meta",
+                JsonTreeSubtreeConfig::new().default_expand(ExpandOverride::None),
+            )
+            .subtree("invalid", JsonTreeSubtreeConfig::new())
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"id\": 1",
+                "  \"meta\": {...}",
+                "  \"raw\": {",
+                "    \"rows\": [",
+                "      0: [...]",
+                "    ]",
+                "  }",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+        assert!(tree_pointers.contains(&"/meta".to_owned()));
+        assert!(!tree_pointers
+            .iter()
+            .any(|pointer| pointer.starts_with("/raw")));
+        assert_eq!(
+            raw_pointers.first().map(String::as_str),
+            Some("/raw"),
+            "The property of the subtree is rendered by its hook"
+        );
+        assert!(raw_pointers
+            .iter()
+            .all(|pointer| pointer.starts_with("/raw")));
+    });
+}
+
+#[test]
+fn json_tree_default_expand_to_pointer() {
+    let value = json!({
+        "bar": {"thud": {"a/b": [1, 2, {"c": 3}]}, "waldo": [1]},
+        "foo": {"qux": 1}
+    });
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::ToPointer("/bar/thud/a~1b/2"))
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"bar\": {",
+                "    \"thud\": {",
+                "      \"a/b\": [",
+                "        0: 1",
+                "        1: 2",
+                "        2: {...}",
+                "      ]",
+                "    }",
+                "    \"waldo\": [...]",
+                "  }",
+                "  \"foo\": {...}",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+    });
+}
+
+#[test]
+fn json_tree_default_expand_json_path() {
+    let value = json!({
+        "store": {
+            "bicycle": {"color": "red"},
+            "book": [{"author": "Rees", "title": "Sayings"}, {"title": "Anonymous"}]
+        }
+    });
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::JsonPath("$.store.book[*].author"))
+            .record_snapshot(true)
+            .show(ui);
+
+        assert!(response.json_path_error().is_none());
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"store\": {",
+                "    \"bicycle\": {...}",
+                "    \"book\": [",
+                "      0: {",
+                "        «\"author\"»: \"Rees\"",
+                "        \"title\": \"Sayings\"",
+                "      }",
+                "      1: {...}",
+                "    ]",
+                "  }",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+    });
+}
+
+#[test]
+fn json_tree_default_expand_json_path_error() {
+    let value = json!({"store": {"book": []}});
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::JsonPath("$.store[book"))
+            .record_snapshot(true)
+            .show(ui);
+
+        let error = response.json_path_error().unwrap();
+        assert_eq!(error.position(), 8);
+        assert_eq!(
+            error.to_string(),
+            "expected a quoted name, an integer, a slice or `*` at position 8"
+        );
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            "{ \"store\": {...} }\n"
+        );
+    });
+}
+
+#[test]
+fn json_tree_search_match_pointers() {
+    let value = json!({"bar": [1, "baz"], "baz": {"qux": "baz"}, "foo": 2});
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::SearchResults("baz"))
+            .show(ui);
+        assert_eq!(response.search_match_count(), 3);
+        assert_eq!(
+            response.search_match_pointers(),
+            ["/bar/1", "/baz", "/baz/qux"]
+        );
+
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::ToLevel(1))
+            .show(ui);
+        assert_eq!(response.search_match_count(), 0);
+        assert!(response.search_match_pointers().is_empty());
+    });
+}
+
+#[test]
+fn json_tree_highlight_only() {
+    let value = json!({"bar": {"baz": 1}, "foo": "baz"});
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::SearchResults("baz"))
+            .filter_search_results(true)
+            .highlight_only(true)
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            "{ \"bar\": {...}, \"foo\": \"«baz»\" }\n"
+        );
+    });
+}
+
+#[test]
+fn json_tree_shared_search() {
+    let request = json!({"bar": {"baz": 1}, "foo": {"qux": 2}});
+    let response = json!({"foo": {"qux": 3}});
+    let search = JsonTreeSharedSearch::new();
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+
+    // Shows both trees until any expand/collapse animation has finished, returning their final snapshots.
+    let show = |term: &str| {
+        search.set_term(term);
+        let mut snapshots = vec![];
+        loop {
+            snapshots.clear();
+            let _ = ctx.run(Default::default(), |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    for (id, value) in [("request", &request), ("response", &response)] {
+                        let response = JsonTree::new(id, value)
+                            .style(JsonTreeStyle::new().abbreviate_root(true))
+                            .default_expand(DefaultExpand::SearchResults(""))
+                            .shared_search(&search)
+                            .record_snapshot(true)
+                            .show(ui);
+                        snapshots.push(response.snapshot().unwrap().to_string());
+                    }
+                });
+            });
+            if !ctx.has_requested_repaint() {
+                return snapshots;
+            }
+        }
+    };
+
+    assert_eq!(
+        show("baz"),
+        vec![
+            [
+                "{",
+                "  \"bar\": {",
+                "    \"«baz»\": 1",
+                "  }",
+                "  \"foo\": {...}",
+                "}",
+                ""
+            ]
+            .join("\n"),
+            "{...}\n".to_string(),
+        ]
+    );
+    // The expanded state of both trees is reset to reveal the matches of the new search term.
+    assert_eq!(
+        show("qux"),
+        vec![
+            [
+                "{",
+                "  \"bar\": {...}",
+                "  \"foo\": {",
+                "    \"«qux»\": 2",
+                "  }",
+                "}",
+                ""
+            ]
+            .join("\n"),
+            ["{", "  \"foo\": {", "    \"«qux»\": 3", "  }", "}", ""].join("\n"),
+        ]
+    );
+}
+
+#[test]
+fn json_tree_element_identity() {
+    let before = json!({"items": [{"id": "a", "tags": [1]}, {"id": "b", "tags": [2]}]});
+    let after = json!({"items": [
+        {"id": "c", "tags": [3]},
+        {"id": "a", "tags": [1]},
+        {"id": "b", "tags": [2]}
+    ]});
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+
+    let run_frame = |value: &Value, collapse: Option<&str>| {
+        let mut pointers = vec![];
+        let mut collapsed = false;
+        let _ = ctx.run(Default::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let response = JsonTree::new("id", value)
+                    .default_expand(DefaultExpand::All)
+                    .element_identity("/items", "/id")
+                    .record_snapshot(true)
+                    .on_render(|ui, render_ctx| match render_ctx {
+                        RenderContext::ExpandableDelimiter(delimiter_ctx) => {
+                            if !collapsed
+                                && Some(delimiter_ctx.pointer.to_json_pointer_string().as_str())
+                                    == collapse
+                            {
+                                delimiter_ctx.collapsing_state.set_open(false);
+                                collapsed = true;
+                            }
+                            delimiter_ctx.render_default(ui);
+                        }
+                        render_ctx => {
+                            render_ctx.render_default(ui);
+                        }
+                    })
+                    .show(ui);
+                pointers = response
+                    .snapshot()
+                    .unwrap()
+                    .rows
+                    .iter()
+                    .map(|row| row.pointer.clone())
+                    .collect::<Vec<_>>();
+            });
+        });
+        pointers
+    };
+
+    let mut pointers = run_frame(&before, Some("/items/1"));
+    for _ in 0..10 {
+        pointers = run_frame(&before, None);
+    }
+    assert!(!pointers.contains(&"/items/1/tags".to_string()));
+
+    // The element with id "b" has moved to index 2, and remains collapsed.
+    for _ in 0..10 {
+        pointers = run_frame(&after, None);
+    }
+    assert!(pointers.contains(&"/items/0/tags".to_string()));
+    assert!(pointers.contains(&"/items/1/tags".to_string()));
+    assert!(pointers.contains(&"/items/2".to_string()));
+    assert!(!pointers.contains(&"/items/2/tags".to_string()));
+}
+
+#[test]
+fn json_tree_numeric_summaries() {
+    let value = json!({"a": [1, 2, 3], "b": [1, "2"], "c": []});
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::ToLevel(0))
+            .numeric_summaries(true)
+            .record_snapshot(true)
+            .show(ui);
+
+        assert_eq!(
+            response.snapshot().unwrap().to_string(),
+            [
+                "{",
+                "  \"a\": [...]  // count 3, min 1, max 3, mean 2, sum 6",
+                "  \"b\": [...]",
+                "  \"c\": []",
+                "}",
+                ""
+            ]
+            .join("\n")
+        );
+    });
+}
+
+#[test]
+fn json_tree_subtree_hashes() {
+    let value = json!({
+        "a": {"x": 1, "y": [2]},
+        "b": {"x": 1, "y": [2]},
+        "c": {"x": "1", "y": [2]}
+    });
+
+    egui::__run_test_ui(|ui| {
+        let response = JsonTree::new("id", &value)
+            .default_expand(DefaultExpand::ToLevel(0))
+            .subtree_hashes(true)
+            .record_snapshot(true)
+            .show(ui);
+
+        let snapshot = response.snapshot().unwrap().to_string();
+        let hashes = snapshot
+            .lines()
+            .map(|line| line.split_once("  // #").map(|(_, hash)| hash.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(hashes.len(), 5);
+        let (root, a, b, c) = (&hashes[0], &hashes[1], &hashes[2], &hashes[3]);
+        assert!(root.is_some());
+        assert!(a.as_ref().is_some_and(|a| a.len() == 8));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_eq!(hashes[4], None);
+    });
+}
+
+#[test]
+fn json_tree_numeric_summaries_cached_per_value_version() {
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+
+    let run_frame = |value: &Value, value_version: u64| {
+        let mut text = String::new();
+        let _ = ctx.run(Default::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let response = JsonTree::new("id", value)
+                    .style(JsonTreeStyle::new().abbreviate_root(true))
+                    .numeric_summaries(true)
+                    .value_version(value_version)
+                    .record_snapshot(true)
+                    .show(ui);
+                text = response.snapshot().unwrap().to_string();
+            });
+        });
+        text
+    };
+
+    assert_eq!(
+        run_frame(&json!([1, 2]), 0),
+        "[...]  // count 2, min 1, max 2, mean 1.5, sum 3\n"
+    );
+    // The summary is not recomputed until the version changes.
+    assert_eq!(
+        run_frame(&json!([5]), 0),
+        "[...]  // count 2, min 1, max 2, mean 1.5, sum 3\n"
+    );
+    assert_eq!(
+        run_frame(&json!([5]), 1),
+        "[...]  // count 1, min 5, max 5, mean 5, sum 5\n"
+    );
+}
+
+#[test]
+fn json_tree_search_cached_per_value_version() {
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+
+    let run_frame = |value: &Value, search_term: &str, value_version: u64| {
+        let mut pointers = vec![];
+        let _ = ctx.run(Default::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let response = JsonTree::new("id", value)
+                    .default_expand(DefaultExpand::SearchResults(search_term))
+                    .value_version(value_version)
+                    .show(ui);
+                pointers = response.search_match_pointers().to_vec();
+            });
+        });
+        pointers
+    };
+
+    assert_eq!(run_frame(&json!({"a": "foo"}), "foo", 0), vec!["/a"]);
+    // The value is not searched again until the version or the search changes.
+    assert_eq!(run_frame(&json!({"b": "foo"}), "foo", 0), vec!["/a"]);
+    assert_eq!(run_frame(&json!({"b": "foo"}), "foo", 1), vec!["/b"]);
+    assert_eq!(
+        run_frame(&json!({"b": "foo", "c": "fo"}), "fo", 1),
+        vec!["/b", "/c"]
+    );
+}
+
+#[test]
+fn json_tree_search_debounce() {
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+    let value = json!({"a": {"x": "foo"}, "b": {"y": "bar"}});
+
+    let run_frame = |search_term: &str, time: f64| {
+        let mut result = (String::new(), vec![]);
+        let input = egui::RawInput {
+            time: Some(time),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let response = JsonTree::new("id", &value)
+                    .style(JsonTreeStyle::new().search_debounce(Some(Duration::from_millis(300))))
+                    .default_expand(DefaultExpand::SearchResults(search_term))
+                    .record_snapshot(true)
+                    .show(ui);
+                result = (
+                    response.snapshot().unwrap().to_string(),
+                    response.search_match_pointers().to_vec(),
+                );
+            });
+        });
+        result
+    };
+
+    // The first search term is applied straight away.
+    let (snapshot, pointers) = run_frame("foo", 0.0);
+    assert_eq!(pointers, vec!["/a/x"]);
+    assert_eq!(
+        snapshot,
+        r#"{
+  "a": {
+    "x": "«foo»"
+  }
+  "b": {...}
+}
+"#
+    );
+
+    // The previous matches are kept until the search term stays unchanged for the debounce duration.
+    assert_eq!(run_frame("ba", 0.1).1, vec!["/a/x"]);
+    assert_eq!(run_frame("bar", 0.2).1, vec!["/a/x"]);
+    assert_eq!(run_frame("bar", 0.4).1, vec!["/a/x"]);
+
+    assert_eq!(run_frame("bar", 0.5).1, vec!["/b/y"]);
+
+    // The expanded state was reset when the new search term was applied.
+    let (snapshot, pointers) = run_frame("bar", 2.0);
+    assert_eq!(pointers, vec!["/b/y"]);
+    assert_eq!(
+        snapshot,
+        r#"{
+  "a": {...}
+  "b": {
+    "y": "«bar»"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn json_tree_background_search() {
+    use egui_json_tree::{JsonTreeBackgroundSearch, JsonTreeDocument};
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+    let document = JsonTreeDocument::new(json!({"a": {"b": "foo"}, "c": [1, "food"]}));
+    let search = JsonTreeBackgroundSearch::new();
+
+    let run_frame = |search_term: &str| {
+        let mut result = (String::new(), vec![]);
+        let _ = ctx.run(Default::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                search.request(ctx, &document, search_term, SearchOptions::default());
+                let snapshot = document.load();
+                let response = JsonTree::new("id", &*snapshot)
+                    .default_expand(DefaultExpand::SearchResults(""))
+                    .background_search(&search)
+                    .record_snapshot(true)
+                    .show(ui);
+                result = (
+                    response.snapshot().unwrap().to_string(),
+                    response.search_match_pointers().to_vec(),
+                );
+            });
+        });
+        result
+    };
+    let settle = |search_term: &str| {
+        let start = std::time::Instant::now();
+        run_frame(search_term);
+        while search.is_searching() {
+            assert!(start.elapsed() < Duration::from_secs(10));
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        // Apply the completed search, then show the matches it revealed.
+        run_frame(search_term);
+        run_frame(search_term)
+    };
+
+    let (snapshot, pointers) = settle("foo");
+    assert_eq!(pointers, vec!["/a/b", "/c/1"]);
+    assert_eq!(
+        snapshot,
+        [
+            "{",
+            "  \"a\": {",
+            "    \"b\": \"«foo»\"",
+            "  }",
+            "  \"c\": [",
+            "    0: 1",
+            "    1: \"«foo»d\"",
+            "  ]",
+            "}",
+            ""
+        ]
+        .join("\n")
+    );
+
+    let (_, pointers) = settle("food");
+    assert_eq!(pointers, vec!["/c/1"]);
+    assert!(!search.is_searching());
+}
+
+#[test]
+fn json_tree_search_document_changes() {
+    use egui_json_tree::JsonTreeDocument;
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+    let document = JsonTreeDocument::new(json!({"logs": ["ok", "error 1"], "z": "error 2"}));
+
+    let run_frame = || {
+        let mut pointers = vec![];
+        let _ = ctx.run(Default::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let (snapshot, version) = document.load_with_version();
+                let response = JsonTree::new("id", &*snapshot)
+                    .default_expand(DefaultExpand::SearchResults("error"))
+                    .value_version(version)
+                    .document_changes(&document)
+                    .show(ui);
+                pointers = response.search_match_pointers().to_vec();
+            });
+        });
+        pointers
+    };
+
+    assert_eq!(run_frame(), vec!["/logs/1", "/z"]);
+
+    document.update_at("/logs/2", |value| {
+        *value = json!({"logs": ["ok", "error 1", "error 3"], "z": "error 2"});
+    });
+    assert_eq!(run_frame(), vec!["/logs/1", "/logs/2", "/z"]);
+
+    document.update_at("/logs/1", |value| {
+        *value = json!({"logs": ["ok", "fine", "error 3"], "z": "error 2"});
+    });
+    assert_eq!(run_frame(), vec!["/logs/2", "/z"]);
+
+    // Several changes between frames, including an object key that matches.
+    document.update_at("/error", |value| {
+        *value = json!({"error": 1, "logs": ["ok", "fine", "error 3"], "z": "error 2"});
+    });
+    document.update_at("/z", |value| {
+        *value = json!({"error": 1, "logs": ["ok", "fine", "error 3"]});
+    });
+    assert_eq!(run_frame(), vec!["/error", "/logs/2"]);
+
+    document.update(|value| *value = json!({"error": 1, "logs": ["error 4"]}));
+    assert_eq!(run_frame(), vec!["/error", "/logs/0"]);
+}
+
+#[test]
+fn json_tree_severity_styling() {
+    use egui::{Rect, Shape};
+    use egui_json_tree::JsonTreeSeverityStyling;
+
+    let value = json!({
+        "logs": [
+            {"level": "ERROR", "message": "timeout"},
+            {"level": "info"},
+            {"severity": "warn"},
+            "error"
+        ],
+        "level": "error"
+    });
+    let severity_styling = JsonTreeSeverityStyling::default();
+    let tint_of = |severity: &str| {
+        severity_styling
+            .tints
+            .iter()
+            .find(|(name, _)| name == severity)
+            .unwrap()
+            .1
+    };
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+    let mut message_rect = None;
+    let output = ctx.run(Default::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let response = JsonTree::new("id", &value)
+                .default_expand(DefaultExpand::All)
+                .style(JsonTreeStyle::new().severity_styling(Some(severity_styling.clone())))
+                .show(ui);
+            message_rect = response.rect_of("/logs/0/message");
+        });
+    });
+    let tinted = |tint| {
+        output
+            .shapes
+            .iter()
+            .filter_map(|clipped| match &clipped.shape {
+                Shape::Rect(rect) if rect.fill == tint => Some(rect.rect),
+                _ => None,
+            })
+            .collect::<Vec<Rect>>()
+    };
+
+    // Only the log entries within the array are tinted, including every row of an expanded entry.
+    let error_rects = tinted(tint_of("error"));
+    assert_eq!(error_rects.len(), 1);
+    assert!(error_rects[0].contains_rect(message_rect.unwrap()));
+    assert_eq!(tinted(tint_of("warn")).len(), 1);
+}
+
+#[test]
+fn json_tree_expand_and_collapse_pointer() {
+    let value = json!({"a": {"b": {"c": 1}}, "d": [1]});
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+    let time = Cell::new(0.0);
+    let run_frame = |action: &dyn Fn(&Context, &egui_json_tree::JsonTreeResponse)| {
+        let mut visible = vec![];
+        // Let any expand/collapse animation finish.
+        time.set(time.get() + 1.0);
+        let input = egui::RawInput {
+            time: Some(time.get()),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let response = JsonTree::new("id", &value)
+                    .default_expand(DefaultExpand::ToLevel(0))
+                    .show(ui);
+                visible = ["/a", "/a/b", "/a/b/c", "/d", "/d/0"]
+                    .into_iter()
+                    .filter(|pointer| response.rect_of(pointer).is_some())
+                    .collect::<Vec<_>>();
+                action(ctx, &response);
+            });
+        });
+        visible
+    };
+
+    assert_eq!(
+        run_frame(&|ctx, response| response.expand_pointer(ctx, "/a/b/c")),
+        ["/a", "/d"]
+    );
+    assert_eq!(
+        run_frame(&|ctx, response| response.collapse_pointer(ctx, "/a")),
+        ["/a", "/a/b", "/a/b/c", "/d"]
+    );
+    // Collapsing an array/object keeps the expanded state of its descendants.
+    assert_eq!(
+        run_frame(&|ctx, response| response.expand_pointer(ctx, "/a")),
+        ["/a", "/d"]
+    );
+    assert_eq!(run_frame(&|_, _| {}), ["/a", "/a/b", "/a/b/c", "/d"]);
+}
+
+#[cfg(feature = "persistence")]
+#[test]
+fn json_tree_expansion_state() {
+    use egui_json_tree::{JsonTreeExpansionState, JsonTreeResponse};
+
+    let value = json!({"a": {"b": [1]}, "c": {"d": 1}});
+    let run_frame = |ctx: &Context,
+                     expansion_state: Option<&JsonTreeExpansionState>,
+                     action: &dyn Fn(&Context, &JsonTreeResponse)| {
+        let mut frame = None;
+        // Let any expand/collapse animation finish.
+        let input = egui::RawInput {
+            time: Some(ctx.input(|i| i.time) + 1.0),
+            ..Default::default()
+        };
+        let _ = ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let mut tree = JsonTree::new("id", &value)
+                    .default_expand(DefaultExpand::ToLevel(1))
+                    .record_snapshot(true);
+                if let Some(expansion_state) = expansion_state {
+                    tree = tree.restore_expansion_state(expansion_state);
+                }
+                let response = tree.show(ui);
+                frame = Some((
+                    response.snapshot().unwrap().to_string(),
+                    response.expansion_state(ctx, &value),
+                ));
+                action(ctx, &response);
+            });
+        });
+        frame.unwrap()
+    };
+
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+    run_frame(&ctx, None, &|ctx, response| {
+        response.expand_pointer(ctx, "/a/b");
+        response.collapse_pointer(ctx, "/c");
+    });
+    let (_, saved) = run_frame(&ctx, None, &|_, _| {});
+    assert_eq!(
+        saved,
+        JsonTreeExpansionState {
+            expanded: ["", "/a", "/a/b"].map(str::to_owned).into(),
+            collapsed: ["/c"].map(str::to_owned).into(),
+        }
+    );
+
+    // The state is restored in a new context, e.g. after restarting the app.
+    let expected = [
+        "{",
+        "  \"a\": {",
+        "    \"b\": [",
+        "      0: 1",
+        "    ]",
+        "  }",
+        "  \"c\": {...}",
+        "}",
+        "",
+    ]
+    .join("\n");
+    let ctx = Context::default();
+    ctx.set_fonts(FontDefinitions::empty());
+    let (snapshot, _) = run_frame(&ctx, Some(&saved), &|ctx, response| {
+        response.collapse_pointer(ctx, "/a/b");
+    });
+    assert_eq!(snapshot, expected);
+
+    // It is only restored once, so that arrays/objects can be toggled afterwards.
+    let (_, expansion_state) = run_frame(&ctx, Some(&saved), &|_, _| {});
+    assert_eq!(
+        expansion_state.collapsed,
+        ["/a/b", "/c"].map(str::to_owned).into()
+    );
+}
+
+#[test]
+fn json_tree_low_cost() {
+    let value = json!({"a": [1, 2, 3]});
+    let show = |low_cost: bool| {
+        let ctx = Context::default();
+        ctx.set_fonts(FontDefinitions::empty());
+        let mut frames = vec![];
+        for pointer in ["", "/a", "/a"] {
+            let _ = ctx.run(Default::default(), |ctx| {
+                CentralPanel::default().show(ctx, |ui| {
+                    let response = JsonTree::new("id", &value)
+                        .default_expand(DefaultExpand::None)
+                        .numeric_summaries(true)
+                        .style(JsonTreeStyle::new().low_cost(low_cost))
+                        .record_snapshot(true)
+                        .show(ui);
+                    frames.push((
+                        response.snapshot().unwrap().to_string(),
+                        response.rect_of("/a/2").is_some(),
+                    ));
+                    response.expand_pointer(ctx, pointer);
+                });
+            });
+        }
+        frames
+    };
+
+    let frames = show(true);
+    assert_eq!(frames[0].0, "{...}\n");
+    assert_eq!(frames[1].0, "{\n  \"a\": [...]\n}\n");
+    // Expanding is not animated, so the last element is visible straight away.
+    assert!(frames[2].1);
+
+    let frames = show(false);
+    assert_eq!(frames[0].0, "{ \"a\": [...] }\n");
+    assert!(frames[1].0.contains("// count 3"));
+    assert!(!frames[2].1);
+}
+
+#[test]
+fn json_tree_search_history() {
+    let value = json!({"level": "error", "message": "timeout"});
+    let ctx = Context::default();
+    let show = |search_term: &str| {
+        let mut history = vec![];
+        let _ = ctx.run(Default::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let response = JsonTree::new("id", &value)
+                    .default_expand(DefaultExpand::SearchResults(search_term))
+                    .show(ui);
+                history = response.search_history().to_vec();
+                if search_term == "clear" {
+                    response.clear_search_history(ctx);
+                }
+            });
+        });
+        history
+    };
+
+    assert!(show("").is_empty());
+    show("t");
+    show("time");
+    assert_eq!(show("timeout"), ["timeout"]);
+    assert_eq!(show("error"), ["error", "timeout"]);
+    assert_eq!(show(""), ["error", "timeout"]);
+    assert_eq!(show("timeout"), ["timeout", "error"]);
+
+    show("clear");
+    assert!(show("").is_empty());
+}
+
+#[test]
+fn json_tree_empty_placeholder() {
+    let reason_for = |value: &Value, search_term: &str| {
+        let reason = Cell::new(None);
+        egui::__run_test_ui(|ui| {
+            JsonTree::new("id", value)
+                .search_term(search_term)
+                .filter_search_results(true)
+                .on_empty(|_, r| reason.set(Some(r)))
+                .show(ui);
+        });
+        reason.get()
+    };
+
+    assert_eq!(
+        reason_for(&json!(null), ""),
+        Some(JsonTreeEmptyReason::Null)
+    );
+    assert_eq!(
+        reason_for(&json!([]), ""),
+        Some(JsonTreeEmptyReason::NoEntries)
+    );
+    assert_eq!(
+        reason_for(&json!({}), "foo"),
+        Some(JsonTreeEmptyReason::NoEntries)
+    );
+    assert_eq!(
+        reason_for(&json!({"bar": 1}), "foo"),
+        Some(JsonTreeEmptyReason::NoSearchResults)
+    );
+    assert_eq!(reason_for(&json!({"foo": 1}), "foo"), None);
+    assert_eq!(reason_for(&json!({"bar": 1}), ""), None);
+    assert_eq!(reason_for(&json!(false), ""), None);
+}